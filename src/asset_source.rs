@@ -0,0 +1,156 @@
+//! Where the game's files come from.
+//!
+//! Normally everything is read from the `assets/` folder next to the binary. With the
+//! `embed-assets` feature every file in [`ASSET_FILES`] is compiled into the binary instead, so a
+//! release build runs from an empty directory. That costs binary size: the files are stored as
+//! they are on disk (around 120 KiB today), and every texture or sound added later grows the
+//! binary by its full size. Edits to embedded files also need a rebuild, so hot reload only sees
+//! files read from disk.
+
+use std::io;
+
+use bevy::{asset::AssetPath, prelude::*};
+
+/// Registers the embedded files when the `embed-assets` feature is on, otherwise does nothing
+#[cfg_attr(not(feature = "embed-assets"), allow(unused_variables))]
+pub fn asset_source_plugin(app: &mut App) {
+    #[cfg(feature = "embed-assets")]
+    app.add_systems(
+        PreStartup,
+        embedded::register_embedded_files.before(crate::load_assets),
+    );
+}
+
+/// Files are looked up relative to this folder when they aren't embedded
+pub const ASSET_DIR: &str = "assets";
+
+macro_rules! asset_files {
+    ($($path:literal),* $(,)?) => {
+        /// Every file under [`ASSET_DIR`] the game reads, relative to it. Anything loaded through
+        /// [`asset_path`] or [`read_text`] has to be listed here to be embedded.
+        pub const ASSET_FILES: &[&str] = &[$($path),*];
+
+        #[cfg(feature = "embed-assets")]
+        const EMBEDDED_BYTES: &[&[u8]] = &[$(include_bytes!(concat!("../assets/", $path))),*];
+    };
+}
+
+asset_files![
+    "config.ron",
+    "locale/en.ron",
+    "locale/de.ron",
+    "waves/01.ron",
+    "waves/02.ron",
+    "waves/03.ron",
+    "waves/04.ron",
+    "waves/05.ron",
+    "kenney-space/PNG/playerShip1_orange.png",
+    "kenney-space/PNG/Lasers/laserRed08.png",
+    "kenney-space/PNG/Parts/turretBase_small.png",
+    "kenney-space/PNG/Effects/shield2.png",
+    "kenney-space/PNG/Effects/shield3.png",
+    "kenney-space/PNG/Meteors/meteorGrey_big1.png",
+    "kenney-space/PNG/Meteors/meteorGrey_big2.png",
+    "kenney-space/PNG/Meteors/meteorGrey_big3.png",
+    "kenney-space/PNG/Meteors/meteorGrey_big4.png",
+    "kenney-space/PNG/Meteors/meteorGrey_med1.png",
+    "kenney-space/PNG/Meteors/meteorGrey_med2.png",
+    "kenney-space/PNG/Meteors/meteorGrey_small1.png",
+    "kenney-space/PNG/Meteors/meteorGrey_small2.png",
+    "kenney-space/PNG/Meteors/meteorBrown_big1.png",
+    "kenney-space/PNG/Meteors/meteorBrown_big2.png",
+    "kenney-space/PNG/Meteors/meteorBrown_big3.png",
+    "kenney-space/PNG/Meteors/meteorBrown_big4.png",
+    "kenney-space/PNG/Meteors/meteorBrown_med1.png",
+    "kenney-space/PNG/Meteors/meteorBrown_med3.png",
+    "kenney-space/PNG/Meteors/meteorBrown_small1.png",
+    "kenney-space/PNG/Meteors/meteorBrown_small2.png",
+    "kenney-space/PNG/Power-ups/powerupBlue_star.png",
+    "kenney-space/PNG/Power-ups/bolt_gold.png",
+    "kenney-space/PNG/Power-ups/powerupRed_star.png",
+    "kenney-space/PNG/Power-ups/powerupGreen_bolt.png",
+    "kenney-space/PNG/Power-ups/powerupYellow_star.png",
+    "kenney-space/PNG/Power-ups/star_gold.png",
+    "kenney-space/Bonus/sfx_laser1.ogg",
+    "kenney-space/Bonus/sfx_zap.ogg",
+    "kenney-space/Bonus/sfx_twoTone.ogg",
+    "kenney-space/Bonus/sfx_shieldDown.ogg",
+];
+
+/// Where to load the asset at `path`, relative to [`ASSET_DIR`], from. Spawn code passes the
+/// same path whether or not assets are embedded.
+pub fn asset_path(path: &str) -> AssetPath<'static> {
+    if !cfg!(feature = "embed-assets") {
+        return AssetPath::from(path.to_string());
+    }
+
+    if !ASSET_FILES.contains(&path) {
+        error!("{path} is not in ASSET_FILES, it won't be found in the embedded assets");
+    }
+    AssetPath::from(format!("embedded://{path}"))
+}
+
+/// Reads the text file at `path`, relative to [`ASSET_DIR`]
+pub fn read_text(path: &str) -> io::Result<String> {
+    #[cfg(feature = "embed-assets")]
+    return ASSET_FILES
+        .iter()
+        .position(|file| *file == path)
+        .map(|index| String::from_utf8_lossy(EMBEDDED_BYTES[index]).into_owned())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "not an embedded file"));
+
+    #[cfg(not(feature = "embed-assets"))]
+    std::fs::read_to_string(std::path::Path::new(ASSET_DIR).join(path))
+}
+
+#[cfg(feature = "embed-assets")]
+mod embedded {
+    use std::path::{Path, PathBuf};
+
+    use bevy::{asset::io::embedded::EmbeddedAssetRegistry, prelude::*};
+
+    use super::{ASSET_DIR, ASSET_FILES, EMBEDDED_BYTES};
+
+    /// Makes every embedded file loadable through the `embedded://` source
+    pub fn register_embedded_files(registry: Res<EmbeddedAssetRegistry>) {
+        for (path, bytes) in ASSET_FILES.iter().zip(EMBEDDED_BYTES) {
+            let full_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join(ASSET_DIR)
+                .join(path);
+            registry.insert_asset(full_path, Path::new(path), *bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DAMAGED_METEOR_SPRITES, METEOR_SPRITES, config::CONFIG_PATH, locale::LOCALE_DIR,
+        powerups::PowerUpKind, settings::Language, waves::WAVE_FILES,
+    };
+
+    #[test]
+    fn every_loaded_file_is_listed_and_exists() {
+        let locales = Language::ALL.map(|language| format!("{LOCALE_DIR}/{}.ron", language.code()));
+        let used = METEOR_SPRITES
+            .iter()
+            .chain(DAMAGED_METEOR_SPRITES)
+            .copied()
+            .chain(PowerUpKind::ALL.map(PowerUpKind::sprite_path))
+            .chain(locales.iter().map(String::as_str))
+            .chain(WAVE_FILES.iter().copied())
+            .chain([CONFIG_PATH]);
+
+        for path in used {
+            assert!(
+                ASSET_FILES.contains(&path),
+                "{path} is missing from ASSET_FILES"
+            );
+        }
+        for path in ASSET_FILES {
+            let on_disk = std::path::Path::new(ASSET_DIR).join(path);
+            assert!(on_disk.is_file(), "{} doesn't exist", on_disk.display());
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::f32::consts::PI;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    AsteroidConfig, AsteroidSize, GameCleanup, GameMode, GameRng, GameStats, HudRoot, MainCamera,
+    config::{BeltConfig, GameConfig},
+    indicators::draw_edge_indicator,
+    locale::{Locale, LocalizedText},
+    palette::Palette,
+    playfield::{PlayBounds, view_half_extents},
+    spawn_asteroid,
+};
+
+pub fn belt_plugin(app: &mut App) {
+    app.init_resource::<AsteroidBelt>();
+
+    app.add_systems(
+        Update,
+        (tick_asteroid_belt, flash_belt_warning).run_if(resource_equals(GameMode::Endless)),
+    );
+}
+
+/// Which side of the play area a belt streams in from
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Edge {
+    pub const ALL: [Edge; 4] = [Edge::Top, Edge::Bottom, Edge::Left, Edge::Right];
+
+    /// Unit vector pointing from the center of the play area out through this edge
+    pub fn outward(self) -> Vec2 {
+        match self {
+            Edge::Top => Vec2::Y,
+            Edge::Bottom => Vec2::NEG_Y,
+            Edge::Left => Vec2::NEG_X,
+            Edge::Right => Vec2::X,
+        }
+    }
+
+    /// Random point along this edge of a box centered on the origin
+    pub fn random_point(self, rng: &mut impl Rng, half_extents: Vec2) -> Vec2 {
+        let outward = self.outward();
+        let along = outward.perp() * outward.perp().abs().dot(half_extents);
+        outward * outward.abs().dot(half_extents) + along * rng.random_range(-1.0..1.0)
+    }
+}
+
+#[derive(Debug)]
+pub enum BeltPhase {
+    Idle,
+    Warning {
+        timer: Timer,
+        edge: Edge,
+    },
+    Shower {
+        spawn_timer: Timer,
+        remaining: u32,
+        edge: Edge,
+    },
+    /// Every rock is in, the score multiplier holds until `timer` runs out
+    Tail {
+        timer: Timer,
+    },
+}
+
+/// Periodic dense asteroid shower in endless mode
+#[derive(Resource)]
+pub struct AsteroidBelt {
+    pub next_belt: Timer,
+    pub phase: BeltPhase,
+}
+
+impl AsteroidBelt {
+    /// Idle, with the first belt a full interval away
+    pub fn new(config: &BeltConfig) -> Self {
+        Self {
+            next_belt: Timer::from_seconds(config.interval_secs, TimerMode::Once),
+            phase: BeltPhase::Idle,
+        }
+    }
+}
+
+impl FromWorld for AsteroidBelt {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(&world.resource::<GameConfig>().belt)
+    }
+}
+
+/// Marker for the warning banner shown before a belt arrives
+#[derive(Component)]
+pub struct BeltBanner;
+
+pub fn tick_asteroid_belt(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut belt: ResMut<AsteroidBelt>,
+    mut game_stats: ResMut<GameStats>,
+    bounds: Res<PlayBounds>,
+    banners: Query<Entity, With<BeltBanner>>,
+    palette: Res<Palette>,
+    locale: Res<Locale>,
+    hud: Single<Entity, With<HudRoot>>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    let config = &config.belt;
+    let rng = &mut game_rng.rng;
+    let belt = &mut *belt;
+
+    match &mut belt.phase {
+        BeltPhase::Idle => {
+            belt.next_belt.tick(time.delta());
+
+            if !belt.next_belt.is_finished()
+                || game_stats.stopwatch.elapsed_secs() < config.grace_secs
+            {
+                return;
+            }
+
+            let edge = Edge::ALL[rng.random_range(0..Edge::ALL.len())];
+            belt.phase = BeltPhase::Warning {
+                timer: Timer::from_seconds(config.warning_secs, TimerMode::Once),
+                edge,
+            };
+
+            cmds.spawn((
+                BeltBanner,
+                LocalizedText("belt.warning"),
+                Text::new(locale.text("belt.warning")),
+                TextColor(palette.warning),
+                palette.hud_shadow(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: percent(20),
+                    width: percent(100),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                TextLayout::new_with_justify(Justify::Center),
+                GameCleanup,
+                ChildOf(*hud),
+            ));
+        }
+        BeltPhase::Warning { timer, edge } => {
+            timer.tick(time.delta());
+            if !timer.is_finished() {
+                return;
+            }
+
+            for banner in banners {
+                cmds.entity(banner).try_despawn();
+            }
+
+            let count = rng.random_range(config.min_count..=config.max_count);
+            let interval = config.duration_secs / count as f32;
+            belt.phase = BeltPhase::Shower {
+                spawn_timer: Timer::from_seconds(interval, TimerMode::Repeating),
+                remaining: count,
+                edge: *edge,
+            };
+            game_stats.belt_multiplier = config.score_multiplier;
+        }
+        BeltPhase::Shower {
+            spawn_timer,
+            remaining,
+            edge,
+        } => {
+            spawn_timer.tick(time.delta());
+
+            let bounds = bounds.wrap();
+            let outward = edge.outward();
+            // Everything in the belt flies away from the edge it came in through
+            let heading = f32::atan2(outward.x, -outward.y);
+
+            for _ in 0..spawn_timer.times_finished_this_tick() {
+                if *remaining == 0 {
+                    break;
+                }
+                *remaining -= 1;
+
+                // Spread the rocks along the entry edge, just outside the play area
+                let pos = edge.random_point(rng, bounds);
+
+                let rotation =
+                    heading + rng.random_range(-config.heading_jitter..=config.heading_jitter);
+                let speed = rng.random_range(config.min_speed..config.max_speed);
+                let angvel = rng.random_range(-PI..PI);
+                cmds.run_system_cached_with(
+                    spawn_asteroid,
+                    AsteroidConfig {
+                        location: pos,
+                        heading: rotation,
+                        speed,
+                        angvel,
+                        size: AsteroidSize::Small,
+                        group: None,
+                    },
+                );
+            }
+
+            if *remaining == 0 {
+                belt.phase = BeltPhase::Tail {
+                    timer: Timer::from_seconds(config.score_tail_secs, TimerMode::Once),
+                };
+                belt.next_belt = Timer::from_seconds(config.interval_secs, TimerMode::Once);
+            }
+        }
+        BeltPhase::Tail { timer } => {
+            // The next belt is counted from the end of the shower, not of the tail
+            belt.next_belt.tick(time.delta());
+            timer.tick(time.delta());
+            if timer.is_finished() {
+                belt.phase = BeltPhase::Idle;
+                game_stats.belt_multiplier = 1.0;
+            }
+        }
+    }
+}
+
+/// Flashes arrows along the edge the belt is about to come through
+pub fn flash_belt_warning(
+    belt: Res<AsteroidBelt>,
+    bounds: Res<PlayBounds>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Transform, &Projection), With<MainCamera>>,
+    time: Res<Time>,
+    palette: Res<Palette>,
+    mut gizmos: Gizmos,
+) {
+    let BeltPhase::Warning { edge, .. } = belt.phase else {
+        return;
+    };
+
+    if (time.elapsed_secs() * 4.0).fract() > 0.5 {
+        return;
+    }
+
+    let (camera, projection) = *camera;
+    let half_extents = bounds.half_extents;
+    let outward = edge.outward();
+    let along = outward.perp() * outward.perp().abs().dot(half_extents);
+
+    for i in -2..=2 {
+        let target = outward * outward.abs().dot(half_extents) + along * (i as f32 / 3.0);
+        draw_edge_indicator(
+            &mut gizmos,
+            camera.translation.xy(),
+            view_half_extents(&window, projection),
+            target,
+            palette.warning,
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::{
+    post_process::bloom::Bloom,
+    prelude::*,
+    render::{renderer::RenderAdapterInfo, settings::Backends, view::Hdr},
+};
+
+use crate::{MainCamera, settings::Settings};
+
+/// HDR bloom on the main camera, so lasers and blasts glow while everything else stays flat.
+///
+/// Glowing colors come from the [`Palette`](crate::palette::Palette), which pushes them past
+/// 1.0 with [`glow`] whenever bloom is on. UI is drawn after post-processing and never blooms.
+pub fn bloom_plugin(app: &mut App) {
+    app.add_systems(Startup, disable_unsupported_bloom);
+    app.add_systems(
+        Update,
+        apply_bloom.run_if(resource_changed::<Settings>.or(any_match_filter::<Added<MainCamera>>)),
+    );
+}
+
+/// Bloom intensity with the settings slider all the way up. The default 30% lands on Bevy's
+/// natural preset.
+pub const MAX_BLOOM_INTENSITY: f32 = 0.5;
+/// How far past full brightness glowing colors are pushed
+pub const GLOW_BRIGHTNESS: f32 = 4.0;
+
+/// Brightens `color` past 1.0 so it blooms, keeping its alpha
+pub fn glow(color: Color) -> Color {
+    let linear = color.to_linear();
+    Color::LinearRgba(LinearRgba {
+        alpha: linear.alpha,
+        ..linear * GLOW_BRIGHTNESS
+    })
+}
+
+/// Bevy's bloom doesn't run on WebGL2 and the GL backend, turn the setting off there instead
+/// of rendering to an HDR target for nothing
+pub fn disable_unsupported_bloom(
+    adapter: Option<Res<RenderAdapterInfo>>,
+    mut settings: ResMut<Settings>,
+) {
+    let Some(adapter) = adapter else {
+        return;
+    };
+
+    if Backends::from(adapter.backend) == Backends::GL && settings.bloom.0 > 0 {
+        warn!("Bloom isn't supported on the GL backend, turning it off");
+        settings.bloom.0 = 0;
+    }
+}
+
+/// Switches the main camera between HDR with bloom and a plain render target
+pub fn apply_bloom(
+    settings: Res<Settings>,
+    camera: Single<Entity, With<MainCamera>>,
+    mut cmds: Commands,
+) {
+    if settings.bloom.0 == 0 {
+        cmds.entity(*camera).remove::<(Bloom, Hdr)>();
+        return;
+    }
+
+    cmds.entity(*camera).insert(Bloom {
+        intensity: MAX_BLOOM_INTENSITY * settings.bloom.0 as f32 / 100.0,
+        ..Bloom::NATURAL
+    });
+}
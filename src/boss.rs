@@ -0,0 +1,297 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    Asteroid, AsteroidConfig, AsteroidSize, GameMode, GameRng, PlayerShip,
+    config::GameConfig,
+    palette::Palette,
+    physics::{CircleCollider, TimeScale, Velocity, apply_velocity},
+    playfield::PlayBounds,
+    spawn_asteroid,
+    stealth::StealthAsteroid,
+    waves::WaveSpawner,
+};
+
+/// The mega asteroid of a boss wave doesn't just drift, it takes turns firing shard volleys at
+/// the ship and dashing at it. Both flash first so they can be dodged.
+pub fn boss_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (arm_boss, run_boss_attacks, flash_boss_telegraphs)
+            .chain()
+            .before(apply_velocity)
+            .run_if(resource_equals(GameMode::Waves)),
+    );
+}
+
+/// How fast the wind-up wobble shakes, in radians per second
+pub const BOSS_WOBBLE_FREQUENCY: f32 = 25.0;
+/// Peak sideways speed of the wobble, it shakes by this over the frequency either way
+pub const BOSS_WOBBLE_SPEED: f32 = 150.0;
+/// Seconds between flashes while an attack winds up
+pub const BOSS_FLASH_SECS: f32 = 0.1;
+/// A dash takes this long to reach full speed
+pub const BOSS_DASH_RAMP_SECS: f32 = 0.3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BossPhase {
+    Drifting,
+    /// Stopped and flashing, the volley goes off at the end
+    VolleyWindup,
+    /// Wobbling in place, then dashes at `target`
+    DashWindup {
+        target: Vec2,
+    },
+    Dashing {
+        direction: Vec2,
+    },
+    /// Sitting still after a dash
+    Recovering,
+}
+
+/// Attack state of a boss asteroid. Each phase lasts until its timer is up, except a dash,
+/// which also stops at the edge of the play area.
+#[derive(Component, Debug)]
+pub struct BossAttacks {
+    pub phase: BossPhase,
+    pub timer: Timer,
+    /// Velocity it goes back to between attacks
+    pub drift: Vec2,
+}
+
+impl BossAttacks {
+    pub fn new(drift: Vec2, idle_secs: f32) -> Self {
+        Self {
+            phase: BossPhase::Drifting,
+            timer: Timer::from_seconds(idle_secs, TimerMode::Once),
+            drift,
+        }
+    }
+
+    fn enter(&mut self, phase: BossPhase, secs: f32) {
+        self.phase = phase;
+        self.timer = Timer::from_seconds(secs, TimerMode::Once);
+    }
+}
+
+/// Directions of a volley's shards, fanned out evenly across `spread` radians around `aim`
+pub fn volley_directions(aim: Vec2, count: u32, spread: f32) -> impl Iterator<Item = Vec2> {
+    let step = if count > 1 {
+        spread / (count - 1) as f32
+    } else {
+        0.0
+    };
+    (0..count).map(move |i| Vec2::from_angle(step * i as f32 - spread / 2.0).rotate(aim))
+}
+
+/// Whether something at `pos` moving along `direction` is about to leave the play area
+pub fn leaving_bounds(pos: Vec2, direction: Vec2, radius: f32, half_extents: Vec2) -> bool {
+    let limit = (half_extents - radius).max(Vec2::ZERO);
+    (pos.x >= limit.x && direction.x > 0.0)
+        || (pos.x <= -limit.x && direction.x < 0.0)
+        || (pos.y >= limit.y && direction.y > 0.0)
+        || (pos.y <= -limit.y && direction.y < 0.0)
+}
+
+/// Turns the mega asteroid of a boss wave into the boss as it spawns
+pub fn arm_boss(
+    asteroids: Query<(Entity, &AsteroidSize, &Velocity), Added<Asteroid>>,
+    spawner: Res<WaveSpawner>,
+    config: Res<GameConfig>,
+    mut cmds: Commands,
+) {
+    if !spawner.boss {
+        return;
+    }
+
+    for (asteroid, size, vel) in asteroids {
+        if *size == AsteroidSize::Mega {
+            cmds.entity(asteroid)
+                .insert(BossAttacks::new(vel.linear, config.boss.idle_secs));
+        }
+    }
+}
+
+/// Attack timers follow the boss's [`TimeScale`], so slow motion stretches the wind-ups too
+pub fn run_boss_attacks(
+    mut bosses: Query<(
+        &mut BossAttacks,
+        &Transform,
+        &mut Velocity,
+        &CircleCollider,
+        Option<&TimeScale>,
+    )>,
+    ship: Option<Single<&Transform, With<PlayerShip>>>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    bounds: Res<PlayBounds>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    let config = &config.boss;
+    let rng = &mut game_rng.rng;
+    // With the ship gone attacks head for the middle, where it respawns
+    let ship_pos = ship.map_or(Vec2::ZERO, |ship| ship.translation.xy());
+
+    for (mut boss, tsf, mut vel, collider, time_scale) in bosses.iter_mut() {
+        let pos = tsf.translation.xy();
+        let scale = time_scale.copied().unwrap_or_default().0;
+        boss.timer.tick(time.delta().mul_f32(scale));
+        let done = boss.timer.is_finished();
+
+        match boss.phase {
+            BossPhase::Drifting => {
+                vel.linear = boss.drift;
+                if !done {
+                    continue;
+                }
+
+                if rng.random_bool(0.5) {
+                    boss.enter(BossPhase::VolleyWindup, config.volley_windup_secs);
+                } else {
+                    let limit = (bounds.half_extents - collider.radius).max(Vec2::ZERO);
+                    let target = ship_pos.clamp(-limit, limit);
+                    boss.enter(BossPhase::DashWindup { target }, config.dash_windup_secs);
+                }
+            }
+            BossPhase::VolleyWindup => {
+                vel.linear = Vec2::ZERO;
+                if !done {
+                    continue;
+                }
+
+                let aim = (ship_pos - pos).normalize_or(Vec2::NEG_Y);
+                for direction in volley_directions(aim, config.volley_count, config.volley_spread) {
+                    // Shards leave from the rim, not from inside the boss
+                    cmds.run_system_cached_with(
+                        spawn_asteroid,
+                        AsteroidConfig {
+                            location: pos + direction * collider.radius,
+                            heading: f32::atan2(-direction.x, direction.y),
+                            speed: config.volley_speed,
+                            angvel: rng.random_range(-3.0..3.0),
+                            size: AsteroidSize::Small,
+                            group: None,
+                        },
+                    );
+                }
+                boss.enter(BossPhase::Drifting, config.idle_secs);
+            }
+            BossPhase::DashWindup { target } => {
+                let side = (target - pos).perp().normalize_or_zero();
+                let wobble = (boss.timer.elapsed_secs() * BOSS_WOBBLE_FREQUENCY).cos();
+                vel.linear = side * wobble * BOSS_WOBBLE_SPEED;
+                if !done {
+                    continue;
+                }
+
+                let direction = (target - pos).normalize_or(Vec2::NEG_Y);
+                boss.enter(BossPhase::Dashing { direction }, config.dash_secs);
+            }
+            BossPhase::Dashing { direction } => {
+                if done || leaving_bounds(pos, direction, collider.radius, bounds.half_extents) {
+                    vel.linear = Vec2::ZERO;
+                    boss.enter(BossPhase::Recovering, config.recovery_secs);
+                    continue;
+                }
+
+                let ramp = (boss.timer.elapsed_secs() / BOSS_DASH_RAMP_SECS).min(1.0);
+                vel.linear = direction * config.dash_speed * ramp;
+            }
+            BossPhase::Recovering => {
+                vel.linear = Vec2::ZERO;
+                if done {
+                    boss.enter(BossPhase::Drifting, config.idle_secs);
+                }
+            }
+        }
+    }
+}
+
+/// Flashes the boss while an attack winds up, in a different color for each attack
+pub fn flash_boss_telegraphs(
+    mut bosses: Query<(&BossAttacks, &mut Sprite, Has<StealthAsteroid>)>,
+    palette: Res<Palette>,
+) {
+    for (boss, mut sprite, stealth) in bosses.iter_mut() {
+        let flash_on = ((boss.timer.elapsed_secs() / BOSS_FLASH_SECS) as u32).is_multiple_of(2);
+        let color = match boss.phase {
+            BossPhase::VolleyWindup if flash_on => palette.warning,
+            BossPhase::DashWindup { .. } if flash_on => palette.highlight,
+            _ if stealth => palette.stealth_asteroid,
+            _ => palette.asteroid_tint(boss.drift.length()),
+        };
+        // Invincible asteroids flicker through alpha, leave that alone
+        sprite.color = color.with_alpha(sprite.color.alpha());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::playfield::PlayField;
+
+    #[test]
+    fn volley_fans_out_around_the_ship() {
+        let directions: Vec<Vec2> = volley_directions(Vec2::X, 8, 1.0).collect();
+        assert_eq!(directions.len(), 8);
+        assert!((Vec2::X.angle_to(directions[0]) + 0.5).abs() < 1e-5);
+        assert!((Vec2::X.angle_to(directions[7]) - 0.5).abs() < 1e-5);
+
+        let middle = (directions[3] + directions[4]).normalize();
+        assert!(middle.distance(Vec2::X) < 1e-5);
+    }
+
+    #[test]
+    fn dashes_stop_at_the_edge() {
+        let half_extents = Vec2::new(400.0, 300.0);
+        assert!(leaving_bounds(
+            Vec2::new(350.0, 0.0),
+            Vec2::X,
+            50.0,
+            half_extents
+        ));
+        assert!(!leaving_bounds(
+            Vec2::new(350.0, 0.0),
+            Vec2::NEG_X,
+            50.0,
+            half_extents
+        ));
+        assert!(!leaving_bounds(Vec2::ZERO, Vec2::Y, 50.0, half_extents));
+    }
+
+    #[test]
+    fn slow_motion_stretches_attack_timers() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+        world.insert_resource(time);
+        world.init_resource::<GameConfig>();
+        world.insert_resource(PlayBounds::new(PlayField::Classic, &Window::default()));
+        world.insert_resource(GameRng::seeded(0));
+
+        let boss = || {
+            (
+                BossAttacks::new(Vec2::ZERO, 1.0),
+                Transform::default(),
+                Velocity::default(),
+                CircleCollider { radius: 50.0 },
+            )
+        };
+        let normal = world.spawn(boss()).id();
+        let slowed = world.spawn((boss(), TimeScale(0.4))).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(run_boss_attacks);
+        // A second and a half, only 0.6 of it for the slowed boss
+        for _ in 0..15 {
+            schedule.run(&mut world);
+        }
+
+        let phase = |ent| world.get::<BossAttacks>(ent).unwrap().phase;
+        assert_ne!(phase(normal), BossPhase::Drifting);
+        assert_eq!(phase(slowed), BossPhase::Drifting);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{f32::consts::PI, time::Duration};
+
+use bevy::{diagnostic::LogDiagnosticsPlugin, ecs::entity::Entities, prelude::*};
+
+use crate::{
+    Asteroid, GameStats, PlayerShip, ShipInput, control_ship,
+    diagnostics::GAME_DIAGNOSTICS,
+    effects::Invincible,
+    physics::{Heading, Velocity},
+    read_ship_input,
+};
+
+/// Autopilot for unattended soak runs, started with `--bot`. It drives the ship through
+/// [`ShipInput`] in place of the keyboard, so it plays through the same code a player does.
+pub fn bot_plugin(app: &mut App) {
+    if !std::env::args().any(|arg| arg == "--bot") {
+        return;
+    }
+
+    app.init_resource::<Bot>();
+    // The game's diagnostics go to the log at the same pace as the heartbeat
+    app.add_plugins(LogDiagnosticsPlugin {
+        wait_duration: Duration::from_secs_f32(BOT_HEARTBEAT_SECS),
+        filter: Some(GAME_DIAGNOSTICS.into_iter().collect()),
+        ..default()
+    });
+    app.add_systems(
+        Update,
+        (
+            drive_bot.after(read_ship_input).before(control_ship),
+            log_bot_heartbeat,
+        ),
+    );
+}
+
+/// Asteroids closer than this are fled from instead of shot at
+pub const BOT_DANGER_RADIUS: f32 = 160.0;
+/// Asteroids closer than this get a mine dropped on them while fleeing
+pub const BOT_MINE_RADIUS: f32 = 80.0;
+/// How far off target the ship may point and still fire
+pub const BOT_AIM_TOLERANCE: f32 = 0.15;
+pub const BOT_FIRE_INTERVAL_SECS: f32 = 0.25;
+pub const BOT_HEARTBEAT_SECS: f32 = 30.0;
+
+#[derive(Resource)]
+pub struct Bot {
+    pub fire_cooldown: Timer,
+    pub heartbeat: Timer,
+}
+
+impl Default for Bot {
+    fn default() -> Self {
+        Self {
+            fire_cooldown: Timer::from_seconds(BOT_FIRE_INTERVAL_SECS, TimerMode::Once),
+            heartbeat: Timer::from_seconds(BOT_HEARTBEAT_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Turns toward the nearest asteroid and shoots it, or turns away and thrusts when it's too close
+pub fn drive_bot(
+    ship: Single<(&Transform, &Heading, &Velocity), With<PlayerShip>>,
+    asteroids: Query<&Transform, (With<Asteroid>, Without<Invincible>)>,
+    time: Res<Time>,
+    mut bot: ResMut<Bot>,
+    mut input: ResMut<ShipInput>,
+) {
+    let (ship_tsf, ship_heading, ship_vel) = ship.into_inner();
+    let ship_pos = ship_tsf.translation.xy();
+    bot.fire_cooldown.tick(time.delta());
+    *input = ShipInput::default();
+
+    let Some(nearest) = asteroids
+        .iter()
+        .map(|tsf| tsf.translation.xy())
+        .min_by(|a, b| {
+            a.distance_squared(ship_pos)
+                .total_cmp(&b.distance_squared(ship_pos))
+        })
+    else {
+        return;
+    };
+
+    let to_target = nearest - ship_pos;
+    let fleeing = to_target.length() < BOT_DANGER_RADIUS;
+    let aim = if fleeing { -to_target } else { to_target };
+
+    let off_by = input.steer_toward(*ship_heading, ship_vel, aim, BOT_AIM_TOLERANCE / 2.0);
+
+    if fleeing {
+        input.thrust = off_by.abs() < PI / 2.0;
+        // No hyperspace to jump out with, a mine is the last resort
+        input.secondary = to_target.length() < BOT_MINE_RADIUS;
+    } else if off_by.abs() < BOT_AIM_TOLERANCE && bot.fire_cooldown.is_finished() {
+        input.fire = true;
+        bot.fire_cooldown.reset();
+    }
+}
+
+/// Logs entity counts and the score now and then, so a leak or overflow shows up in the log
+pub fn log_bot_heartbeat(
+    time: Res<Time<Real>>,
+    entities: &Entities,
+    asteroids: Query<(), With<Asteroid>>,
+    game_stats: Res<GameStats>,
+    mut bot: ResMut<Bot>,
+) {
+    bot.heartbeat.tick(time.delta());
+    if !bot.heartbeat.just_finished() {
+        return;
+    }
+
+    info!(
+        "Bot heartbeat after {:.0}s: {} entities, {} asteroids, score {}",
+        time.elapsed_secs(),
+        entities.len(),
+        asteroids.iter().count(),
+        game_stats.score
+    );
+}
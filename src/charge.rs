@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+
+use crate::{LASER_MUZZLE_OFFSET, palette::Palette, physics::Heading};
+
+/// Holding fire charges a bigger shot that goes off on release. The tap that starts a charge
+/// still fires a normal laser straight away.
+pub fn charge_plugin(app: &mut App) {
+    app.add_systems(Update, draw_charge_glow);
+}
+
+/// Charging longer than this doesn't make the shot any stronger
+pub const CHARGE_MAX_SECS: f32 = 1.5;
+/// Letting go before this is just a tap, nothing more is fired
+pub const CHARGE_MIN_SECS: f32 = 0.25;
+/// A charging ship accelerates this much slower
+pub const CHARGE_ACCEL_FACTOR: f32 = 0.7;
+/// Sprite and collider scale of a full charge shot
+pub const CHARGE_MAX_SCALE: f32 = 3.0;
+/// Damage of a full charge shot, a normal laser deals 1
+pub const CHARGE_MAX_DAMAGE: u32 = 3;
+/// Asteroids a full charge shot passes through before it's spent on the next one
+pub const CHARGE_MAX_PIERCE: u32 = 3;
+
+/// The ship is holding fire
+#[derive(Component, Default, Debug)]
+pub struct Charging {
+    pub elapsed: f32,
+}
+
+impl Charging {
+    /// How far the shot has charged, from 0 to 1
+    pub fn charge(&self) -> f32 {
+        (self.elapsed / CHARGE_MAX_SECS).clamp(0.0, 1.0)
+    }
+}
+
+/// A laser fired from a charge
+#[derive(Component, Debug)]
+pub struct ChargedShot {
+    /// Health taken off each asteroid hit
+    pub damage: u32,
+    /// Asteroids it can still pass through
+    pub pierce: u32,
+    /// Asteroids it has passed through, which it can't hit again while still inside them
+    pub hit: Vec<Entity>,
+}
+
+impl ChargedShot {
+    pub fn new(charge: f32) -> Self {
+        let charge = charge.clamp(0.0, 1.0);
+        Self {
+            damage: 1 + (charge * (CHARGE_MAX_DAMAGE - 1) as f32).round() as u32,
+            pierce: (charge * CHARGE_MAX_PIERCE as f32).floor() as u32,
+            hit: vec![],
+        }
+    }
+}
+
+/// Size of a shot fired at `charge` relative to a normal laser
+pub fn charged_scale(charge: f32) -> f32 {
+    1.0 + (CHARGE_MAX_SCALE - 1.0) * charge.clamp(0.0, 1.0)
+}
+
+/// Glow at the nose that grows with the charge
+pub fn draw_charge_glow(
+    ships: Query<(&Transform, &Heading, &Charging)>,
+    palette: Res<Palette>,
+    mut gizmos: Gizmos,
+) {
+    for (tsf, heading, charging) in ships {
+        if charging.elapsed < CHARGE_MIN_SECS {
+            continue;
+        }
+
+        let charge = charging.charge();
+        let nose = tsf.translation.xy() + heading.forward() * LASER_MUZZLE_OFFSET;
+        gizmos.circle_2d(
+            nose,
+            4.0 + 12.0 * charge,
+            palette.laser.with_alpha(0.4 + 0.6 * charge),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_charge_hits_hardest() {
+        let tap = ChargedShot::new(0.0);
+        assert_eq!((tap.damage, tap.pierce), (1, 0));
+
+        let full = ChargedShot::new(2.0);
+        assert_eq!(
+            (full.damage, full.pierce),
+            (CHARGE_MAX_DAMAGE, CHARGE_MAX_PIERCE)
+        );
+        assert_eq!(charged_scale(1.0), CHARGE_MAX_SCALE);
+    }
+}
@@ -0,0 +1,237 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+use rand::Rng;
+
+use crate::{
+    GameAssets, GameCleanup, GameMode, GameRng, GameStats, HudRoot, LaserShot,
+    effects::Lifetime,
+    locale::{Locale, LocalizedText},
+    mines::{ShockwaveConfig, spawn_shockwave},
+    palette::Palette,
+    physics::{
+        CircleCollider, CollisionEvent, Disabled, TimeScale, Velocity, detect_collisions,
+        random_edge_position,
+    },
+    playfield::PlayBounds,
+    pool::{Pool, Pooled, release},
+    powerups::Dilatable,
+    tutorial::tutorial_active,
+};
+
+/// A rare, fast streak that crosses the play area once. Shooting it is worth a lot of points,
+/// and it flies straight through the ship and asteroids without touching them.
+pub fn comet_plugin(app: &mut App) {
+    app.init_resource::<CometTimer>();
+
+    app.add_systems(
+        Update,
+        (
+            spawn_comets.run_if(not(resource_equals(GameMode::Sandbox)).and(not(tutorial_active))),
+            shoot_comets.after(detect_collisions),
+            (emit_comet_trails, fade_comet_trails, despawn_passed_comets).chain(),
+        ),
+    );
+}
+
+pub const COMET_INTERVAL_SECS: f32 = 60.0;
+pub const COMET_SPEED: f32 = 600.0;
+pub const COMET_SCORE: u32 = 1000;
+const COMET_SIZE: Vec2 = Vec2::new(10.0, 80.0);
+const COMET_RADIUS: f32 = 15.0;
+/// Seconds between puffs of the tail
+const TRAIL_INTERVAL_SECS: f32 = 0.02;
+const TRAIL_SECS: f32 = 0.4;
+const TRAIL_SIZE: f32 = 8.0;
+const BANNER_SECS: f32 = 1.5;
+/// Rings of the explosion when a comet is shot down, biggest first
+const EXPLOSION_RADII: [f32; 3] = [260.0, 150.0, 70.0];
+
+/// Counts down to the next comet
+#[derive(Resource)]
+pub struct CometTimer(pub Timer);
+
+impl Default for CometTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            COMET_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+#[derive(Component)]
+pub struct Comet {
+    pub trail_timer: Timer,
+}
+
+/// One puff of a comet's tail, shrinking and fading over its [`Lifetime`]
+#[derive(Component)]
+pub struct CometTrail;
+
+pub fn spawn_comets(
+    time: Res<Time>,
+    mut timer: ResMut<CometTimer>,
+    bounds: Res<PlayBounds>,
+    assets: Res<GameAssets>,
+    palette: Res<Palette>,
+    locale: Res<Locale>,
+    hud: Single<Entity, With<HudRoot>>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    timer.0.tick(time.delta());
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    let rng = &mut game_rng.rng;
+    let start = random_edge_position(rng, bounds.wrap());
+    // Aim somewhere around the middle, so it crosses the whole play area
+    let target =
+        Vec2::new(rng.random_range(-0.5..0.5), rng.random_range(-0.5..0.5)) * bounds.half_extents;
+    let direction = (target - start).normalize_or(Vec2::Y);
+    let heading = f32::atan2(-direction.x, direction.y);
+
+    cmds.spawn((
+        Comet {
+            trail_timer: Timer::from_seconds(TRAIL_INTERVAL_SECS, TimerMode::Repeating),
+        },
+        Sprite {
+            color: palette.blast,
+            custom_size: Some(COMET_SIZE),
+            ..Sprite::from_image(assets.laser.clone())
+        },
+        Transform::from_xyz(start.x, start.y, 0.5).with_rotation(Quat::from_rotation_z(heading)),
+        Velocity {
+            linear: direction * COMET_SPEED,
+            linear_drag: Vec2::ZERO,
+            angular: 0.0,
+            angular_drag: 0.0,
+        },
+        CircleCollider {
+            radius: COMET_RADIUS,
+        },
+        Dilatable,
+        GameCleanup,
+    ));
+
+    cmds.spawn((
+        LocalizedText("comet.banner"),
+        Text::new(locale.text("comet.banner")),
+        TextColor(palette.warning),
+        palette.hud_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(25),
+            width: percent(100),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+        Lifetime { secs: BANNER_SECS },
+        GameCleanup,
+        ChildOf(*hud),
+    ));
+}
+
+/// A laser touching a comet destroys both, and the comet goes up in a big flash
+pub fn shoot_comets(
+    mut collisions: MessageReader<CollisionEvent>,
+    comets: Query<&Transform, With<Comet>>,
+    lasers: Query<(), With<LaserShot>>,
+    mut game_stats: ResMut<GameStats>,
+    palette: Res<Palette>,
+    mut cmds: Commands,
+) {
+    let mut destroyed = HashSet::new();
+
+    for CollisionEvent(a, b) in collisions.read() {
+        for (laser, comet) in [(*a, *b), (*b, *a)] {
+            let Ok(tsf) = comets.get(comet) else {
+                continue;
+            };
+            if !lasers.contains(laser) || !destroyed.insert(comet) {
+                continue;
+            }
+
+            release(&mut cmds, laser);
+            cmds.entity(comet).try_despawn();
+            game_stats.award(COMET_SCORE);
+
+            let position = tsf.translation.xy();
+            for (radius, color) in
+                EXPLOSION_RADII
+                    .into_iter()
+                    .zip([palette.blast, palette.critical, palette.blast])
+            {
+                cmds.run_system_cached_with(
+                    spawn_shockwave,
+                    ShockwaveConfig {
+                        position: position.extend(1.0),
+                        radius,
+                        color,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Puffs come out of the trail pool when there are parked ones, otherwise they're spawned fresh
+pub fn emit_comet_trails(
+    mut comets: Query<(&mut Comet, &Transform, Option<&TimeScale>)>,
+    palette: Res<Palette>,
+    time: Res<Time>,
+    mut pool: ResMut<Pool<CometTrail>>,
+    parked: Query<(), (With<CometTrail>, With<Disabled>)>,
+    mut cmds: Commands,
+) {
+    for (mut comet, tsf, time_scale) in comets.iter_mut() {
+        let scale = time_scale.copied().unwrap_or_default().0;
+        comet.trail_timer.tick(time.delta().mul_f32(scale));
+
+        for _ in 0..comet.trail_timer.times_finished_this_tick() {
+            let puff = match pool.pop(&parked) {
+                Some(puff) => {
+                    cmds.entity(puff).remove::<Disabled>();
+                    puff
+                }
+                None => cmds.spawn((CometTrail, Pooled, GameCleanup)).id(),
+            };
+
+            cmds.entity(puff).insert((
+                Sprite::from_color(palette.blast, Vec2::splat(TRAIL_SIZE)),
+                // Just under the comet itself
+                Transform::from_translation(tsf.translation.with_z(0.4)),
+                Visibility::Visible,
+                Lifetime { secs: TRAIL_SECS },
+            ));
+        }
+    }
+}
+
+pub fn fade_comet_trails(
+    mut trails: Query<(&Lifetime, &mut Sprite), (With<CometTrail>, Without<Disabled>)>,
+) {
+    for (lifetime, mut sprite) in trails.iter_mut() {
+        let left = (lifetime.secs / TRAIL_SECS).clamp(0.0, 1.0);
+        sprite.color.set_alpha(left);
+        sprite.custom_size = Some(Vec2::splat(TRAIL_SIZE * left));
+    }
+}
+
+/// Comets make a single pass, once one is outside the play area and heading away it's gone
+pub fn despawn_passed_comets(
+    comets: Query<(Entity, &Transform, &Velocity), With<Comet>>,
+    bounds: Res<PlayBounds>,
+    mut cmds: Commands,
+) {
+    let bounds = bounds.wrap();
+
+    for (comet, tsf, vel) in comets {
+        let pos = tsf.translation.xy();
+        let outside = pos.x.abs() > bounds.x || pos.y.abs() > bounds.y;
+        if outside && vel.linear.dot(pos) > 0.0 {
+            cmds.entity(comet).try_despawn();
+        }
+    }
+}
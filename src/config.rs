@@ -0,0 +1,509 @@
+use bevy::prelude::*;
+use rand::{
+    Rng,
+    distr::{Distribution, weighted::WeightedIndex},
+};
+use serde::Deserialize;
+
+use crate::{AsteroidSize, METEOR_SPRITES, asset_source::read_text};
+
+/// Where gameplay tuning is read from at startup, see [`read_text`]
+pub const CONFIG_PATH: &str = "config.ron";
+
+/// Gameplay tuning values. Anything missing from the config file keeps its default.
+#[derive(Resource, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GameConfig {
+    pub belt: BeltConfig,
+    pub physics: PhysicsConfig,
+    pub asteroids: AsteroidTiers,
+    pub spawning: SpawnConfig,
+    pub combat: CombatConfig,
+    pub frenzy: FrenzyConfig,
+    pub boss: BossConfig,
+}
+
+/// How endless mode brings asteroids in
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SpawnConfig {
+    /// How long a warning marker shows before each asteroid arrives, 0 spawns them instantly
+    pub telegraph_secs: f32,
+    /// Expected asteroid spawns per second at the start of a run
+    pub spawn_rate: f32,
+    /// The rate goes up by another `spawn_rate` every this many seconds survived, starting from
+    /// twice as many
+    pub ramp_secs: f32,
+    /// The ramp stops here
+    pub max_spawn_rate: f32,
+    /// Odds of each asteroid size by score, sorted by `score_threshold`. The last row the score
+    /// has reached is used.
+    pub size_weights: Vec<SizeWeights>,
+}
+
+impl Default for SpawnConfig {
+    fn default() -> Self {
+        Self {
+            telegraph_secs: 0.75,
+            spawn_rate: 0.2,
+            ramp_secs: 10.0,
+            max_spawn_rate: 2.0,
+            size_weights: vec![
+                SizeWeights {
+                    score_threshold: 0,
+                    large: 0.8,
+                    medium: 0.15,
+                    small: 0.05,
+                },
+                SizeWeights {
+                    score_threshold: 500,
+                    large: 0.5,
+                    medium: 0.3,
+                    small: 0.2,
+                },
+                SizeWeights {
+                    score_threshold: 1500,
+                    large: 0.2,
+                    medium: 0.3,
+                    small: 0.5,
+                },
+            ],
+        }
+    }
+}
+
+impl SpawnConfig {
+    /// Spawn rate `elapsed` seconds into a run that started out at `base` spawns per second
+    pub fn ramped_rate(&self, base: f32, elapsed: f32) -> f32 {
+        let steps = (elapsed / self.ramp_secs.max(f32::EPSILON))
+            .floor()
+            .max(1.0);
+        (base * steps).min(self.max_spawn_rate)
+    }
+
+    /// Rolls the size of the next asteroid for the current score. Falls back to large if no row
+    /// applies yet or a row's weights are all zero.
+    pub fn random_size(&self, score: u64, rng: &mut impl Rng) -> AsteroidSize {
+        let Some(weights) = self
+            .size_weights
+            .iter()
+            .rev()
+            .find(|row| score >= row.score_threshold)
+        else {
+            return AsteroidSize::Large;
+        };
+
+        match WeightedIndex::new([weights.large, weights.medium, weights.small]) {
+            Ok(dist) => [
+                AsteroidSize::Large,
+                AsteroidSize::Medium,
+                AsteroidSize::Small,
+            ][dist.sample(rng)],
+            Err(_) => AsteroidSize::Large,
+        }
+    }
+}
+
+/// One row of [`SpawnConfig::size_weights`]. Weights are relative, they don't need to add up
+/// to 1.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SizeWeights {
+    pub score_threshold: u64,
+    pub large: f32,
+    pub medium: f32,
+    pub small: f32,
+}
+
+/// Timing and makeup of the asteroid belt event in endless mode
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BeltConfig {
+    /// Seconds between belts
+    pub interval_secs: f32,
+    /// How long the warning shows before the first rock arrives
+    pub warning_secs: f32,
+    /// How long the shower keeps streaming in
+    pub duration_secs: f32,
+    pub min_count: u32,
+    pub max_count: u32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    /// Max radians each rock's heading strays from the shared heading
+    pub heading_jitter: f32,
+    /// Applied to every point scored while the shower is running
+    pub score_multiplier: f32,
+    /// The multiplier keeps going this long after the last rock streams in, so the shower can
+    /// still be shot down for it
+    pub score_tail_secs: f32,
+    /// No belt starts this early into a run
+    pub grace_secs: f32,
+}
+
+impl Default for BeltConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 60.0,
+            warning_secs: 3.0,
+            duration_secs: 5.0,
+            min_count: 15,
+            max_count: 25,
+            min_speed: 250.0,
+            max_speed: 350.0,
+            heading_jitter: 0.15,
+            score_multiplier: 2.0,
+            score_tail_secs: 5.0,
+            grace_secs: 10.0,
+        }
+    }
+}
+
+/// The score-attack window after a boss wave is cleared, see [`frenzy`](crate::frenzy)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct FrenzyConfig {
+    pub duration_secs: f32,
+    /// Small asteroids in the ring spawned when the frenzy starts
+    pub ring_count: u32,
+    /// How far out the ring sits, as a fraction of the play area's half extents
+    pub ring_scale: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    /// Applied to every point scored while the frenzy lasts
+    pub score_multiplier: f32,
+    /// How long leftover frenzy asteroids take to fade away once it's over
+    pub fade_secs: f32,
+}
+
+impl Default for FrenzyConfig {
+    fn default() -> Self {
+        Self {
+            duration_secs: 10.0,
+            ring_count: 24,
+            ring_scale: 0.8,
+            min_speed: 40.0,
+            max_speed: 80.0,
+            score_multiplier: 2.0,
+            fade_secs: 1.0,
+        }
+    }
+}
+
+/// Attack patterns of the boss asteroid, see [`boss`](crate::boss)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BossConfig {
+    /// Seconds it drifts between attacks
+    pub idle_secs: f32,
+    /// How long it stops and flashes before a volley
+    pub volley_windup_secs: f32,
+    /// Small asteroids fired per volley
+    pub volley_count: u32,
+    /// Radians the volley fans out across, centered on the ship
+    pub volley_spread: f32,
+    pub volley_speed: f32,
+    /// How long it wobbles before a dash. It dashes at where the ship was when this started.
+    pub dash_windup_secs: f32,
+    pub dash_speed: f32,
+    /// Longest a dash lasts, it also stops at the edge of the play area
+    pub dash_secs: f32,
+    /// How long it sits still after a dash
+    pub recovery_secs: f32,
+}
+
+impl Default for BossConfig {
+    fn default() -> Self {
+        Self {
+            idle_secs: 4.0,
+            volley_windup_secs: 1.0,
+            volley_count: 8,
+            volley_spread: 1.6,
+            volley_speed: 160.0,
+            dash_windup_secs: 1.0,
+            dash_speed: 450.0,
+            dash_secs: 1.5,
+            recovery_secs: 3.0,
+        }
+    }
+}
+
+/// Limits for the collision frame-budget guard, and when distant asteroids are put to sleep
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PhysicsConfig {
+    /// Collision detection taking longer than this counts as over budget
+    pub collision_budget_ms: f32,
+    /// How many frames in a row have to be over (or back under) budget before switching modes
+    pub budget_frames: u32,
+    /// Asteroids farther than this from the ship can fall asleep, see [`sleep`](crate::sleep)
+    pub sleep_distance: f32,
+    /// Sleeping asteroids closer than this to the ship wake up. Kept under `sleep_distance` so
+    /// nothing flickers between the two at the boundary.
+    pub wake_distance: f32,
+    /// Only asteroids slower than this can fall asleep
+    pub sleep_speed: f32,
+    /// Width of the grid cells sleepers are woken by. Has to be wider than the biggest collider.
+    pub sleep_cell_size: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            collision_budget_ms: 4.0,
+            budget_frames: 10,
+            sleep_distance: 1500.0,
+            wake_distance: 1200.0,
+            sleep_speed: 40.0,
+            sleep_cell_size: 400.0,
+        }
+    }
+}
+
+/// How lasers fly and how their hits are resolved
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CombatConfig {
+    /// Odds of each laser hit being critical, before any power-up bonus
+    pub crit_chance: f64,
+    /// Health a critical hit takes off, a normal hit takes 1
+    pub crit_damage: u32,
+    /// Score for an asteroid finished off by a critical hit is multiplied by this
+    pub crit_score_multiplier: f32,
+    /// How much of the ship's velocity a laser carries, from 0 (none) to 1 (all of it)
+    pub projectile_velocity_inheritance: f32,
+    /// Lasers never fly slower than this, however the ship is moving
+    pub min_projectile_speed: f32,
+}
+
+impl Default for CombatConfig {
+    fn default() -> Self {
+        Self {
+            crit_chance: 0.1,
+            crit_damage: 2,
+            crit_score_multiplier: 1.5,
+            projectile_velocity_inheritance: 0.5,
+            min_projectile_speed: 250.0,
+        }
+    }
+}
+
+impl CombatConfig {
+    /// Velocity of a laser leaving the gun at `muzzle` from a ship moving at `ship`
+    pub fn projectile_velocity(&self, muzzle: Vec2, ship: Vec2) -> Vec2 {
+        let inheritance = self.projectile_velocity_inheritance.clamp(0.0, 1.0);
+        let velocity = muzzle + ship * inheritance;
+        if velocity.length() >= self.min_projectile_speed {
+            return velocity;
+        }
+
+        // Reversing can cancel the shot out entirely, it still leaves along the barrel then
+        velocity
+            .try_normalize()
+            .unwrap_or(muzzle.normalize_or_zero())
+            * self.min_projectile_speed
+    }
+}
+
+/// Everything that differs between asteroid sizes, in one place so balancing a size only means
+/// touching its entry here
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AsteroidTiers {
+    pub mega: AsteroidTier,
+    pub large: AsteroidTier,
+    pub medium: AsteroidTier,
+    pub small: AsteroidTier,
+}
+
+impl AsteroidTiers {
+    pub fn get(&self, size: AsteroidSize) -> &AsteroidTier {
+        match size {
+            AsteroidSize::Mega => &self.mega,
+            AsteroidSize::Large => &self.large,
+            AsteroidSize::Medium => &self.medium,
+            AsteroidSize::Small => &self.small,
+        }
+    }
+
+    /// Checks every tier has sprites to pick from, and only ones that are actually loaded
+    pub fn validate(&self) -> Result<(), String> {
+        for size in AsteroidSize::ALL {
+            let sprites = &self.get(size).sprites;
+            if sprites.is_empty() {
+                return Err(format!("{size:?} asteroids have no sprites"));
+            }
+            if let Some(index) = sprites.iter().find(|index| **index >= METEOR_SPRITES.len()) {
+                return Err(format!(
+                    "{size:?} asteroids use sprite {index}, only {} are loaded",
+                    METEOR_SPRITES.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for AsteroidTiers {
+    fn default() -> Self {
+        Self {
+            mega: AsteroidTier {
+                sprites: vec![0],
+                radius: 50.0,
+                scale: 2.0,
+                score: 500,
+                health: 5,
+                split_count: 4,
+                speed_multiplier: 0.5,
+            },
+            large: AsteroidTier {
+                sprites: vec![0, 1, 2, 3],
+                radius: 50.0,
+                scale: 1.0,
+                score: 10,
+                health: 1,
+                split_count: 2,
+                speed_multiplier: 1.0,
+            },
+            medium: AsteroidTier {
+                sprites: vec![4, 5],
+                radius: 30.0,
+                scale: 1.0,
+                score: 20,
+                health: 1,
+                split_count: 2,
+                speed_multiplier: 1.3,
+            },
+            small: AsteroidTier {
+                sprites: vec![6, 7],
+                radius: 20.0,
+                scale: 1.0,
+                score: 40,
+                health: 1,
+                split_count: 0,
+                speed_multiplier: 1.6,
+            },
+        }
+    }
+}
+
+/// One row of the [`AsteroidTiers`] table
+#[derive(Deserialize, Debug, Clone)]
+pub struct AsteroidTier {
+    /// Indices into [`METEOR_SPRITES`](crate::METEOR_SPRITES), one is picked per asteroid
+    pub sprites: Vec<usize>,
+    pub radius: f32,
+    /// Drawn at this scale, the collider grows and shrinks with it
+    #[serde(default = "unscaled")]
+    pub scale: f32,
+    /// Points for destroying one, before any multiplier
+    pub score: u32,
+    /// Laser hits it takes to destroy
+    pub health: u32,
+    /// How many asteroids of the next size down it breaks into
+    pub split_count: u32,
+    /// Fragments of this size fly this much faster than the asteroid they broke off
+    pub speed_multiplier: f32,
+}
+
+fn unscaled() -> f32 {
+    1.0
+}
+
+impl AsteroidTier {
+    /// Collider radius once [`scale`](Self::scale) is applied
+    pub fn scaled_radius(&self) -> f32 {
+        self.radius * self.scale
+    }
+}
+
+impl GameConfig {
+    /// Reads [`CONFIG_PATH`], falling back to defaults if it is missing or malformed
+    pub fn load() -> Self {
+        let contents = match read_text(CONFIG_PATH) {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!("Could not read {CONFIG_PATH}, using default config: {err}");
+                return Self::default();
+            }
+        };
+
+        let mut config: Self = match ron::from_str(&contents) {
+            Ok(config) => config,
+            Err(err) => {
+                error!("Could not parse {CONFIG_PATH}, using default config: {err}");
+                return Self::default();
+            }
+        };
+
+        // A bad sprite index would only panic later, the first time that size spawns
+        if let Err(err) = config.asteroids.validate() {
+            error!("Invalid asteroids in {CONFIG_PATH}, using default tiers: {err}");
+            config.asteroids = AsteroidTiers::default();
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DAMAGED_METEOR_SPRITES, LASER_SPEED, SHIP_MAX_SPEED};
+
+    /// Fires straight up from a ship moving at `ship` and checks the shot's speed is in range
+    fn assert_shot_speed(ship: Vec2) {
+        let combat = CombatConfig::default();
+        let speed = combat
+            .projectile_velocity(Vec2::Y * LASER_SPEED, ship)
+            .length();
+        let max = LASER_SPEED + combat.projectile_velocity_inheritance * SHIP_MAX_SPEED;
+        assert!(
+            (combat.min_projectile_speed..=max + 1e-3).contains(&speed),
+            "{speed} outside {}..={max}",
+            combat.min_projectile_speed
+        );
+    }
+
+    #[test]
+    fn shot_from_rest_keeps_muzzle_speed() {
+        assert_shot_speed(Vec2::ZERO);
+    }
+
+    #[test]
+    fn shot_at_full_speed_only_carries_part_of_it() {
+        assert_shot_speed(Vec2::Y * SHIP_MAX_SPEED);
+    }
+
+    #[test]
+    fn shot_while_reversing_keeps_min_speed() {
+        assert_shot_speed(Vec2::NEG_Y * SHIP_MAX_SPEED);
+
+        // Even a ship backing off as fast as the shot leaves can't stop it
+        let combat = CombatConfig {
+            projectile_velocity_inheritance: 1.0,
+            ..default()
+        };
+        let velocity = combat.projectile_velocity(Vec2::Y * LASER_SPEED, Vec2::NEG_Y * LASER_SPEED);
+        assert_eq!(velocity, Vec2::Y * combat.min_projectile_speed);
+    }
+
+    #[test]
+    fn asteroid_tiers_reference_loaded_sprites() {
+        assert_eq!(METEOR_SPRITES.len(), DAMAGED_METEOR_SPRITES.len());
+
+        // Parsed directly, since `load` would quietly swap bad tiers for the defaults
+        let shipped: GameConfig = ron::from_str(include_str!("../assets/config.ron")).unwrap();
+        for config in [GameConfig::default(), shipped] {
+            config.asteroids.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn tiers_with_missing_sprites_are_rejected() {
+        let mut tiers = AsteroidTiers::default();
+        tiers.medium.sprites.clear();
+        assert!(tiers.validate().is_err());
+
+        let mut tiers = AsteroidTiers::default();
+        tiers.small.sprites.push(METEOR_SPRITES.len());
+        assert!(tiers.validate().is_err());
+    }
+}
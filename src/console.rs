@@ -0,0 +1,335 @@
+use std::{collections::VecDeque, f32::consts::PI};
+
+use bevy::{
+    input::{
+        ButtonState, InputSystems,
+        keyboard::{Key, KeyboardInput},
+    },
+    platform::collections::HashMap,
+    prelude::*,
+};
+use rand::Rng;
+
+use crate::{
+    Asteroid, AsteroidConfig, AsteroidSize, GameStats, PlayerShip,
+    effects::Invincible,
+    physics::CollisionBudget,
+    playfield::PlayBounds,
+    powerups::{PowerUpKind, spawn_powerup},
+    spawn_asteroid,
+};
+
+/// Developer console, only compiled into debug builds. Toggled with the grave key.
+pub fn console_plugin(app: &mut App) {
+    app.init_resource::<Console>();
+    app.insert_resource(builtin_commands());
+
+    app.add_systems(Startup, spawn_console_ui);
+    app.add_systems(
+        PreUpdate,
+        (
+            read_console_input,
+            suppress_gameplay_input.run_if(|console: Res<Console>| console.open),
+        )
+            .chain()
+            .after(InputSystems),
+    );
+    app.add_systems(Update, (run_console_commands, update_console_ui).chain());
+}
+
+/// How many output lines are kept around, older ones scroll off the top
+pub const CONSOLE_HISTORY: usize = 10;
+/// How long `give shield` keeps the ship invincible
+const SHIELD_SECS: f32 = 10.0;
+/// Long enough that god mode never wears off during a session
+const GOD_MODE_SECS: f32 = 1_000_000.0;
+
+/// Runs a command against the world. Gets the words after the command name and returns the
+/// line to print, or an error line.
+pub type ConsoleHandler = Box<dyn Fn(&mut World, &[&str]) -> Result<String, String> + Send + Sync>;
+
+/// Every command the console understands, keyed by its first word
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(HashMap<&'static str, ConsoleHandler>);
+
+impl ConsoleCommands {
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        handler: impl Fn(&mut World, &[&str]) -> Result<String, String> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.0.insert(name, Box::new(handler));
+        self
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Console {
+    pub open: bool,
+    /// The line currently being typed
+    pub input: String,
+    /// Submitted lines waiting to be run
+    pub pending: Vec<String>,
+    pub history: VecDeque<String>,
+}
+
+impl Console {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.history.push_back(line.into());
+        while self.history.len() > CONSOLE_HISTORY {
+            self.history.pop_front();
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ConsoleRoot;
+
+#[derive(Component)]
+pub struct ConsoleOutput;
+
+#[derive(Component)]
+pub struct ConsoleInput;
+
+fn builtin_commands() -> ConsoleCommands {
+    let mut commands = ConsoleCommands::default();
+
+    commands
+        .register("spawn", |world, args| match args {
+            ["asteroid", count] => {
+                let count: u32 = count
+                    .parse()
+                    .map_err(|_| format!("not a number: {count}"))?;
+                spawn_asteroids(world, count);
+                Ok(format!("spawned {count} asteroids"))
+            }
+            _ => Err("usage: spawn asteroid <count>".into()),
+        })
+        .register("give", |world, args| {
+            let ship = world
+                .query_filtered::<(Entity, &Transform), With<PlayerShip>>()
+                .single(world)
+                .map(|(ent, tsf)| (ent, tsf.translation.xy()));
+            let Ok((ship, pos)) = ship else {
+                return Err("no ship".into());
+            };
+
+            // Power-ups are dropped right on the ship and collected through the normal path
+            let kind = match args {
+                ["shield"] => {
+                    world.entity_mut(ship).insert(Invincible::new(SHIELD_SECS));
+                    return Ok("shield up".into());
+                }
+                ["time"] => PowerUpKind::TimeDilation,
+                ["ammo"] => PowerUpKind::Ammo,
+                ["spread"] => PowerUpKind::Spread,
+                ["speed"] => PowerUpKind::SpeedBoost,
+                ["crit"] => PowerUpKind::Critical,
+                _ => return Err("usage: give <shield|time|ammo|spread|speed|crit>".into()),
+            };
+            world
+                .run_system_cached_with(spawn_powerup, (kind, pos))
+                .map_err(|e| e.to_string())?;
+            Ok(format!("gave {kind:?}"))
+        })
+        .register("set", |world, args| match args {
+            ["spawn_rate", value] => {
+                let value = value
+                    .parse()
+                    .map_err(|_| format!("not a number: {value}"))?;
+                world.resource_mut::<GameStats>().spawn_rate = value;
+                Ok(format!("spawn_rate = {value}"))
+            }
+            _ => Err("usage: set spawn_rate <per second>".into()),
+        })
+        .register("kill", |world, args| match args {
+            ["asteroids"] => {
+                let asteroids: Vec<Entity> = world
+                    .query_filtered::<Entity, With<Asteroid>>()
+                    .iter(world)
+                    .collect();
+                for asteroid in &asteroids {
+                    world.despawn(*asteroid);
+                }
+                Ok(format!("killed {} asteroids", asteroids.len()))
+            }
+            _ => Err("usage: kill asteroids".into()),
+        })
+        .register("budget", |world, _| {
+            let budget = world.resource::<CollisionBudget>();
+            Ok(format!(
+                "collisions took {:.2}ms, {}",
+                budget.last_frame.as_secs_f32() * 1000.0,
+                if budget.degraded {
+                    "degraded"
+                } else {
+                    "normal"
+                }
+            ))
+        })
+        .register("god", |world, args| {
+            let on = match args {
+                ["on"] => true,
+                ["off"] => false,
+                _ => return Err("usage: god <on|off>".into()),
+            };
+            let Ok(ship) = world
+                .query_filtered::<Entity, With<PlayerShip>>()
+                .single(world)
+            else {
+                return Err("no ship".into());
+            };
+
+            if on {
+                world
+                    .entity_mut(ship)
+                    .insert(Invincible::new(GOD_MODE_SECS));
+            } else {
+                world.entity_mut(ship).remove::<Invincible>();
+            }
+            Ok(format!("god mode {}", if on { "on" } else { "off" }))
+        });
+
+    commands
+}
+
+fn spawn_asteroids(world: &mut World, count: u32) {
+    let half_extents = world.resource::<PlayBounds>().half_extents;
+    let mut rng = rand::rng();
+
+    for _ in 0..count {
+        let pos = Vec2::new(
+            rng.random_range(-half_extents.x..half_extents.x),
+            rng.random_range(-half_extents.y..half_extents.y),
+        );
+        let rotation = rng.random_range(-PI..PI);
+        let speed = rng.random_range(50.0..200.0);
+        let angvel = rng.random_range(-PI..PI);
+
+        // Only fails if the system can't be initialized, which would fail every time
+        let _ = world.run_system_cached_with(
+            spawn_asteroid,
+            AsteroidConfig {
+                location: pos,
+                heading: rotation,
+                speed,
+                angvel,
+                size: AsteroidSize::Large,
+                group: None,
+            },
+        );
+    }
+}
+
+pub fn spawn_console_ui(mut cmds: Commands) {
+    cmds.spawn((
+        ConsoleRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(0),
+            width: percent(100),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(px(8)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.8)),
+        GlobalZIndex(10),
+        children![
+            (ConsoleOutput, Text::default()),
+            (ConsoleInput, Text::default()),
+        ],
+    ));
+}
+
+/// Opens and closes the console, and types into it while it's open
+pub fn read_console_input(mut keys: MessageReader<KeyboardInput>, mut console: ResMut<Console>) {
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+
+        if key.key_code == KeyCode::Backquote {
+            console.open = !console.open;
+            continue;
+        }
+
+        if !console.open {
+            continue;
+        }
+
+        match &key.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    console.pending.push(line);
+                }
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            _ => {
+                if let Some(text) = &key.text {
+                    console
+                        .input
+                        .extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+}
+
+/// Releases every key before gameplay systems see them, so typing never steers the ship
+pub fn suppress_gameplay_input(mut btn_input: ResMut<ButtonInput<KeyCode>>) {
+    btn_input.reset_all();
+}
+
+/// Runs submitted lines through the [`ConsoleCommands`] registry
+pub fn run_console_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<Console>().pending);
+    if pending.is_empty() {
+        return;
+    }
+
+    world.resource_scope(|world, commands: Mut<ConsoleCommands>| {
+        for line in pending {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let Some((name, args)) = words.split_first() else {
+                continue;
+            };
+
+            let output = match commands.0.get(name) {
+                Some(handler) => handler(world, args).unwrap_or_else(|e| format!("error: {e}")),
+                None => format!("error: unknown command `{name}`"),
+            };
+
+            let mut console = world.resource_mut::<Console>();
+            console.print(format!("> {line}"));
+            console.print(output);
+        }
+    });
+}
+
+pub fn update_console_ui(
+    console: Res<Console>,
+    mut root: Single<&mut Node, With<ConsoleRoot>>,
+    mut output: Single<&mut Text, (With<ConsoleOutput>, Without<ConsoleInput>)>,
+    mut input: Single<&mut Text, (With<ConsoleInput>, Without<ConsoleOutput>)>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    root.display = if console.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    output.0 = console
+        .history
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    input.0 = format!("> {}_", console.input);
+}
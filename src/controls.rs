@@ -0,0 +1,373 @@
+use bevy::{
+    input::{ButtonState, InputSystems, keyboard::KeyboardInput},
+    prelude::*,
+    ui::FocusPolicy,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::{Locale, LocalizedText},
+    settings::Settings,
+};
+
+/// Controls screen for rebinding the ship's keys, toggled with F4
+pub fn controls_plugin(app: &mut App) {
+    app.init_resource::<ControlsScreen>();
+
+    app.add_systems(Startup, spawn_controls_ui);
+    app.add_systems(
+        PreUpdate,
+        (
+            read_controls_input,
+            suppress_gameplay_input.run_if(|screen: Res<ControlsScreen>| screen.open),
+        )
+            .chain()
+            .after(InputSystems),
+    );
+    app.add_systems(
+        Update,
+        (
+            click_controls_buttons,
+            update_controls_ui.run_if(
+                resource_changed::<ControlsScreen>
+                    .or(resource_changed::<Settings>)
+                    .or(resource_changed::<Locale>),
+            ),
+        )
+            .chain(),
+    );
+}
+
+/// Keys the game already uses for something else, they can't be bound to an [`Action`]
+pub const RESERVED_KEYS: [KeyCode; 17] = [
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F12,
+    KeyCode::KeyV,
+    KeyCode::KeyC,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyL,
+    KeyCode::KeyP,
+    KeyCode::Backquote,
+    KeyCode::Escape,
+    KeyCode::Tab,
+];
+
+/// Everything the player can do with the ship
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Thrust,
+    Reverse,
+    RotateLeft,
+    RotateRight,
+    Fire,
+    Secondary,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Action::Thrust,
+        Action::Reverse,
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::Fire,
+        Action::Secondary,
+    ];
+
+    /// [`Locale`] key for the action's name
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Thrust => "action.thrust",
+            Action::Reverse => "action.reverse",
+            Action::RotateLeft => "action.rotate_left",
+            Action::RotateRight => "action.rotate_right",
+            Action::Fire => "action.fire",
+            Action::Secondary => "action.secondary",
+        }
+    }
+}
+
+/// Which key triggers each [`Action`], saved with the rest of the [`Settings`]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct InputBindings {
+    pub thrust: KeyCode,
+    pub reverse: KeyCode,
+    pub rotate_left: KeyCode,
+    pub rotate_right: KeyCode,
+    pub fire: KeyCode,
+    pub secondary: KeyCode,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            thrust: KeyCode::KeyW,
+            reverse: KeyCode::KeyS,
+            rotate_left: KeyCode::KeyA,
+            rotate_right: KeyCode::KeyD,
+            fire: KeyCode::Space,
+            secondary: KeyCode::KeyE,
+        }
+    }
+}
+
+/// What happened when a key was bound to an action
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rebind {
+    Bound,
+    /// The key belonged to this other action, which gets the old key instead
+    Swapped(Action),
+    /// The key is one of the [`RESERVED_KEYS`], nothing changed
+    Reserved,
+}
+
+impl InputBindings {
+    pub fn key(&self, action: Action) -> KeyCode {
+        *self.slot(action)
+    }
+
+    fn slot(&self, action: Action) -> &KeyCode {
+        match action {
+            Action::Thrust => &self.thrust,
+            Action::Reverse => &self.reverse,
+            Action::RotateLeft => &self.rotate_left,
+            Action::RotateRight => &self.rotate_right,
+            Action::Fire => &self.fire,
+            Action::Secondary => &self.secondary,
+        }
+    }
+
+    fn slot_mut(&mut self, action: Action) -> &mut KeyCode {
+        match action {
+            Action::Thrust => &mut self.thrust,
+            Action::Reverse => &mut self.reverse,
+            Action::RotateLeft => &mut self.rotate_left,
+            Action::RotateRight => &mut self.rotate_right,
+            Action::Fire => &mut self.fire,
+            Action::Secondary => &mut self.secondary,
+        }
+    }
+
+    /// Binds `key` to `action`. A key already used by another action is swapped over, so every
+    /// action always keeps a key of its own.
+    pub fn rebind(&mut self, action: Action, key: KeyCode) -> Rebind {
+        if RESERVED_KEYS.contains(&key) {
+            return Rebind::Reserved;
+        }
+
+        let old = self.key(action);
+        let clash = Action::ALL
+            .into_iter()
+            .find(|other| *other != action && self.key(*other) == key);
+
+        *self.slot_mut(action) = key;
+        match clash {
+            Some(other) => {
+                *self.slot_mut(other) = old;
+                Rebind::Swapped(other)
+            }
+            None => Rebind::Bound,
+        }
+    }
+}
+
+/// A key's name for on-screen text, letters and numbers without their `Key`/`Digit` prefix
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+#[derive(Resource, Default)]
+pub struct ControlsScreen {
+    pub open: bool,
+    /// The action waiting for its new key
+    pub listening: Option<Action>,
+    /// Result of the last rebind, shown under the list
+    pub status: String,
+}
+
+#[derive(Component)]
+pub struct ControlsRoot;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ControlsButton {
+    Rebind(Action),
+    Reset,
+}
+
+#[derive(Component)]
+pub struct ControlsStatus;
+
+pub fn spawn_controls_ui(locale: Res<Locale>, mut cmds: Commands) {
+    let button_node = Node {
+        padding: UiRect::axes(px(12), px(4)),
+        ..default()
+    };
+
+    cmds.spawn((
+        ControlsRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(20),
+            left: percent(30),
+            width: percent(40),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(4),
+            padding: UiRect::all(px(16)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.85)),
+        // Keeps clicks off the pause menu underneath
+        FocusPolicy::Block,
+        GlobalZIndex(9),
+    ))
+    .with_children(|root| {
+        root.spawn((
+            LocalizedText("controls.title"),
+            Text::new(locale.text("controls.title")),
+        ));
+
+        for action in Action::ALL {
+            root.spawn((
+                ControlsButton::Rebind(action),
+                Button,
+                Text::default(),
+                button_node.clone(),
+                BackgroundColor(Color::NONE),
+            ));
+        }
+
+        root.spawn((
+            ControlsButton::Reset,
+            Button,
+            LocalizedText("controls.reset"),
+            Text::new(locale.text("controls.reset")),
+            button_node.clone(),
+            BackgroundColor(Color::NONE),
+        ));
+        root.spawn((ControlsStatus, Text::default()));
+    });
+}
+
+/// Opens and closes the screen, and captures the next key press while listening for a binding
+pub fn read_controls_input(
+    mut keys: MessageReader<KeyboardInput>,
+    mut screen: ResMut<ControlsScreen>,
+    mut settings: ResMut<Settings>,
+    locale: Res<Locale>,
+) {
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+
+        let Some(action) = screen.listening else {
+            match key.key_code {
+                KeyCode::F4 => screen.open = !screen.open,
+                KeyCode::Escape if screen.open => screen.open = false,
+                _ => {}
+            }
+            continue;
+        };
+
+        screen.listening = None;
+        if key.key_code == KeyCode::Escape {
+            screen.status = locale.text("controls.cancelled").into();
+            continue;
+        }
+
+        let key_code = key_name(key.key_code);
+        screen.status = match settings.bindings.rebind(action, key.key_code) {
+            Rebind::Bound => {
+                locale.text_with("controls.bound", &[&locale.text(action.label()), &key_code])
+            }
+            Rebind::Swapped(other) => locale.text_with(
+                "controls.swapped",
+                &[
+                    &key_code,
+                    &locale.text(other.label()),
+                    &key_name(settings.bindings.key(other)),
+                ],
+            ),
+            Rebind::Reserved => locale.text_with("controls.reserved", &[&key_code]),
+        };
+    }
+}
+
+/// Releases every key before gameplay systems see them, so rebinding never steers the ship
+pub fn suppress_gameplay_input(mut btn_input: ResMut<ButtonInput<KeyCode>>) {
+    btn_input.reset_all();
+}
+
+pub fn click_controls_buttons(
+    buttons: Query<(&Interaction, &ControlsButton), Changed<Interaction>>,
+    mut screen: ResMut<ControlsScreen>,
+    mut settings: ResMut<Settings>,
+    locale: Res<Locale>,
+) {
+    for (interaction, button) in buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            ControlsButton::Rebind(action) => {
+                screen.listening = Some(*action);
+                screen.status =
+                    locale.text_with("controls.press_key", &[&locale.text(action.label())]);
+            }
+            ControlsButton::Reset => {
+                settings.bindings = InputBindings::default();
+                screen.listening = None;
+                screen.status = locale.text("controls.restored").into();
+            }
+        }
+    }
+}
+
+pub fn update_controls_ui(
+    screen: Res<ControlsScreen>,
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut root: Single<&mut Node, With<ControlsRoot>>,
+    mut buttons: Query<(&ControlsButton, &mut Text, &mut BackgroundColor), Without<ControlsStatus>>,
+    mut status: Single<&mut Text, With<ControlsStatus>>,
+) {
+    root.display = if screen.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    status.0 = screen.status.clone();
+
+    for (button, mut text, mut bg) in buttons.iter_mut() {
+        let ControlsButton::Rebind(action) = *button else {
+            continue;
+        };
+
+        let listening = screen.listening == Some(action);
+        let label = locale.text(action.label());
+        text.0 = if listening {
+            locale.text_with("controls.row_listening", &[&label])
+        } else {
+            let key = key_name(settings.bindings.key(action));
+            locale.text_with("controls.row", &[&label, &key])
+        };
+        bg.0 = if listening {
+            Color::WHITE.with_alpha(0.2)
+        } else {
+            Color::NONE
+        };
+    }
+}
@@ -0,0 +1,105 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    GameAssets, GameCleanup, GameRng, PlayerShip, destroy_asteroids,
+    effects::Lifetime,
+    events::AsteroidDestroyed,
+    physics::{CircleCollider, CollisionEvent, Velocity},
+    shadows::AddShadow,
+};
+
+/// Currency dropped by asteroids, kept apart from the score
+pub fn credits_plugin(app: &mut App) {
+    app.init_resource::<Credits>();
+
+    app.add_systems(
+        Update,
+        (drop_credits.after(destroy_asteroids), collect_credits),
+    );
+}
+
+/// Chance that a destroyed asteroid leaves a [`CreditPickup`] behind
+pub const CREDIT_DROP_CHANCE: f64 = 0.25;
+pub const CREDITS_PER_PICKUP: u32 = 50;
+pub const CREDIT_LIFETIME_SECS: f32 = 5.0;
+/// Pickups keep this much of the asteroid's velocity, so they drift the way it was going
+const CREDIT_DRIFT: f32 = 0.2;
+const CREDIT_SIZE: f32 = 20.0;
+
+/// Credits earned so far. Unlike the score these are meant to be spent, and carry over between
+/// waves.
+#[derive(Resource, Default, Debug)]
+pub struct Credits(pub u32);
+
+#[derive(Component)]
+pub struct CreditPickup {
+    pub value: u32,
+}
+
+pub fn drop_credits(
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    assets: Res<GameAssets>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    let rng = &mut game_rng.rng;
+
+    for asteroid in destroyed.read() {
+        if !rng.random_bool(CREDIT_DROP_CHANCE) {
+            continue;
+        }
+
+        let position = asteroid.position;
+        cmds.spawn((
+            CreditPickup {
+                value: CREDITS_PER_PICKUP,
+            },
+            Sprite {
+                custom_size: Some(Vec2::splat(CREDIT_SIZE)),
+                ..Sprite::from_image(assets.credit.clone())
+            },
+            AddShadow,
+            Transform::from_xyz(position.x, position.y, 0.0),
+            CircleCollider {
+                radius: CREDIT_SIZE,
+            },
+            Velocity {
+                linear: asteroid.velocity * CREDIT_DRIFT,
+                linear_drag: Vec2::ZERO,
+                angular: rng.random_range(PI..2.0 * PI),
+                angular_drag: 0.0,
+            },
+            Lifetime {
+                secs: CREDIT_LIFETIME_SECS,
+            },
+            GameCleanup,
+        ));
+    }
+}
+
+pub fn collect_credits(
+    mut collisions: MessageReader<CollisionEvent>,
+    pickups: Query<&CreditPickup>,
+    ship: Single<Entity, With<PlayerShip>>,
+    mut credits: ResMut<Credits>,
+    mut cmds: Commands,
+) {
+    for collision in collisions.read() {
+        let pickup = if collision.0 == *ship {
+            collision.1
+        } else if collision.1 == *ship {
+            collision.0
+        } else {
+            continue;
+        };
+
+        let Ok(pickup_value) = pickups.get(pickup) else {
+            continue;
+        };
+        credits.0 += pickup_value.value;
+        cmds.entity(pickup).try_despawn();
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GameMode, GameRng,
+    events::PlayerDied,
+    handle_collisions,
+    locale::Locale,
+    mutators::RunModifiers,
+    pause::{PauseButton, PauseMenu, restart_run},
+    setup_scene,
+    tutorial::{Tutorial, TutorialPhase, start_tutorial},
+};
+
+/// Daily challenge, started from the pause menu or with `--daily`. Everyone playing on the same
+/// UTC date gets the same seed, each day can be attempted once, and its score is kept in
+/// [`daily_scores_path`].
+pub fn daily_plugin(app: &mut App) {
+    if DailyChallenge::from_args().is_some() {
+        app.add_systems(
+            Startup,
+            start_daily.after(setup_scene).after(start_tutorial),
+        );
+    }
+    app.add_systems(
+        Update,
+        (
+            finish_daily
+                .after(handle_collisions)
+                .run_if(resource_exists::<DailyChallenge>),
+            update_daily_status
+                .run_if(resource_changed::<PauseMenu>.or(resource_changed::<Locale>)),
+        ),
+    );
+}
+
+/// Folder under the home directory the daily scores are kept in
+pub const DAILY_DIR: &str = ".asteroids_clone";
+pub const DAILY_SCORES_FILE: &str = "daily_scores.json";
+
+/// Today's daily run, only present while it's being played
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DailyChallenge {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl DailyChallenge {
+    pub fn from_args() -> Option<Self> {
+        std::env::args()
+            .any(|arg| arg == "--daily")
+            .then(Self::today)
+    }
+
+    /// Today's date in UTC
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or_default();
+        Self::from_unix_days((secs / 86_400) as i64)
+    }
+
+    /// Converts days since 1970-01-01 to a calendar date, see
+    /// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    pub fn from_unix_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+
+        Self { year, month, day }
+    }
+
+    /// The [`GameRng`](crate::GameRng) seed every player gets today
+    pub fn seed(self) -> u64 {
+        (self.year * 10_000) as u64 + (self.month * 100 + self.day) as u64
+    }
+
+    /// `YYYY-MM-DD`, the key scores are stored under
+    pub fn date(self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The one run of a day. It's written as soon as the run starts, so the attempt is used up even
+/// if the game is closed halfway through.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DailyScore {
+    pub seed: u64,
+    pub score: u64,
+    /// See [`sign_score`]
+    pub signature: String,
+}
+
+impl DailyScore {
+    pub fn new(challenge: DailyChallenge, score: u64) -> Self {
+        Self {
+            seed: challenge.seed(),
+            score,
+            signature: sign_score(&challenge.date(), score),
+        }
+    }
+}
+
+/// Ties a score to the date it was set on. This is a plain FNV-1a checksum, it catches hand
+/// edits of the scores file but is no protection against someone who reads this code. A real
+/// leaderboard has to verify runs on the server.
+pub fn sign_score(date: &str, score: u64) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in format!("{date}:{score}").bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// `~/.asteroids_clone/daily_scores.json`, `None` if there's no home directory to put it in
+pub fn daily_scores_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(DAILY_DIR).join(DAILY_SCORES_FILE))
+}
+
+/// Every day played so far, keyed by `YYYY-MM-DD`. Missing or unreadable files count as empty.
+pub fn load_daily_scores() -> BTreeMap<String, DailyScore> {
+    daily_scores_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_daily_scores(scores: &BTreeMap<String, DailyScore>) {
+    let Some(path) = daily_scores_path() else {
+        error!("No home directory to save the daily score in");
+        return;
+    };
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            let contents = serde_json::to_string_pretty(scores).map_err(std::io::Error::other)?;
+            std::fs::write(&path, contents)
+        });
+    if let Err(err) = result {
+        error!("Could not write {}: {err}", path.display());
+    }
+}
+
+/// Whether today's attempt hasn't been used yet
+pub fn daily_available() -> bool {
+    !load_daily_scores().contains_key(&DailyChallenge::today().date())
+}
+
+/// Throws the current run away and starts today's daily in endless mode, from its seed and with
+/// a stock ship and no mutators. Does nothing once today's attempt is used.
+pub fn start_daily(
+    mut game_rng: ResMut<GameRng>,
+    mut mode: ResMut<GameMode>,
+    mut tutorial: ResMut<Tutorial>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut cmds: Commands,
+) {
+    let challenge = DailyChallenge::today();
+    let date = challenge.date();
+    let mut scores = load_daily_scores();
+    if scores.contains_key(&date) {
+        info!("The daily challenge for {date} was already played");
+        return;
+    }
+
+    info!("Daily challenge for {date}");
+    scores.insert(date, DailyScore::new(challenge, 0));
+    save_daily_scores(&scores);
+
+    *game_rng = GameRng::seeded(challenge.seed());
+    *mode = GameMode::Endless;
+    *modifiers = RunModifiers::default();
+    tutorial.phase = TutorialPhase::Off;
+    // Restarting ends any daily in progress, so the challenge only goes in after it
+    cmds.run_system_cached(restart_run);
+    cmds.insert_resource(challenge);
+}
+
+/// The first death ends the daily. Its score is saved and a normal run with a fresh seed starts.
+pub fn finish_daily(
+    challenge: Res<DailyChallenge>,
+    mut deaths: MessageReader<PlayerDied>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    let Some(score) = deaths.read().map(|death| death.score).max() else {
+        return;
+    };
+
+    let date = challenge.date();
+    let entry = DailyScore::new(*challenge, score);
+    // Once there's a leaderboard server the entry goes there as well:
+    // if let Err(err) = reqwest::blocking::Client::new()
+    //     .post(format!("{LEADERBOARD_URL}/daily/{date}"))
+    //     .json(&entry)
+    //     .send()
+    // {
+    //     error!("Could not submit the daily score: {err}");
+    // }
+    let mut scores = load_daily_scores();
+    scores.insert(date, entry);
+    save_daily_scores(&scores);
+    info!("Daily score {score} saved");
+
+    *game_rng = GameRng::seeded(rand::rng().random());
+    cmds.run_system_cached(restart_run);
+}
+
+/// Line under the pause menu buttons with today's daily result
+#[derive(Component)]
+pub struct DailyStatus;
+
+const UNAVAILABLE_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+/// Shows today's score and whether the attempt is used, and greys out the menu entry once it is
+pub fn update_daily_status(
+    menu: Res<PauseMenu>,
+    challenge: Option<Res<DailyChallenge>>,
+    locale: Res<Locale>,
+    mut status: Single<&mut Text, With<DailyStatus>>,
+    mut buttons: Query<(&PauseButton, &mut TextColor)>,
+) {
+    if !menu.open {
+        return;
+    }
+
+    let today = DailyChallenge::today();
+    let scores = load_daily_scores();
+    let played = scores.get(&today.date());
+    status.0 = match (challenge, played) {
+        (Some(challenge), _) => locale.text_with("daily.in_progress", &[&challenge.date()]),
+        (None, Some(entry)) => locale.text_with(
+            "daily.played",
+            &[&today.date(), &locale.format_score(entry.score)],
+        ),
+        (None, None) => locale.text_with("daily.available", &[&today.date()]),
+    };
+
+    for (button, mut color) in buttons.iter_mut() {
+        if *button == PauseButton::Daily {
+            color.0 = if played.is_some() {
+                UNAVAILABLE_COLOR
+            } else {
+                Color::WHITE
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unix_days_to_dates() {
+        let date = |days| DailyChallenge::from_unix_days(days).date();
+
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(11_016), "2000-02-29");
+        assert_eq!(date(20_742), "2026-10-16");
+        assert_eq!(DailyChallenge::from_unix_days(20_742).seed(), 20_261_016);
+    }
+}
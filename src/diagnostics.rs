@@ -0,0 +1,45 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+
+use crate::{
+    Asteroid, LaserShot,
+    physics::{CollisionBudget, Disabled, detect_collisions},
+};
+
+/// Entity and collision counts as Bevy diagnostics, so they show up next to the frame rate and
+/// in anything that logs diagnostics
+pub fn diagnostics_plugin(app: &mut App) {
+    for path in GAME_DIAGNOSTICS {
+        app.register_diagnostic(Diagnostic::new(path));
+    }
+
+    app.add_systems(Update, measure_game_diagnostics.after(detect_collisions));
+}
+
+pub const ASTEROID_COUNT: DiagnosticPath = DiagnosticPath::const_new("game/asteroids");
+/// Lasers in flight, parked ones in the pool don't count
+pub const LASER_COUNT: DiagnosticPath = DiagnosticPath::const_new("game/lasers");
+pub const COLLISION_PAIRS: DiagnosticPath = DiagnosticPath::const_new("physics/pairs_tested");
+pub const COLLISION_EVENTS: DiagnosticPath = DiagnosticPath::const_new("physics/collisions");
+pub const GAME_DIAGNOSTICS: [DiagnosticPath; 4] = [
+    ASTEROID_COUNT,
+    LASER_COUNT,
+    COLLISION_PAIRS,
+    COLLISION_EVENTS,
+];
+
+/// Both queries only filter on components, so counting them adds up archetype sizes instead of
+/// visiting every entity
+pub fn measure_game_diagnostics(
+    asteroids: Query<(), With<Asteroid>>,
+    lasers: Query<(), (With<LaserShot>, Without<Disabled>)>,
+    budget: Res<CollisionBudget>,
+    mut diagnostics: Diagnostics,
+) {
+    diagnostics.add_measurement(&ASTEROID_COUNT, || asteroids.iter().len() as f64);
+    diagnostics.add_measurement(&LASER_COUNT, || lasers.iter().len() as f64);
+    diagnostics.add_measurement(&COLLISION_PAIRS, || budget.pairs_tested.into());
+    diagnostics.add_measurement(&COLLISION_EVENTS, || budget.collisions.into());
+}
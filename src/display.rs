@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
+
+use crate::{
+    HudRoot,
+    diagnostics::{ASTEROID_COUNT, COLLISION_EVENTS, COLLISION_PAIRS, LASER_COUNT},
+    locale::Locale,
+    palette::Palette,
+    physics::CollisionBudget,
+    settings::{Settings, SyncMode},
+};
+
+pub fn display_plugin(app: &mut App) {
+    app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+
+    app.add_systems(Startup, spawn_fps_counter);
+    app.add_systems(
+        Update,
+        (
+            apply_present_mode.run_if(resource_changed::<Settings>),
+            update_fps_counter,
+        ),
+    );
+    app.add_systems(Last, limit_frame_rate);
+}
+
+/// How often the FPS counter text changes, any faster and it's an unreadable blur
+pub const FPS_REFRESH_SECS: f32 = 0.25;
+
+/// Frame rate readout in the bottom-right corner
+#[derive(Component)]
+pub struct FpsText {
+    pub refresh: Timer,
+}
+
+pub fn spawn_fps_counter(
+    palette: Res<Palette>,
+    hud: Single<Entity, With<HudRoot>>,
+    mut cmds: Commands,
+) {
+    cmds.spawn((
+        FpsText {
+            refresh: Timer::from_seconds(FPS_REFRESH_SECS, TimerMode::Repeating),
+        },
+        Text::default(),
+        TextColor(palette.hud_text),
+        palette.hud_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(12),
+            right: px(12),
+            ..default()
+        },
+        Visibility::Hidden,
+        ChildOf(*hud),
+    ));
+}
+
+/// Pushes the chosen [`SyncMode`] onto the live window
+pub fn apply_present_mode(
+    settings: Res<Settings>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    let present_mode = match settings.sync {
+        SyncMode::Vsync => PresentMode::AutoVsync,
+        SyncMode::Uncapped => PresentMode::AutoNoVsync,
+    };
+
+    // Touching the window for nothing would look like a resize to anything watching for changes
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+pub fn update_fps_counter(
+    counter: Single<(&mut FpsText, &mut Text, &mut Visibility)>,
+    settings: Res<Settings>,
+    diagnostics: Res<DiagnosticsStore>,
+    budget: Res<CollisionBudget>,
+    locale: Res<Locale>,
+    time: Res<Time<Real>>,
+) {
+    let (mut counter, mut text, mut visibility) = counter.into_inner();
+
+    visibility.set_if_neq(if settings.show_fps {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+
+    counter.refresh.tick(time.delta());
+    if !settings.show_fps || !counter.refresh.just_finished() {
+        return;
+    }
+
+    let Some(fps) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+    else {
+        return;
+    };
+    text.0 = locale.text_with("hud.fps", &[&locale.number(fps.round() as i64)]);
+
+    // Debug builds list the game's own diagnostics above it, along with whether collision
+    // detection is cutting corners to stay in budget
+    if cfg!(debug_assertions) {
+        let count = |path| {
+            let value = diagnostics.get(path).and_then(|d| d.value()).unwrap_or(0.0);
+            locale.number(value.round() as i64)
+        };
+        let counts = locale.text_with(
+            "hud.diagnostics",
+            &[
+                &count(&ASTEROID_COUNT),
+                &count(&LASER_COUNT),
+                &count(&COLLISION_PAIRS),
+                &count(&COLLISION_EVENTS),
+            ],
+        );
+        let budget = locale.text_with(
+            if budget.degraded {
+                "hud.budget_degraded"
+            } else {
+                "hud.budget"
+            },
+            &[&locale.number(budget.last_frame.as_micros() as i64)],
+        );
+        text.0 = format!("{counts}\n{budget}\n{}", text.0);
+    }
+}
+
+/// Sleeps off whatever is left of the frame when [`Settings::fps_limit`] is set, to save power
+/// on laptops
+pub fn limit_frame_rate(settings: Res<Settings>, mut last_frame: Local<Option<Instant>>) {
+    if let (Some(limit), Some(last)) = (settings.fps_limit, *last_frame) {
+        let budget = Duration::from_secs_f64(1.0 / limit.max(1) as f64);
+        if let Some(left) = budget.checked_sub(last.elapsed()) {
+            std::thread::sleep(left);
+        }
+    }
+
+    *last_frame = Some(Instant::now());
+}
@@ -0,0 +1,207 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::{
+    events::LaserHit,
+    mines::{ShockwaveConfig, spawn_shockwave},
+    palette::Palette,
+    physics::{Disabled, TimeScale},
+    pool::{Pooled, release},
+};
+
+pub fn effects_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_lifetimes,
+            pulse_scale,
+            spawn_impacts,
+            (tick_invincible, flicker_invincible).chain(),
+            tick_gun_flash,
+        ),
+    );
+}
+
+/// How long the ship glows after firing
+pub const GUN_FLASH_SECS: f32 = 0.05;
+
+/// Size of the flash a normal laser hit leaves
+pub const IMPACT_RADIUS: f32 = 20.0;
+/// Critical hits flash bigger, and in [`Palette::critical`]
+pub const CRITICAL_IMPACT_RADIUS: f32 = 55.0;
+
+/// Despawns the entity once `secs` runs out, or parks it if it's [`Pooled`]. Respects the
+/// entity's [`TimeScale`].
+#[derive(Component)]
+pub struct Lifetime {
+    pub secs: f32,
+}
+
+/// Breathes the entity's scale between `min` and `max`, `hz` times a second
+#[derive(Component)]
+pub struct PulseScale {
+    pub min: f32,
+    pub max: f32,
+    pub hz: f32,
+}
+
+/// Collisions that would destroy this entity are ignored until the timer runs out
+#[derive(Component)]
+pub struct Invincible {
+    pub timer: Timer,
+}
+
+impl Invincible {
+    pub fn new(secs: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+        }
+    }
+}
+
+/// Tints the ship's sprite for a moment after each shot, see [`tick_gun_flash`]
+#[derive(Component)]
+pub struct GunFlash {
+    pub timer: Timer,
+}
+
+impl Default for GunFlash {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(GUN_FLASH_SECS, TimerMode::Once),
+        }
+    }
+}
+
+/// Flips between a dimmed and normal state 8 times a second
+#[derive(Component)]
+pub struct FlickerTimer {
+    pub timer: Timer,
+    pub dimmed: bool,
+}
+
+impl Default for FlickerTimer {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(1.0 / 16.0, TimerMode::Repeating),
+            dimmed: false,
+        }
+    }
+}
+
+impl FlickerTimer {
+    /// Advances the timer and returns whether the flicker is currently dimmed
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        self.timer.tick(delta);
+        if self.timer.times_finished_this_tick() % 2 == 1 {
+            self.dimmed = !self.dimmed;
+        }
+        self.dimmed
+    }
+}
+
+pub fn tick_lifetimes(
+    mut lifetimes: Query<
+        (Entity, &mut Lifetime, Option<&TimeScale>, Has<Pooled>),
+        Without<Disabled>,
+    >,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    for (ent, mut lifetime, time_scale, pooled) in lifetimes.iter_mut() {
+        lifetime.secs -= time.delta_secs() * time_scale.copied().unwrap_or_default().0;
+
+        if lifetime.secs > 0.0 {
+            continue;
+        }
+        if pooled {
+            release(&mut cmds, ent);
+        } else {
+            cmds.entity(ent).try_despawn();
+        }
+    }
+}
+
+pub fn tick_invincible(
+    mut invincible: Query<(Entity, &mut Invincible)>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    for (ent, mut invincible) in invincible.iter_mut() {
+        invincible.timer.tick(time.delta());
+
+        if invincible.timer.is_finished() {
+            cmds.entity(ent).remove::<Invincible>();
+        }
+    }
+}
+
+/// Fades invincible sprites in and out so they read as ghostly without ever vanishing
+pub fn flicker_invincible(
+    mut invincible: Query<&mut Sprite, With<Invincible>>,
+    mut vulnerable: RemovedComponents<Invincible>,
+    mut restore: Query<&mut Sprite, Without<Invincible>>,
+    mut flicker: Local<FlickerTimer>,
+    time: Res<Time>,
+) {
+    let alpha = if flicker.tick(time.delta()) { 0.3 } else { 1.0 };
+    for mut sprite in invincible.iter_mut() {
+        sprite.color.set_alpha(alpha);
+    }
+
+    for ent in vulnerable.read() {
+        if let Ok(mut sprite) = restore.get_mut(ent) {
+            sprite.color.set_alpha(1.0);
+        }
+    }
+}
+
+/// Holds the flash tint while the timer runs, then puts the sprite back to white. Alpha is left
+/// alone so it doesn't fight [`flicker_invincible`].
+pub fn tick_gun_flash(
+    mut flashing: Query<(Entity, &mut GunFlash, &mut Sprite)>,
+    time: Res<Time>,
+    palette: Res<Palette>,
+    mut cmds: Commands,
+) {
+    for (ent, mut flash, mut sprite) in flashing.iter_mut() {
+        flash.timer.tick(time.delta());
+
+        let alpha = sprite.color.alpha();
+        if flash.timer.is_finished() {
+            sprite.color = Color::WHITE.with_alpha(alpha);
+            cmds.entity(ent).remove::<GunFlash>();
+        } else {
+            sprite.color = palette.gun_flash.with_alpha(alpha);
+        }
+    }
+}
+
+/// Leaves a small ring wherever a laser struck
+pub fn spawn_impacts(mut hits: MessageReader<LaserHit>, palette: Res<Palette>, mut cmds: Commands) {
+    for hit in hits.read() {
+        let (radius, color) = if hit.critical {
+            (CRITICAL_IMPACT_RADIUS, palette.critical)
+        } else {
+            (IMPACT_RADIUS, palette.laser)
+        };
+
+        cmds.run_system_cached_with(
+            spawn_shockwave,
+            ShockwaveConfig {
+                // Over the asteroid that was hit
+                position: hit.position.extend(1.0),
+                radius,
+                color,
+            },
+        );
+    }
+}
+
+pub fn pulse_scale(mut pulsing: Query<(&mut Transform, &PulseScale)>, time: Res<Time>) {
+    for (mut tsf, pulse) in pulsing.iter_mut() {
+        let t = 0.5 + 0.5 * (time.elapsed_secs() * pulse.hz * TAU).sin();
+        tsf.scale = Vec3::splat(pulse.min.lerp(pulse.max, t));
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::{AsteroidSize, powerups::PowerUpKind};
+
+/// Gameplay happenings other systems can listen for without knowing who caused them
+pub fn events_plugin(app: &mut App) {
+    app.add_message::<AsteroidSpawned>();
+    app.add_message::<LaserHit>();
+    app.add_message::<DestroyAsteroid>();
+    app.add_message::<AsteroidDestroyed>();
+    app.add_message::<ShotFired>();
+    app.add_message::<PlayerDied>();
+    app.add_message::<ShipHit>();
+    app.add_message::<PowerUpCollected>();
+    app.add_message::<WaveStarted>();
+    app.add_message::<BossDefeated>();
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct AsteroidSpawned {
+    pub position: Vec2,
+    pub size: AsteroidSize,
+    pub speed: f32,
+}
+
+/// Asks for an asteroid to be destroyed. Anything that kills asteroids sends this instead of
+/// despawning them, see [`destroy_asteroids`](crate::destroy_asteroids).
+#[derive(Message, Clone, Copy, Debug)]
+pub struct DestroyAsteroid {
+    pub asteroid: Entity,
+    /// Finished off by a critical hit, which is worth more points
+    pub critical: bool,
+}
+
+/// A laser struck an asteroid, whether or not it was destroyed
+#[derive(Message, Clone, Copy, Debug)]
+pub struct LaserHit {
+    pub position: Vec2,
+    pub critical: bool,
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct AsteroidDestroyed {
+    pub position: Vec2,
+    pub size: AsteroidSize,
+    /// How the asteroid was moving when it was destroyed, fragments carry on from this
+    pub velocity: Vec2,
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct ShotFired {
+    pub position: Vec2,
+    /// Radians, after any spread was applied
+    pub heading: f32,
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct PlayerDied {
+    pub score: u64,
+}
+
+/// The ship was hit by something and survived it
+#[derive(Message, Clone, Copy, Debug)]
+pub struct ShipHit {
+    pub position: Vec2,
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct PowerUpCollected {
+    pub kind: PowerUpKind,
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct WaveStarted {
+    pub wave: u32,
+    pub boss: bool,
+}
+
+/// The last asteroid of a boss wave is gone
+#[derive(Message, Clone, Copy, Debug)]
+pub struct BossDefeated {
+    pub wave: u32,
+}
@@ -0,0 +1,222 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    Asteroid, AsteroidConfig, AsteroidSize, GameCleanup, GameMode, GameRng, GameStats, HudRoot,
+    PlayerShip,
+    config::GameConfig,
+    effects::Lifetime,
+    events::{BossDefeated, PlayerDied},
+    locale::{Locale, LocalizedText},
+    palette::Palette,
+    physics::CircleCollider,
+    playfield::PlayBounds,
+    respawn::RESPAWN_SAFE_RADIUS,
+    shop::open_shop,
+    spawn_asteroid,
+    waves::{award_wave_bonus, start_next_wave},
+};
+
+/// Score-attack window after a boss wave: a ring of small asteroids worth double points, with
+/// a countdown bar. The next wave and the shop wait until it's over.
+pub fn frenzy_plugin(app: &mut App) {
+    app.init_resource::<Frenzy>();
+
+    app.add_systems(
+        Update,
+        (
+            start_frenzy
+                .after(award_wave_bonus)
+                .before(start_next_wave)
+                .before(open_shop),
+            (end_frenzy_on_death, tick_frenzy)
+                .chain()
+                .run_if(frenzy_active),
+            fade_frenzy_leftovers,
+        )
+            .run_if(resource_equals(GameMode::Waves)),
+    );
+}
+
+#[derive(Resource, Default)]
+pub struct Frenzy {
+    /// Counts down the window, `None` when there's no frenzy
+    pub timer: Option<Timer>,
+}
+
+pub fn frenzy_active(frenzy: Res<Frenzy>) -> bool {
+    frenzy.timer.is_some()
+}
+
+/// Banner and countdown bar shown while the frenzy lasts
+#[derive(Component)]
+pub struct FrenzyBar;
+
+/// The part of the [`FrenzyBar`] that shrinks as time runs out
+#[derive(Component)]
+pub struct FrenzyBarFill;
+
+/// A frenzy asteroid left over when time ran out. It has no collider and isn't an
+/// [`Asteroid`] anymore, it only fades out until its [`Lifetime`] is up.
+#[derive(Component)]
+pub struct FrenzyLeftover;
+
+pub fn start_frenzy(
+    mut boss_defeated: MessageReader<BossDefeated>,
+    mut frenzy: ResMut<Frenzy>,
+    mut game_stats: ResMut<GameStats>,
+    config: Res<GameConfig>,
+    bounds: Res<PlayBounds>,
+    ship: Option<Single<&Transform, With<PlayerShip>>>,
+    hud: Single<Entity, With<HudRoot>>,
+    palette: Res<Palette>,
+    locale: Res<Locale>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    if boss_defeated.read().count() == 0 {
+        return;
+    }
+
+    let config = &config.frenzy;
+    frenzy.timer = Some(Timer::from_seconds(config.duration_secs, TimerMode::Once));
+    game_stats.frenzy_multiplier = config.score_multiplier;
+
+    let rng = &mut game_rng.rng;
+    let ship_pos = ship.map(|ship| ship.translation.xy());
+    let offset = rng.random_range(0.0..TAU);
+    for i in 0..config.ring_count {
+        let angle = offset + TAU * i as f32 / config.ring_count as f32;
+        let pos = Vec2::from_angle(angle) * bounds.half_extents * config.ring_scale;
+
+        // The ring can pass right over the ship, leave a gap there instead
+        if ship_pos.is_some_and(|ship| ship.distance(pos) < RESPAWN_SAFE_RADIUS) {
+            continue;
+        }
+
+        // Heading along the ring sends the whole thing circling the play area
+        cmds.run_system_cached_with(
+            spawn_asteroid,
+            AsteroidConfig {
+                location: pos,
+                heading: angle,
+                speed: rng.random_range(config.min_speed..=config.max_speed),
+                angvel: rng.random_range(-PI..PI),
+                size: AsteroidSize::Small,
+                group: None,
+            },
+        );
+    }
+
+    cmds.spawn((
+        FrenzyBar,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(12),
+            left: percent(30),
+            width: percent(40),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(6),
+            ..default()
+        },
+        GameCleanup,
+        ChildOf(*hud),
+        children![
+            (
+                LocalizedText("frenzy.banner"),
+                Text::new(locale.text("frenzy.banner")),
+                TextColor(palette.warning),
+                palette.hud_shadow(),
+            ),
+            (
+                Node {
+                    width: percent(100),
+                    height: px(8),
+                    ..default()
+                },
+                BackgroundColor(Color::WHITE.with_alpha(0.2)),
+                children![(
+                    FrenzyBarFill,
+                    Node {
+                        width: percent(100),
+                        height: percent(100),
+                        ..default()
+                    },
+                    BackgroundColor(palette.warning),
+                )],
+            ),
+        ],
+    ));
+}
+
+/// Counts the frenzy down. Once it's over every asteroid still around loses its collider and
+/// fades away, and the wave flow picks back up.
+pub fn tick_frenzy(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut frenzy: ResMut<Frenzy>,
+    mut game_stats: ResMut<GameStats>,
+    mut fill: Query<&mut Node, With<FrenzyBarFill>>,
+    bars: Query<Entity, With<FrenzyBar>>,
+    asteroids: Query<Entity, With<Asteroid>>,
+    mut cmds: Commands,
+) {
+    let Some(timer) = &mut frenzy.timer else {
+        return;
+    };
+
+    timer.tick(time.delta());
+    for mut node in fill.iter_mut() {
+        node.width = percent(timer.fraction_remaining() * 100.0);
+    }
+
+    if !timer.is_finished() {
+        return;
+    }
+
+    frenzy.timer = None;
+    game_stats.frenzy_multiplier = 1.0;
+
+    for bar in bars {
+        cmds.entity(bar).try_despawn();
+    }
+
+    // The wave was already clear when the frenzy started, so anything left belongs to it
+    for asteroid in asteroids {
+        cmds.entity(asteroid)
+            .remove::<(Asteroid, CircleCollider)>()
+            .insert((
+                FrenzyLeftover,
+                Lifetime {
+                    secs: config.frenzy.fade_secs,
+                },
+            ));
+    }
+}
+
+/// Dying ends the frenzy on the spot. The death already swept away its asteroids and bar.
+pub fn end_frenzy_on_death(
+    mut deaths: MessageReader<PlayerDied>,
+    mut frenzy: ResMut<Frenzy>,
+    mut game_stats: ResMut<GameStats>,
+) {
+    if deaths.read().count() == 0 {
+        return;
+    }
+
+    frenzy.timer = None;
+    game_stats.frenzy_multiplier = 1.0;
+}
+
+pub fn fade_frenzy_leftovers(
+    mut leftovers: Query<(&Lifetime, &mut Sprite), With<FrenzyLeftover>>,
+    config: Res<GameConfig>,
+) {
+    for (lifetime, mut sprite) in leftovers.iter_mut() {
+        let alpha = (lifetime.secs / config.frenzy.fade_secs).clamp(0.0, 1.0);
+        sprite.color.set_alpha(alpha);
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::{palette::Palette, playfield::PlayBounds};
+
+/// Faint vector-arcade grid across the whole play area, toggled with G
+pub fn grid_plugin(app: &mut App) {
+    app.init_resource::<ShowGrid>();
+
+    app.add_systems(
+        Update,
+        (toggle_grid, draw_grid.run_if(|show: Res<ShowGrid>| show.0)).chain(),
+    );
+}
+
+/// World units between grid lines
+pub const GRID_SPACING: f32 = 100.0;
+
+#[derive(Resource)]
+pub struct ShowGrid(pub bool);
+
+impl Default for ShowGrid {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+pub fn toggle_grid(btn_input: Res<ButtonInput<KeyCode>>, mut show: ResMut<ShowGrid>) {
+    if btn_input.just_pressed(KeyCode::KeyG) {
+        show.0 = !show.0;
+    }
+}
+
+/// Lines every [`GRID_SPACING`] from the origin, out to one cell past the play area. 2D gizmos
+/// always draw over sprites, the grid is kept faint so it never hides anything.
+pub fn draw_grid(bounds: Res<PlayBounds>, palette: Res<Palette>, mut gizmos: Gizmos) {
+    let cells = (bounds.half_extents / GRID_SPACING).ceil() + Vec2::ONE;
+    let extents = cells * GRID_SPACING;
+
+    for i in -cells.x as i32..=cells.x as i32 {
+        let x = i as f32 * GRID_SPACING;
+        gizmos.line_2d(
+            Vec2::new(x, -extents.y),
+            Vec2::new(x, extents.y),
+            palette.grid,
+        );
+    }
+    for i in -cells.y as i32..=cells.y as i32 {
+        let y = i as f32 * GRID_SPACING;
+        gizmos.line_2d(
+            Vec2::new(-extents.x, y),
+            Vec2::new(extents.x, y),
+            palette.grid,
+        );
+    }
+}
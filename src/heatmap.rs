@@ -0,0 +1,215 @@
+use std::{
+    fmt::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+
+use crate::{Asteroid, playfield::PlayBounds};
+
+/// Where asteroids spend their time, for tuning spawns. F7 shows the heatmap behind the
+/// playfield, F8 clears it and Shift+F8 writes it to `heatmap-<unix secs>.csv`. Only built with
+/// the `debug-tools` feature in debug builds.
+pub fn heatmap_plugin(app: &mut App) {
+    app.init_resource::<AsteroidHeatmap>();
+    app.init_resource::<ShowHeatmap>();
+
+    app.add_systems(
+        Update,
+        (
+            rebuild_heatmap.run_if(resource_changed::<PlayBounds>),
+            accumulate_heatmap,
+            heatmap_keys,
+            show_heatmap.run_if(resource_changed::<ShowHeatmap>),
+            color_heatmap.run_if(on_timer(HEATMAP_REFRESH).and(|show: Res<ShowHeatmap>| show.0)),
+        )
+            .chain(),
+    );
+}
+
+/// Width and height of each heatmap cell in world units
+pub const HEATMAP_CELL_SIZE: f32 = 100.0;
+/// Recoloring every cell is only worth doing a few times a second
+pub const HEATMAP_REFRESH: Duration = Duration::from_millis(250);
+pub const HEATMAP_ALPHA: f32 = 0.35;
+/// Above the background asteroids, below everything that plays
+pub const HEATMAP_Z: f32 = -4.0;
+
+#[derive(Resource, Default)]
+pub struct ShowHeatmap(pub bool);
+
+/// Seconds of asteroid presence in each cell of a grid laid over the play area, row by row from
+/// the bottom left
+#[derive(Resource, Default, Debug)]
+pub struct AsteroidHeatmap {
+    pub origin: Vec2,
+    pub cols: usize,
+    pub rows: usize,
+    pub seconds: Vec<f32>,
+}
+
+impl AsteroidHeatmap {
+    pub fn new(half_extents: Vec2) -> Self {
+        let cells = (half_extents * 2.0 / HEATMAP_CELL_SIZE)
+            .ceil()
+            .max(Vec2::ONE);
+        let (cols, rows) = (cells.x as usize, cells.y as usize);
+        Self {
+            origin: -half_extents,
+            cols,
+            rows,
+            seconds: vec![0.0; cols * rows],
+        }
+    }
+
+    /// Index of the cell covering `pos`, if it's inside the grid
+    pub fn cell(&self, pos: Vec2) -> Option<usize> {
+        let cell = ((pos - self.origin) / HEATMAP_CELL_SIZE).floor();
+        if cell.x < 0.0 || cell.y < 0.0 {
+            return None;
+        }
+
+        let (col, row) = (cell.x as usize, cell.y as usize);
+        (col < self.cols && row < self.rows).then_some(row * self.cols + col)
+    }
+
+    pub fn cell_center(&self, index: usize) -> Vec2 {
+        let (col, row) = (index % self.cols, index / self.cols);
+        self.origin + (Vec2::new(col as f32, row as f32) + 0.5) * HEATMAP_CELL_SIZE
+    }
+
+    pub fn reset(&mut self) {
+        self.seconds.fill(0.0);
+    }
+
+    /// One line per cell: its column, row, world space center and seconds
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("col,row,x,y,seconds\n");
+        for (index, seconds) in self.seconds.iter().enumerate() {
+            let center = self.cell_center(index);
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{seconds}",
+                index % self.cols,
+                index / self.cols,
+                center.x,
+                center.y
+            );
+        }
+        csv
+    }
+}
+
+/// Quad drawn over one [`AsteroidHeatmap`] cell
+#[derive(Component)]
+pub struct HeatmapCell(pub usize);
+
+/// Starts the heatmap over whenever the play area changes size, old cells no longer line up
+pub fn rebuild_heatmap(
+    bounds: Res<PlayBounds>,
+    show: Res<ShowHeatmap>,
+    cells: Query<Entity, With<HeatmapCell>>,
+    mut heatmap: ResMut<AsteroidHeatmap>,
+    mut cmds: Commands,
+) {
+    for cell in cells {
+        cmds.entity(cell).despawn();
+    }
+
+    *heatmap = AsteroidHeatmap::new(bounds.half_extents);
+    let visibility = if show.0 {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    for index in 0..heatmap.seconds.len() {
+        let center = heatmap.cell_center(index);
+        cmds.spawn((
+            HeatmapCell(index),
+            Sprite::from_color(Color::NONE, Vec2::splat(HEATMAP_CELL_SIZE)),
+            Transform::from_xyz(center.x, center.y, HEATMAP_Z),
+            visibility,
+        ));
+    }
+}
+
+pub fn accumulate_heatmap(
+    asteroids: Query<&Transform, With<Asteroid>>,
+    time: Res<Time>,
+    mut heatmap: ResMut<AsteroidHeatmap>,
+) {
+    for tsf in asteroids {
+        if let Some(cell) = heatmap.cell(tsf.translation.xy()) {
+            heatmap.seconds[cell] += time.delta_secs();
+        }
+    }
+}
+
+pub fn heatmap_keys(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    mut show: ResMut<ShowHeatmap>,
+    mut heatmap: ResMut<AsteroidHeatmap>,
+) {
+    if btn_input.just_pressed(KeyCode::F7) {
+        show.0 = !show.0;
+    }
+
+    if !btn_input.just_pressed(KeyCode::F8) {
+        return;
+    }
+    if !btn_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        heatmap.reset();
+        info!("Asteroid heatmap cleared");
+        return;
+    }
+
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+    let path = format!("heatmap-{stamp}.csv");
+    match std::fs::write(&path, heatmap.to_csv()) {
+        Ok(()) => info!("Wrote asteroid heatmap to {path}"),
+        Err(err) => error!("Could not write asteroid heatmap to {path}: {err}"),
+    }
+}
+
+pub fn show_heatmap(show: Res<ShowHeatmap>, mut cells: Query<&mut Visibility, With<HeatmapCell>>) {
+    for mut visibility in cells.iter_mut() {
+        *visibility = if show.0 {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Blue for the quietest cells through to red for the busiest
+pub fn color_heatmap(heatmap: Res<AsteroidHeatmap>, mut cells: Query<(&HeatmapCell, &mut Sprite)>) {
+    let busiest = heatmap.seconds.iter().copied().fold(0.0, f32::max);
+    if busiest <= 0.0 {
+        return;
+    }
+
+    for (cell, mut sprite) in cells.iter_mut() {
+        let heat = heatmap.seconds.get(cell.0).copied().unwrap_or_default() / busiest;
+        sprite.color = Color::srgba(heat, 0.0, 1.0 - heat, HEATMAP_ALPHA);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_cover_the_play_area() {
+        let heatmap = AsteroidHeatmap::new(Vec2::new(250.0, 100.0));
+        assert_eq!((heatmap.cols, heatmap.rows), (5, 2));
+
+        assert_eq!(heatmap.cell(Vec2::new(-249.0, -99.0)), Some(0));
+        assert_eq!(heatmap.cell(Vec2::new(249.0, 99.0)), Some(9));
+        assert_eq!(heatmap.cell(Vec2::new(0.0, 101.0)), None);
+        assert_eq!(heatmap.cell_center(9), Vec2::new(200.0, 50.0));
+        assert_eq!(heatmap.to_csv().lines().count(), 11);
+    }
+}
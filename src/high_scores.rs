@@ -0,0 +1,604 @@
+use std::path::PathBuf;
+
+use bevy::{
+    input::{
+        InputSystems,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+    ui::FocusPolicy,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GameMode,
+    bot::Bot,
+    daily::DAILY_DIR,
+    events::PlayerDied,
+    locale::{Locale, LocalizedText},
+    palette::Palette,
+    pause::{PauseMenu, toggle_pause_menu, update_pause_menu},
+};
+
+/// Top five scores kept on disk. A run that makes the list asks for the player's initials
+/// before the next one gets going, and the pause menu can show the table any time.
+pub fn high_scores_plugin(app: &mut App) {
+    app.insert_resource(HighScoreTable::load());
+    app.init_resource::<NameInputState>();
+    app.init_resource::<HighScoreScreen>();
+
+    app.add_systems(Startup, (spawn_name_entry_ui, spawn_high_score_screen));
+    app.add_systems(
+        PreUpdate,
+        (
+            read_name_input,
+            suppress_gameplay_input.run_if(|input: Res<NameInputState>| input.open),
+        )
+            .chain()
+            .after(InputSystems)
+            .before(toggle_pause_menu),
+    );
+    app.add_systems(
+        Update,
+        (
+            prompt_for_initials,
+            click_high_score_back,
+            // Closing the pause menu unpauses, so this goes after it to pause again
+            update_name_entry_ui.after(update_pause_menu).run_if(
+                resource_changed::<NameInputState>
+                    .or(resource_changed::<PauseMenu>)
+                    .or(resource_changed::<Locale>),
+            ),
+            update_high_score_screen.run_if(resource_changed::<HighScoreScreen>),
+            update_high_score_rows.run_if(
+                resource_changed::<HighScoreTable>
+                    .or(resource_changed::<NameInputState>)
+                    .or(resource_changed::<Locale>)
+                    .or(resource_changed::<Palette>),
+            ),
+            save_high_scores.run_if(
+                resource_changed::<HighScoreTable>.and(not(resource_added::<HighScoreTable>)),
+            ),
+        )
+            .chain(),
+    );
+}
+
+/// How many scores the table keeps
+pub const HIGH_SCORE_COUNT: usize = 5;
+/// Length of the initials entered for a high score
+pub const INITIALS_LEN: usize = 3;
+/// Shown for entries saved without a name
+pub const MISSING_NAME: &str = "AAA";
+pub const HIGH_SCORES_FILE: &str = "high_scores.json";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HighScore {
+    /// Empty in files written before initials were asked for
+    #[serde(default)]
+    pub name: String,
+    pub score: u64,
+}
+
+impl HighScore {
+    pub fn display_name(&self) -> &str {
+        if self.name.is_empty() {
+            MISSING_NAME
+        } else {
+            &self.name
+        }
+    }
+}
+
+/// Loaded from [`HIGH_SCORES_FILE`] at startup and written back whenever it changes
+#[derive(Resource, Default, Debug)]
+pub struct HighScoreTable {
+    /// Best first, never longer than [`HIGH_SCORE_COUNT`]
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScoreTable {
+    /// Missing or unreadable files count as an empty table
+    pub fn load() -> Self {
+        let mut entries: Vec<HighScore> = high_scores_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        // Hand edits can leave it in any order or length
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        entries.truncate(HIGH_SCORE_COUNT);
+        Self { entries }
+    }
+
+    /// Whether `score` would make the table
+    pub fn qualifies(&self, score: u64) -> bool {
+        score > 0
+            && (self.entries.len() < HIGH_SCORE_COUNT
+                || self
+                    .entries
+                    .last()
+                    .is_some_and(|lowest| score > lowest.score))
+    }
+
+    /// Where `score` would go, below any equal scores
+    pub fn rank(&self, score: u64) -> usize {
+        self.entries.partition_point(|other| other.score >= score)
+    }
+
+    /// Puts `entry` in its place and drops whatever falls off the end
+    pub fn insert(&mut self, entry: HighScore) {
+        self.entries.insert(self.rank(entry.score), entry);
+        self.entries.truncate(HIGH_SCORE_COUNT);
+    }
+
+    /// The table as shown, with `pending` slotted in where it would land. The flag marks it.
+    pub fn rows(&self, pending: Option<HighScore>) -> Vec<(HighScore, bool)> {
+        let mut rows: Vec<(HighScore, bool)> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.clone(), false))
+            .collect();
+        if let Some(entry) = pending {
+            rows.insert(self.rank(entry.score), (entry, true));
+            rows.truncate(HIGH_SCORE_COUNT);
+        }
+        rows
+    }
+}
+
+/// `~/.asteroids_clone/high_scores.json`, next to the daily scores. `None` if there's no home
+/// directory to put it in.
+pub fn high_scores_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(DAILY_DIR).join(HIGH_SCORES_FILE))
+}
+
+pub fn save_high_scores(table: Res<HighScoreTable>) {
+    let Some(path) = high_scores_path() else {
+        error!("No home directory to save the high scores in");
+        return;
+    };
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            let contents =
+                serde_json::to_string_pretty(&table.entries).map_err(std::io::Error::other)?;
+            std::fs::write(&path, contents)
+        });
+    if let Err(err) = result {
+        error!("Could not write {}: {err}", path.display());
+    }
+}
+
+/// Initials being typed for a new high score. The game is paused while it's open.
+#[derive(Resource, Default, Debug)]
+pub struct NameInputState {
+    pub open: bool,
+    /// The score the initials are for
+    pub score: u64,
+    pub chars: Vec<char>,
+    /// Where the next letter goes
+    pub cursor: usize,
+}
+
+impl NameInputState {
+    pub fn open(score: u64) -> Self {
+        Self {
+            open: true,
+            score,
+            ..default()
+        }
+    }
+
+    /// Adds `c` at the cursor if it's a letter or digit and there's room, always uppercase
+    pub fn type_char(&mut self, c: char) {
+        if !c.is_ascii_alphanumeric() || self.chars.len() >= INITIALS_LEN {
+            return;
+        }
+
+        self.chars.insert(self.cursor, c.to_ascii_uppercase());
+        self.cursor += 1;
+    }
+
+    /// Removes the character before the cursor
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+
+        self.cursor -= 1;
+        self.chars.remove(self.cursor);
+    }
+
+    /// Moves the cursor by `by`, staying within the typed characters
+    pub fn move_cursor(&mut self, by: isize) {
+        self.cursor = self.cursor.saturating_add_signed(by).min(self.chars.len());
+    }
+
+    pub fn name(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    /// The initials with the unfilled ones as underscores and the cursor in brackets
+    pub fn display(&self) -> String {
+        (0..INITIALS_LEN)
+            .map(|i| {
+                let c = self.chars.get(i).copied().unwrap_or('_');
+                if i == self.cursor {
+                    format!("[{c}]")
+                } else {
+                    format!(" {c} ")
+                }
+            })
+            .collect()
+    }
+
+    /// The run as its table row, unfilled initials as underscores
+    pub fn entry(&self) -> HighScore {
+        let typed = self.chars.iter().copied();
+        HighScore {
+            name: typed
+                .chain(std::iter::repeat('_'))
+                .take(INITIALS_LEN)
+                .collect(),
+            score: self.score,
+        }
+    }
+}
+
+/// A run that made the table asks for initials. Sandbox runs don't count, and the autopilot
+/// never stops to type.
+pub fn prompt_for_initials(
+    mut deaths: MessageReader<PlayerDied>,
+    table: Res<HighScoreTable>,
+    mode: Res<GameMode>,
+    bot: Option<Res<Bot>>,
+    mut input: ResMut<NameInputState>,
+) {
+    let Some(score) = deaths.read().map(|death| death.score).max() else {
+        return;
+    };
+    if *mode != GameMode::Sandbox && bot.is_none() && !input.open && table.qualifies(score) {
+        *input = NameInputState::open(score);
+    }
+}
+
+/// Letters and digits type, Backspace deletes, the arrows move the cursor and Enter saves once
+/// all the initials are in. Escape gives the spot up. Keys pressed while it's closed are
+/// dropped, so whatever was held when the ship died doesn't end up in the initials.
+pub fn read_name_input(
+    mut keys: MessageReader<KeyboardInput>,
+    menu: Res<PauseMenu>,
+    mut input: ResMut<NameInputState>,
+    mut table: ResMut<HighScoreTable>,
+) {
+    if !input.open || menu.open {
+        keys.clear();
+        return;
+    }
+
+    for key in keys.read() {
+        if !key.state.is_pressed() || key.repeat {
+            continue;
+        }
+
+        match &key.logical_key {
+            Key::Enter if input.chars.len() == INITIALS_LEN => {
+                table.insert(HighScore {
+                    name: input.name(),
+                    score: input.score,
+                });
+                input.open = false;
+            }
+            Key::Escape => input.open = false,
+            Key::Backspace => input.backspace(),
+            Key::ArrowLeft => input.move_cursor(-1),
+            Key::ArrowRight => input.move_cursor(1),
+            Key::Character(text) => {
+                for c in text.chars() {
+                    input.type_char(c);
+                }
+            }
+            _ => {}
+        }
+
+        if !input.open {
+            break;
+        }
+    }
+}
+
+/// Releases every key before gameplay systems see them, so typing initials never fires the gun
+pub fn suppress_gameplay_input(mut btn_input: ResMut<ButtonInput<KeyCode>>) {
+    btn_input.reset_all();
+}
+
+/// The table on its own, opened from the pause menu
+#[derive(Resource, Default)]
+pub struct HighScoreScreen {
+    pub open: bool,
+}
+
+#[derive(Component)]
+pub struct NameEntryRoot;
+
+/// Title, initials and help, top to bottom
+#[derive(Component)]
+pub struct NameEntryLine(pub usize);
+
+#[derive(Component)]
+pub struct HighScoreRoot;
+
+/// Shown on the high score screen while the table is empty
+#[derive(Component)]
+pub struct NoHighScores;
+
+#[derive(Component)]
+pub struct HighScoreBack;
+
+/// Place `n` of the table. Both the name entry prompt and the high score screen have a set.
+#[derive(Component)]
+pub struct HighScoreRow(pub usize);
+
+fn spawn_rows(parent: &mut ChildSpawnerCommands, palette: &Palette) {
+    for row in 0..HIGH_SCORE_COUNT {
+        parent.spawn((
+            HighScoreRow(row),
+            Text::default(),
+            TextColor(palette.hud_text),
+            Node {
+                display: Display::None,
+                ..default()
+            },
+        ));
+    }
+}
+
+pub fn spawn_name_entry_ui(palette: Res<Palette>, mut cmds: Commands) {
+    cmds.spawn((
+        NameEntryRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(25),
+            left: percent(35),
+            width: percent(30),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(8),
+            padding: UiRect::all(px(16)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.85)),
+        // Under the pause menu, which can still open over it when the window loses focus
+        GlobalZIndex(7),
+    ))
+    .with_children(|root| {
+        for line in 0..3 {
+            root.spawn((
+                NameEntryLine(line),
+                Text::default(),
+                TextColor(palette.hud_text),
+                TextLayout::new_with_justify(Justify::Center),
+            ));
+        }
+        spawn_rows(root, &palette);
+    });
+}
+
+pub fn spawn_high_score_screen(locale: Res<Locale>, palette: Res<Palette>, mut cmds: Commands) {
+    cmds.spawn((
+        HighScoreRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(25),
+            left: percent(35),
+            width: percent(30),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(8),
+            padding: UiRect::all(px(16)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.9)),
+        // Keeps clicks off the pause menu underneath
+        FocusPolicy::Block,
+        GlobalZIndex(9),
+    ))
+    .with_children(|root| {
+        root.spawn((
+            LocalizedText("high_scores.screen_title"),
+            Text::new(locale.text("high_scores.screen_title")),
+        ));
+        root.spawn((
+            NoHighScores,
+            LocalizedText("high_scores.empty"),
+            Text::new(locale.text("high_scores.empty")),
+        ));
+        spawn_rows(root, &palette);
+        root.spawn((
+            HighScoreBack,
+            Button,
+            LocalizedText("high_scores.back"),
+            Text::new(locale.text("high_scores.back")),
+            Node {
+                padding: UiRect::axes(px(12), px(4)),
+                ..default()
+            },
+        ));
+    });
+}
+
+pub fn click_high_score_back(
+    buttons: Query<&Interaction, (With<HighScoreBack>, Changed<Interaction>)>,
+    mut screen: ResMut<HighScoreScreen>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        screen.open = false;
+    }
+}
+
+/// Shows the prompt and pauses the game while initials are being typed
+pub fn update_name_entry_ui(
+    input: Res<NameInputState>,
+    locale: Res<Locale>,
+    menu: Res<PauseMenu>,
+    mut time: ResMut<Time<Virtual>>,
+    mut root: Single<&mut Node, With<NameEntryRoot>>,
+    mut lines: Query<(&NameEntryLine, &mut Text)>,
+) {
+    if input.open {
+        time.pause();
+        root.display = Display::Flex;
+    } else {
+        if !menu.open {
+            time.unpause();
+        }
+        root.display = Display::None;
+        return;
+    }
+
+    for (line, mut text) in lines.iter_mut() {
+        text.0 = match line.0 {
+            0 => locale.text_with("high_scores.title", &[&locale.format_score(input.score)]),
+            1 => input.display(),
+            _ => locale.text("high_scores.help").to_string(),
+        };
+    }
+}
+
+pub fn update_high_score_screen(
+    screen: Res<HighScoreScreen>,
+    mut root: Single<&mut Node, With<HighScoreRoot>>,
+) {
+    root.display = if screen.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+}
+
+/// Fills in both tables. While initials are being typed the run shows up in its place,
+/// highlighted.
+pub fn update_high_score_rows(
+    table: Res<HighScoreTable>,
+    input: Res<NameInputState>,
+    locale: Res<Locale>,
+    palette: Res<Palette>,
+    mut rows: Query<(&HighScoreRow, &mut Text, &mut TextColor, &mut Node), Without<NoHighScores>>,
+    mut empty: Single<&mut Node, With<NoHighScores>>,
+) {
+    let pending = input.open.then(|| input.entry());
+    let shown = table.rows(pending);
+
+    empty.display = if shown.is_empty() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    for (row, mut text, mut color, mut node) in rows.iter_mut() {
+        let Some((entry, highlighted)) = shown.get(row.0) else {
+            node.display = Display::None;
+            continue;
+        };
+
+        node.display = Display::Flex;
+        text.0 = locale.text_with(
+            "high_scores.entry",
+            &[
+                &(row.0 + 1),
+                &entry.display_name(),
+                &locale.format_score(entry.score),
+            ],
+        );
+        color.0 = if *highlighted {
+            palette.highlight
+        } else {
+            palette.hud_text
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: u64) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            score,
+        }
+    }
+
+    #[test]
+    fn keeps_the_best_five() {
+        let mut table = HighScoreTable::default();
+        for (i, score) in [300, 100, 500, 200, 400].into_iter().enumerate() {
+            assert!(table.qualifies(score));
+            table.insert(entry(&i.to_string(), score));
+        }
+
+        assert!(!table.qualifies(100));
+        assert!(table.qualifies(150));
+        table.insert(entry("NEW", 150));
+        table.insert(entry("TIE", 500));
+
+        let names: Vec<(&str, u64)> = table
+            .entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.score))
+            .collect();
+        assert_eq!(
+            names,
+            [("2", 500), ("TIE", 500), ("4", 400), ("0", 300), ("3", 200)]
+        );
+        assert!(!HighScoreTable::default().qualifies(0));
+    }
+
+    #[test]
+    fn pending_run_is_highlighted_in_place() {
+        let table = HighScoreTable {
+            entries: vec![entry("ABC", 300), entry("DEF", 100)],
+        };
+
+        let rows = table.rows(Some(entry("X__", 200)));
+        let highlighted: Vec<bool> = rows.iter().map(|(_, highlighted)| *highlighted).collect();
+        assert_eq!(highlighted, [false, true, false]);
+        assert_eq!(rows[1].0.name, "X__");
+    }
+
+    #[test]
+    fn old_entries_without_a_name_show_placeholder() {
+        let entries: Vec<HighScore> = serde_json::from_str(r#"[{"score": 42}]"#).unwrap();
+        assert_eq!(entries[0].display_name(), MISSING_NAME);
+        assert_eq!(entry("ZED", 1).display_name(), "ZED");
+    }
+
+    #[test]
+    fn initials_edit_at_the_cursor() {
+        let mut input = NameInputState::open(100);
+        for c in "a-bcd".chars() {
+            input.type_char(c);
+        }
+        assert_eq!(input.name(), "ABC");
+        assert_eq!(input.cursor, 3);
+
+        input.move_cursor(-2);
+        input.backspace();
+        assert_eq!(input.name(), "BC");
+        assert_eq!(input.display(), "[B] C  _ ");
+        assert_eq!(input.entry().name, "BC_");
+
+        input.type_char('x');
+        input.move_cursor(10);
+        assert_eq!(input.name(), "XBC");
+        assert_eq!(input.cursor, 3);
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{events::ShipHit, palette::Palette, settings::Settings};
+
+/// Feedback for hits the ship survives: a brief hit-stop and a red flash around the screen
+/// edges. Each can be turned off in the settings. The impact sound is played with the rest of
+/// the sound effects, see [`play_ship_hit_sfx`](crate::sfx::play_ship_hit_sfx).
+pub fn impact_plugin(app: &mut App) {
+    app.init_resource::<HitStop>();
+
+    app.add_systems(Startup, spawn_damage_flash);
+    app.add_systems(
+        Update,
+        // A hit-stop isn't counted down in the frame it starts
+        (end_hit_stop, start_impact_feedback, fade_damage_flash).chain(),
+    );
+}
+
+/// How long the simulation freezes for
+pub const HIT_STOP: Duration = Duration::from_millis(80);
+/// How long the red edges take to fade out
+pub const DAMAGE_FLASH_SECS: f32 = 0.4;
+/// Width of the red band along each edge of the screen
+pub const DAMAGE_FLASH_WIDTH: f32 = 24.0;
+
+/// Counts down in real time to restoring the game speed. Virtual time stands still during the
+/// hit-stop, so anything driven by it would never get there.
+#[derive(Resource, Default)]
+pub struct HitStop {
+    pub restore: Option<Timer>,
+}
+
+/// Full-screen overlay whose border is the damage flash
+#[derive(Component)]
+pub struct DamageFlash {
+    pub fade: Timer,
+}
+
+pub fn spawn_damage_flash(mut cmds: Commands) {
+    let mut fade = Timer::from_seconds(DAMAGE_FLASH_SECS, TimerMode::Once);
+    fade.finish();
+
+    cmds.spawn((
+        DamageFlash { fade },
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            border: UiRect::all(px(DAMAGE_FLASH_WIDTH)),
+            ..default()
+        },
+        BorderColor::all(Color::NONE),
+        GlobalZIndex(5),
+    ));
+}
+
+pub fn start_impact_feedback(
+    mut hits: MessageReader<ShipHit>,
+    settings: Res<Settings>,
+    mut hit_stop: ResMut<HitStop>,
+    mut time: ResMut<Time<Virtual>>,
+    mut flash: Single<&mut DamageFlash>,
+) {
+    if hits.read().count() == 0 {
+        return;
+    }
+
+    if settings.hit_stop {
+        time.set_relative_speed(0.0);
+        hit_stop.restore = Some(Timer::new(HIT_STOP, TimerMode::Once));
+    }
+    if settings.damage_flash {
+        flash.fade.reset();
+    }
+}
+
+pub fn end_hit_stop(
+    real: Res<Time<Real>>,
+    mut hit_stop: ResMut<HitStop>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let Some(restore) = hit_stop.restore.as_mut() else {
+        return;
+    };
+
+    if restore.tick(real.delta()).is_finished() {
+        time.set_relative_speed(1.0);
+        hit_stop.restore = None;
+    }
+}
+
+pub fn fade_damage_flash(
+    real: Res<Time<Real>>,
+    palette: Res<Palette>,
+    flash: Single<(&mut DamageFlash, &mut BorderColor)>,
+) {
+    let (mut flash, mut border) = flash.into_inner();
+    if flash.fade.is_finished() {
+        return;
+    }
+    flash.fade.tick(real.delta());
+
+    let alpha = palette.damage_flash.alpha() * flash.fade.fraction_remaining();
+    *border = BorderColor::all(palette.damage_flash.with_alpha(alpha));
+}
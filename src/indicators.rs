@@ -0,0 +1,120 @@
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    Asteroid, GameMode, MainCamera, PlayerShip, palette::Palette, physics::Velocity,
+    playfield::view_half_extents, settings::Settings,
+};
+
+pub fn indicators_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            highlight_last_asteroids.run_if(resource_equals(GameMode::Waves)),
+            draw_velocity_indicator
+                .run_if(|settings: Res<Settings>| settings.show_velocity_indicator),
+        ),
+    );
+}
+
+/// Highlighting kicks in once fewer than this many asteroids are left in a wave
+pub const LAST_ASTEROIDS: usize = 3;
+const PULSE_HZ: f32 = 1.5;
+/// Distance between the window edge and the tip of an edge arrow
+const EDGE_INSET: f32 = 20.0;
+const ARROW_LENGTH: f32 = 30.0;
+/// Below this speed the travel arrow is hidden, it only jitters around at a near standstill
+const MIN_INDICATOR_SPEED: f32 = 10.0;
+/// Travel arrow length per unit of speed
+const INDICATOR_SCALE: f32 = 0.5;
+const INDICATOR_MAX_LENGTH: f32 = 150.0;
+
+/// Draws an arrow on the edge of the window pointing toward an off-screen `target`.
+///
+/// `view_center` is where the camera is looking and `half_extents` is half the window size.
+pub fn draw_edge_indicator(
+    gizmos: &mut Gizmos,
+    view_center: Vec2,
+    half_extents: Vec2,
+    target: Vec2,
+    color: Color,
+) {
+    let Some(dir) = (target - view_center).try_normalize() else {
+        return;
+    };
+
+    let inset = half_extents - Vec2::splat(EDGE_INSET);
+    let to_edge = (inset.x / dir.x.abs()).min(inset.y / dir.y.abs());
+    let tip = view_center + dir * to_edge;
+
+    gizmos.arrow_2d(tip - dir * ARROW_LENGTH, tip, color);
+}
+
+/// Points out the stragglers at the end of a wave: arrows for asteroids off-screen and a faint
+/// ring around the ones still in view. Gizmos are redrawn every frame, so a marker disappears
+/// the same frame its asteroid is destroyed.
+pub fn highlight_last_asteroids(
+    asteroids: Query<&Transform, With<Asteroid>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Transform, &Projection), With<MainCamera>>,
+    time: Res<Time>,
+    palette: Res<Palette>,
+    mut gizmos: Gizmos,
+) {
+    if asteroids.iter().len() >= LAST_ASTEROIDS {
+        return;
+    }
+
+    let pulse = 0.5 + 0.5 * (time.elapsed_secs() * PULSE_HZ * TAU).sin();
+    let (camera, projection) = *camera;
+    let half_extents = view_half_extents(&window, projection);
+    let view_center = camera.translation.xy();
+
+    for tsf in asteroids {
+        let pos = tsf.translation.xy();
+        let offset = pos - view_center;
+
+        if offset.x.abs() > half_extents.x || offset.y.abs() > half_extents.y {
+            let color = palette.highlight.with_alpha(0.4 + 0.5 * pulse);
+            draw_edge_indicator(&mut gizmos, view_center, half_extents, pos, color);
+        } else {
+            let color = palette.highlight.with_alpha(0.1 + 0.2 * pulse);
+            gizmos.circle_2d(pos, 60.0 + 5.0 * pulse, color);
+        }
+    }
+}
+
+/// Draws the ship's actual direction of travel (length grows with speed) and a faint tick for
+/// where it is facing. Gizmos are drawn in world space, so the aid never spins with the ship.
+pub fn draw_velocity_indicator(
+    ship: Option<Single<(&Transform, &Velocity), With<PlayerShip>>>,
+    palette: Res<Palette>,
+    mut gizmos: Gizmos,
+) {
+    let Some(ship) = ship else {
+        return;
+    };
+    let (tsf, vel) = ship.into_inner();
+    let pos = tsf.translation.xy();
+
+    let facing = (tsf.rotation * Vec3::Y).xy();
+    gizmos.line_2d(
+        pos + facing * 55.0,
+        pos + facing * 70.0,
+        palette.velocity_indicator.with_alpha(0.3),
+    );
+
+    let speed = vel.linear.length();
+    if speed < MIN_INDICATOR_SPEED {
+        return;
+    }
+
+    let length = (speed * INDICATOR_SCALE).min(INDICATOR_MAX_LENGTH);
+    let dir = vel.linear / speed;
+    gizmos.arrow_2d(
+        pos,
+        pos + dir * length,
+        palette.velocity_indicator.with_alpha(0.8),
+    );
+}
@@ -0,0 +1,1460 @@
+// Bevy systems routinely have long parameter lists and deeply nested query types
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use std::f32::consts::{PI, TAU};
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    time::Stopwatch,
+    window::PrimaryWindow,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asset_source::{asset_path, asset_source_plugin},
+    belt::belt_plugin,
+    bloom::bloom_plugin,
+    boss::boss_plugin,
+    bot::bot_plugin,
+    charge::{
+        CHARGE_ACCEL_FACTOR, CHARGE_MIN_SECS, ChargedShot, Charging, charge_plugin, charged_scale,
+    },
+    comet::comet_plugin,
+    config::{GameConfig, SpawnConfig},
+    controls::controls_plugin,
+    credits::{Credits, credits_plugin},
+    daily::daily_plugin,
+    diagnostics::diagnostics_plugin,
+    display::display_plugin,
+    effects::{GunFlash, Invincible, effects_plugin},
+    events::{
+        AsteroidDestroyed, AsteroidSpawned, DestroyAsteroid, LaserHit, PlayerDied, ShipHit,
+        ShotFired, events_plugin,
+    },
+    frenzy::frenzy_plugin,
+    grid::grid_plugin,
+    high_scores::high_scores_plugin,
+    impact::impact_plugin,
+    indicators::indicators_plugin,
+    locale::{Locale, locale_plugin},
+    magnetic::{MAGNETIC_CHANCE, magnetic_bundle, magnetic_plugin},
+    mines::{detonate_mines, mines_plugin, spawn_mine},
+    minimap::minimap_plugin,
+    mutators::mutators_plugin,
+    palette::{Palette, palette_plugin},
+    pause::pause_plugin,
+    photo::photo_plugin,
+    physics::{
+        CircleCollider, CollisionBudget, CollisionEvent, CollisionGroup, Disabled, ForceAccum,
+        Heading, IgnoreCollisionWith, LowPriorityCollisions, MaxSpeed, PolygonCollider,
+        RadialImpulse, ScreenWrap, Velocity, find_safe_spawn_position, physics_plugin, wrap_angle,
+    },
+    playfield::{PlayBounds, playfield_plugin},
+    pool::{Pool, clear_pools, pool_plugin, release},
+    powerups::{
+        Dilatable, POWERUP_DROP_CHANCE, POWERUP_INTERVAL_SECS, PowerUpKind, powerups_plugin,
+        random_powerup_position, spawn_powerup,
+    },
+    profile::profile_plugin,
+    respawn::{PendingRespawn, respawn_plugin},
+    rumble::rumble_plugin,
+    sandbox::{SANDBOX_BOUNCE_SPEED, sandbox_plugin},
+    settings::{ControlMode, Settings, settings_plugin},
+    sfx::sfx_plugin,
+    shadows::{AddShadow, shadows_plugin},
+    shop::shop_plugin,
+    sleep::sleep_plugin,
+    stealth::{STEALTH_CHANCE, stealth_bundle, stealth_plugin},
+    swarm::{SWARM_CHANCE, SWARM_SPAWN_DISTANCE, spawn_swarm, swarm_plugin},
+    telegraph::{SpawnTelegraph, telegraph_plugin},
+    telemetry::telemetry_plugin,
+    tutorial::tutorial_plugin,
+    waves::waves_plugin,
+};
+
+pub mod asset_source;
+pub mod belt;
+pub mod bloom;
+pub mod boss;
+pub mod bot;
+pub mod charge;
+pub mod comet;
+pub mod config;
+#[cfg(debug_assertions)]
+pub mod console;
+pub mod controls;
+pub mod credits;
+pub mod daily;
+pub mod diagnostics;
+pub mod display;
+pub mod effects;
+pub mod events;
+pub mod frenzy;
+pub mod grid;
+#[cfg(all(debug_assertions, feature = "debug-tools"))]
+pub mod heatmap;
+pub mod high_scores;
+pub mod impact;
+pub mod indicators;
+pub mod locale;
+pub mod magnetic;
+pub mod mines;
+pub mod minimap;
+pub mod mutators;
+pub mod palette;
+pub mod pause;
+pub mod photo;
+pub mod physics;
+pub mod playfield;
+pub mod pool;
+pub mod powerups;
+pub mod profile;
+pub mod respawn;
+pub mod rumble;
+pub mod sandbox;
+pub mod settings;
+pub mod sfx;
+pub mod shadows;
+pub mod shop;
+pub mod sleep;
+pub mod stealth;
+pub mod swarm;
+pub mod telegraph;
+pub mod telemetry;
+pub mod tutorial;
+pub mod waves;
+
+/// Everything that makes up the game, minus the engine's own plugins
+pub fn game_plugin(app: &mut App) {
+    // Plugins may read the config while building
+    app.insert_resource(GameConfig::load());
+
+    // Lets inspector and scene tooling see the core gameplay state
+    app.register_type::<PlayerShip>();
+    app.register_type::<Asteroid>();
+    app.register_type::<LaserShot>();
+    app.register_type::<GameCleanup>();
+    app.register_type::<GameStats>();
+
+    app.add_plugins(asset_source_plugin);
+    app.add_plugins(physics_plugin);
+    app.add_plugins(playfield_plugin);
+    app.add_plugins(grid_plugin);
+    app.add_plugins(impact_plugin);
+    app.add_plugins(sleep_plugin);
+    app.add_plugins(events_plugin);
+    app.add_plugins(settings_plugin);
+    app.add_plugins(profile_plugin);
+    app.add_plugins(palette_plugin);
+    app.add_plugins(bloom_plugin);
+    app.add_plugins(locale_plugin);
+    app.add_plugins(display_plugin);
+    app.add_plugins(diagnostics_plugin);
+    app.add_plugins(controls_plugin);
+    app.add_plugins(photo_plugin);
+    app.add_plugins(pause_plugin);
+    app.add_plugins(mutators_plugin);
+    app.add_plugins(pool_plugin);
+    app.add_plugins(respawn_plugin);
+    app.add_plugins(powerups_plugin);
+    app.add_plugins(credits_plugin);
+    app.add_plugins(effects_plugin);
+    app.add_plugins(rumble_plugin);
+    app.add_plugins(sfx_plugin);
+    app.add_plugins(shadows_plugin);
+    app.add_plugins(mines_plugin);
+    app.add_plugins(swarm_plugin);
+    app.add_plugins(stealth_plugin);
+    app.add_plugins(magnetic_plugin);
+    app.add_plugins(waves_plugin);
+    app.add_plugins(frenzy_plugin);
+    app.add_plugins(boss_plugin);
+    app.add_plugins(tutorial_plugin);
+    app.add_plugins(shop_plugin);
+    app.add_plugins(high_scores_plugin);
+    app.add_plugins(belt_plugin);
+    app.add_plugins(comet_plugin);
+    app.add_plugins(indicators_plugin);
+    app.add_plugins(minimap_plugin);
+    app.add_plugins(telegraph_plugin);
+    app.add_plugins(telemetry_plugin);
+    app.add_plugins(daily_plugin);
+    app.add_plugins(bot_plugin);
+    app.add_plugins(charge_plugin);
+    app.add_plugins(sandbox_plugin);
+    #[cfg(debug_assertions)]
+    app.add_plugins(console::console_plugin);
+    #[cfg(all(debug_assertions, feature = "debug-tools"))]
+    app.add_plugins(heatmap::heatmap_plugin);
+
+    let spawn_rate = app.world().resource::<GameConfig>().spawning.spawn_rate;
+    app.insert_resource(GameStats {
+        spawn_rate,
+        ..default()
+    });
+    app.init_resource::<ShipInput>();
+    app.insert_resource(GameMode::from_args());
+    app.insert_resource(GameRng::from_args());
+
+    app.add_systems(PreStartup, (spawn_hud_root, load_assets));
+    app.add_systems(Startup, (setup_scene, spawn_decorative_background));
+
+    app.add_systems(
+        Update,
+        (
+            game_tick,
+            (read_ship_input, control_ship).chain(),
+            handle_collisions,
+            destroy_asteroids
+                .after(handle_collisions)
+                .after(detonate_mines),
+            check_damage_state.after(handle_collisions),
+            release_offscreen_lasers,
+            rescatter_background
+                .run_if(resource_changed::<PlayBounds>.and(not(resource_added::<PlayBounds>))),
+        ),
+    );
+}
+
+/// How asteroids are fed into the game, picked once at startup
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    /// Asteroids keep spawning at random, faster and faster
+    #[default]
+    Endless,
+    /// Fixed batches of asteroids, the next one starts when the field is clear
+    Waves,
+    /// No spawning and no dying, for practice and reproducing bugs
+    Sandbox,
+}
+
+impl GameMode {
+    /// Wave mode is opted into with `--waves`, sandbox with `--sandbox`
+    pub fn from_args() -> Self {
+        if std::env::args().any(|arg| arg == "--waves") {
+            GameMode::Waves
+        } else if std::env::args().any(|arg| arg == "--sandbox") {
+            GameMode::Sandbox
+        } else {
+            GameMode::Endless
+        }
+    }
+}
+
+/// Seeded randomness for anything that should play out the same way twice from the same seed.
+/// Everything that spawns, drops or rolls during a run draws from it, only cosmetics like the
+/// background use `rand::rng()`.
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl GameRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Uses the seed passed with `--seed <n>`, or a random one. The daily challenge reseeds it
+    /// when it starts.
+    pub fn from_args() -> Self {
+        let seed = std::env::args()
+            .skip_while(|arg| arg != "--seed")
+            .nth(1)
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or_else(|| rand::rng().random());
+        info!("Game seed: {seed}");
+        Self::seeded(seed)
+    }
+}
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct GameStats {
+    pub score: u64,
+    /// Current wave number, only advances in [`GameMode::Waves`]
+    pub wave: u32,
+    pub stopwatch: Stopwatch,
+    /// Expected asteroid spawns per second before the ramp, from
+    /// [`SpawnConfig::spawn_rate`] at the start of each run
+    pub spawn_rate: f32,
+    pub powerup_timer: Timer,
+    /// Applied to every point awarded through [`GameStats::award`]
+    pub score_multiplier: f32,
+    /// The run's [`RunModifiers`](mutators::RunModifiers) multiplier, applied on top of
+    /// `score_multiplier` for the whole run
+    pub mutator_multiplier: f32,
+    /// Extra multiplier while a [`Frenzy`](frenzy::Frenzy) is running, 1 the rest of the time
+    pub frenzy_multiplier: f32,
+    /// Extra multiplier while an [`AsteroidBelt`](belt::AsteroidBelt) shower is coming through and
+    /// for [`BeltConfig::score_tail_secs`](config::BeltConfig::score_tail_secs) after
+    pub belt_multiplier: f32,
+}
+
+impl GameStats {
+    /// Saturates instead of wrapping, however long the run goes
+    pub fn award(&mut self, points: u32) {
+        let multiplier = f64::from(self.score_multiplier)
+            * f64::from(self.mutator_multiplier)
+            * f64::from(self.frenzy_multiplier)
+            * f64::from(self.belt_multiplier);
+        let points = (f64::from(points) * multiplier).round() as u64;
+        self.score = self.score.saturating_add(points);
+    }
+}
+
+/// How many asteroids to spawn over a tick of `dt` seconds at `rate` per second. Whole expected
+/// spawns always happen and the fraction left over is a coin toss, so the count averages out to
+/// `rate * dt` however the frames are sliced, long frames included.
+pub fn spawn_count(rate: f32, dt: f32, rng: &mut impl Rng) -> u32 {
+    let expected = (rate * dt).max(0.0);
+    expected as u32 + u32::from(rng.random_bool(expected.fract().into()))
+}
+
+impl Default for GameStats {
+    fn default() -> Self {
+        Self {
+            score: Default::default(),
+            wave: Default::default(),
+            stopwatch: Default::default(),
+            spawn_rate: SpawnConfig::default().spawn_rate,
+            powerup_timer: Timer::from_seconds(POWERUP_INTERVAL_SECS, TimerMode::Repeating),
+            score_multiplier: 1.0,
+            mutator_multiplier: 1.0,
+            frenzy_multiplier: 1.0,
+            belt_multiplier: 1.0,
+        }
+    }
+}
+
+/// Handles to every texture used by the game.
+///
+/// These are strong handles, and every sprite is spawned with a clone of one of them. When an
+/// image is hot reloaded the asset behind the handle is replaced in place, so live sprites
+/// pick up the new texture on the next frame without any extra bookkeeping.
+#[derive(Resource, Default)]
+pub struct GameAssets {
+    /// Loaded from [`METEOR_SPRITES`], in the same order
+    pub meteors: Vec<Handle<Image>>,
+    /// Loaded from [`DAMAGED_METEOR_SPRITES`], lined up with `meteors`
+    pub meteors_damaged: Vec<Handle<Image>>,
+    pub ship: Handle<Image>,
+    pub laser: Handle<Image>,
+    pub mine: Handle<Image>,
+    pub shockwave: Handle<Image>,
+    pub magnetic_field: Handle<Image>,
+    pub credit: Handle<Image>,
+    /// Indexed by [`PowerUpKind`]
+    pub powerups: Vec<Handle<Image>>,
+}
+
+/// Every asteroid sprite. [`AsteroidTier::sprites`](config::AsteroidTier::sprites) picks from
+/// these by index.
+pub const METEOR_SPRITES: &[&str] = &[
+    "kenney-space/PNG/Meteors/meteorGrey_big1.png",
+    "kenney-space/PNG/Meteors/meteorGrey_big2.png",
+    "kenney-space/PNG/Meteors/meteorGrey_big3.png",
+    "kenney-space/PNG/Meteors/meteorGrey_big4.png",
+    "kenney-space/PNG/Meteors/meteorGrey_med1.png",
+    "kenney-space/PNG/Meteors/meteorGrey_med2.png",
+    "kenney-space/PNG/Meteors/meteorGrey_small1.png",
+    "kenney-space/PNG/Meteors/meteorGrey_small2.png",
+];
+
+/// What each of [`METEOR_SPRITES`] looks like once it's been worn down to half health. The pack
+/// has no cracked meteors, so the brown ones of the same shape stand in.
+pub const DAMAGED_METEOR_SPRITES: &[&str] = &[
+    "kenney-space/PNG/Meteors/meteorBrown_big1.png",
+    "kenney-space/PNG/Meteors/meteorBrown_big2.png",
+    "kenney-space/PNG/Meteors/meteorBrown_big3.png",
+    "kenney-space/PNG/Meteors/meteorBrown_big4.png",
+    "kenney-space/PNG/Meteors/meteorBrown_med1.png",
+    "kenney-space/PNG/Meteors/meteorBrown_med3.png",
+    "kenney-space/PNG/Meteors/meteorBrown_small1.png",
+    "kenney-space/PNG/Meteors/meteorBrown_small2.png",
+];
+
+pub fn load_assets(asset_server: Res<AssetServer>, mut cmds: Commands) {
+    let assets = GameAssets {
+        ship: asset_server.load(asset_path("kenney-space/PNG/playerShip1_orange.png")),
+        laser: asset_server.load(asset_path("kenney-space/PNG/Lasers/laserRed08.png")),
+        mine: asset_server.load(asset_path("kenney-space/PNG/Parts/turretBase_small.png")),
+        shockwave: asset_server.load(asset_path("kenney-space/PNG/Effects/shield3.png")),
+        magnetic_field: asset_server.load(asset_path("kenney-space/PNG/Effects/shield2.png")),
+        credit: asset_server.load(asset_path("kenney-space/PNG/Power-ups/star_gold.png")),
+        meteors: METEOR_SPRITES
+            .iter()
+            .map(|path| asset_server.load(asset_path(path)))
+            .collect(),
+        meteors_damaged: DAMAGED_METEOR_SPRITES
+            .iter()
+            .map(|path| asset_server.load(asset_path(path)))
+            .collect(),
+        powerups: PowerUpKind::ALL
+            .iter()
+            .map(|kind| asset_server.load(asset_path(kind.sprite_path())))
+            .collect(),
+    };
+
+    cmds.insert_resource(assets);
+}
+
+/// How long the ship is untouchable after spawning
+pub const SPAWN_INVINCIBILITY_SECS: f32 = 2.0;
+/// Outline of the ship sprite's hull, nose up
+pub const SHIP_HULL: [Vec2; 3] = [
+    Vec2::new(0.0, 30.0),
+    Vec2::new(-20.0, -25.0),
+    Vec2::new(20.0, -25.0),
+];
+/// Thrust against drag tops out at half of this, the rest is headroom for speed boosts
+pub const SHIP_MAX_SPEED: f32 = 400.0;
+/// How long a fresh laser passes through the ship that fired it
+pub const LASER_SHOOTER_GRACE_SECS: f32 = 0.1;
+
+/// Sets up the game scene
+/// - Spawns the player
+/// - Spawns 10 asteroids
+/// - Spawns a camera
+pub fn setup_scene(
+    mut cmds: Commands,
+    assets: Res<GameAssets>,
+    palette: Res<Palette>,
+    hud: Single<Entity, With<HudRoot>>,
+) {
+    //Spawns a NEW entity with the specified components / bundle
+    // The minimap camera draws later, so the HUD has to be pinned to this one
+    cmds.spawn((Camera2d, MainCamera, IsDefaultUiCamera, GameCleanup));
+
+    cmds.spawn((
+        Velocity::default(),
+        Heading::default(),
+        GameCleanup,
+        PlayerShip::default(),
+        Invincible::new(SPAWN_INVINCIBILITY_SECS),
+        MaxSpeed(SHIP_MAX_SPEED),
+        Sprite::from_image(assets.ship.clone()),
+        AddShadow,
+        // The circle only picks out what's near enough to test against the hull
+        CircleCollider { radius: 50.0 },
+        PolygonCollider {
+            vertices: SHIP_HULL.to_vec(),
+        },
+    ));
+
+    // Spawns the text
+    cmds.spawn((
+        ScoreText,
+        Text::default(),
+        TextColor(palette.hud_text),
+        palette.hud_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+        GameCleanup,
+        ChildOf(*hud),
+    ));
+}
+
+/// Parent of every HUD node, so the whole HUD can be hidden by flipping its visibility
+#[derive(Component)]
+pub struct HudRoot;
+
+pub fn spawn_hud_root(mut cmds: Commands) {
+    cmds.spawn((
+        HudRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        Pickable::IGNORE,
+    ));
+}
+
+/// How many decorative asteroids drift around behind the playfield
+pub const BACKGROUND_ASTEROIDS: usize = 20;
+
+/// Spawns the distant, slow asteroids in the background. They have no collider and no
+/// [`GameCleanup`], so they never interact with gameplay and live through restarts.
+pub fn spawn_decorative_background(
+    assets: Res<GameAssets>,
+    config: Res<GameConfig>,
+    bounds: Res<PlayBounds>,
+    palette: Res<Palette>,
+    mut cmds: Commands,
+) {
+    let mut rng = rand::rng();
+    let bounds = bounds.wrap();
+
+    for _ in 0..BACKGROUND_ASTEROIDS {
+        let pos = Vec2::new(
+            rng.random_range(-bounds.x..bounds.x),
+            rng.random_range(-bounds.y..bounds.y),
+        );
+        let sprites = &config.asteroids.large.sprites;
+        let sprite = &assets.meteors[sprites[rng.random_range(0..sprites.len())]];
+        let speed = rng.random_range(5.0..15.0);
+
+        cmds.spawn((
+            BackgroundAsteroid,
+            Sprite {
+                color: palette.background_asteroid,
+                ..Sprite::from_image(sprite.clone())
+            },
+            Velocity {
+                linear: Vec2::from_angle(rng.random_range(-PI..PI)) * speed,
+                linear_drag: Vec2::ZERO,
+                angular: rng.random_range(-0.2..0.2),
+                angular_drag: 0.0,
+            },
+            ScreenWrap,
+            Transform::from_xyz(pos.x, pos.y, -5.0),
+        ));
+    }
+}
+
+/// Spreads the background asteroids back over the whole play area after it changes size, so a
+/// grown window isn't left with an empty border
+pub fn rescatter_background(
+    bounds: Res<PlayBounds>,
+    mut asteroids: Query<&mut Transform, With<BackgroundAsteroid>>,
+) {
+    let mut rng = rand::rng();
+    let bounds = bounds.wrap();
+
+    for mut tsf in asteroids.iter_mut() {
+        tsf.translation.x = rng.random_range(-bounds.x..bounds.x);
+        tsf.translation.y = rng.random_range(-bounds.y..bounds.y);
+    }
+}
+
+/// Random spots tried for each new asteroid before giving up on it
+pub const SPAWN_POSITION_ATTEMPTS: u32 = 8;
+
+pub fn game_tick(
+    time: Res<Time>,
+    mut cmds: Commands,
+    mut game_stats: ResMut<GameStats>,
+    mut text: Single<&mut Text, With<ScoreText>>,
+    locale: Res<Locale>,
+    credits: Res<Credits>,
+    mode: Res<GameMode>,
+    ship: Single<&Transform, With<PlayerShip>>,
+    bounds: Res<PlayBounds>,
+    budget: Res<CollisionBudget>,
+    config: Res<GameConfig>,
+    mut game_rng: ResMut<GameRng>,
+    asteroids: Query<(&Transform, &CircleCollider), With<Asteroid>>,
+    telegraphs: Query<&SpawnTelegraph>,
+) {
+    game_stats.stopwatch.tick(time.delta());
+    game_stats.powerup_timer.tick(time.delta());
+
+    let rand = &mut game_rng.rng;
+
+    for _ in 0..game_stats.powerup_timer.times_finished_this_tick() {
+        let kind = PowerUpKind::random(rand);
+        let pos = random_powerup_position(rand, bounds.half_extents, ship.translation.xy());
+        cmds.run_system_cached_with(spawn_powerup, (kind, pos));
+    }
+
+    // Hold off on adding more asteroids while collision detection is struggling
+    let spawns = match *mode {
+        GameMode::Endless if !budget.degraded => {
+            let elapsed = game_stats.stopwatch.elapsed_secs();
+            let rate = config.spawning.ramped_rate(game_stats.spawn_rate, elapsed);
+            spawn_count(rate, time.delta_secs(), rand)
+        }
+        _ => 0,
+    };
+
+    // Where asteroids are, or are about to appear, so new ones don't land on top of them
+    let mut occupied: Vec<(Vec2, f32)> = if spawns == 0 {
+        vec![]
+    } else {
+        asteroids
+            .iter()
+            .map(|(tsf, collider)| (tsf.translation.xy(), collider.radius))
+            .chain(telegraphs.iter().map(|telegraph| {
+                let tier = config.asteroids.get(telegraph.asteroid.size);
+                (telegraph.asteroid.location, tier.scaled_radius())
+            }))
+            .collect()
+    };
+
+    for _ in 0..spawns {
+        if rand.random_bool(SWARM_CHANCE) {
+            let angle = rand.random_range(-PI..PI);
+            cmds.run_system_cached_with(
+                spawn_swarm,
+                Vec2::from_angle(angle) * SWARM_SPAWN_DISTANCE,
+            );
+        } else {
+            // Come in from the edge of the play area, spawning near the middle could land an
+            // asteroid right on top of the ship. With no clear spot this spawn is skipped.
+            let size = config.spawning.random_size(game_stats.score, rand);
+            let radius = config.asteroids.get(size).scaled_radius();
+            let Some(pos) = find_safe_spawn_position(
+                rand,
+                bounds.wrap(),
+                SPAWN_POSITION_ATTEMPTS,
+                radius,
+                &occupied,
+            ) else {
+                continue;
+            };
+            occupied.push((pos, radius));
+
+            let to_center = -pos.normalize_or_zero();
+            let rotation = f32::atan2(-to_center.x, to_center.y) + rand.random_range(-0.5..0.5);
+            let speed = rand.random_range(50.0..200.0);
+            let angvel = rand.random_range(-PI..PI);
+            let asteroid = AsteroidConfig {
+                location: pos,
+                heading: rotation,
+                speed,
+                angvel,
+                size,
+                group: None,
+            };
+
+            let telegraph_secs = config.spawning.telegraph_secs;
+            if telegraph_secs > 0.0 {
+                cmds.spawn((SpawnTelegraph::new(asteroid, telegraph_secs), GameCleanup));
+            } else {
+                cmds.run_system_cached_with(spawn_asteroid, asteroid);
+            }
+        }
+    }
+
+    // Displays Score while in game
+    let score = locale.format_score(game_stats.score);
+    let credits = locale.number(credits.0.into());
+    text.0 = match *mode {
+        GameMode::Endless => locale.text_with(
+            "hud.endless",
+            &[
+                &score,
+                &locale.duration(game_stats.stopwatch.elapsed_secs()),
+                &credits,
+            ],
+        ),
+        GameMode::Waves => locale.text_with(
+            "hud.waves",
+            &[&score, &locale.number(game_stats.wave.into()), &credits],
+        ),
+        GameMode::Sandbox => locale.text("hud.sandbox").to_string(),
+    };
+}
+
+/// What the ship is being told to do this frame, filled from the keyboard by [`read_ship_input`]
+/// or by the [`bot`] autopilot
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ShipInput {
+    pub thrust: bool,
+    pub reverse: bool,
+    pub rotate_left: bool,
+    pub rotate_right: bool,
+    /// Only set on the frame the shot should go off
+    pub fire: bool,
+    /// Fire is held down, charging a bigger shot for when it's let go
+    pub fire_held: bool,
+    /// Only set on the frame the mine should be dropped
+    pub secondary: bool,
+}
+
+impl ShipInput {
+    /// Sets the rotate inputs to turn the ship toward `direction`, stopping within `deadzone`
+    /// radians of it. Returns how far off the ship is pointing right now.
+    pub fn steer_toward(
+        &mut self,
+        heading: Heading,
+        velocity: &Velocity,
+        direction: Vec2,
+        deadzone: f32,
+    ) -> f32 {
+        // Same heading convention as the ship, zero points up
+        let wanted = f32::atan2(-direction.x, direction.y);
+        let off_by = wrap_angle(wanted - heading.0);
+
+        // Lead with the current spin so the ship doesn't overshoot and wobble
+        let correction = off_by - velocity.angular * 0.3;
+        self.rotate_left = correction > deadzone;
+        self.rotate_right = correction < -deadzone;
+        off_by
+    }
+}
+
+/// How close to the cursor the ship turns in [`ControlMode::MouseAim`]
+const MOUSE_AIM_DEADZONE: f32 = 0.05;
+
+pub fn read_ship_input(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    ship: Single<(&Transform, &Heading, &Velocity), With<PlayerShip>>,
+    mut input: ResMut<ShipInput>,
+) {
+    let bindings = &settings.bindings;
+    *input = ShipInput {
+        thrust: btn_input.pressed(bindings.thrust),
+        reverse: btn_input.pressed(bindings.reverse),
+        rotate_left: btn_input.pressed(bindings.rotate_left),
+        rotate_right: btn_input.pressed(bindings.rotate_right),
+        fire: btn_input.just_pressed(bindings.fire),
+        fire_held: btn_input.pressed(bindings.fire),
+        secondary: btn_input.just_pressed(bindings.secondary),
+    };
+
+    if settings.control_mode != ControlMode::MouseAim {
+        return;
+    }
+
+    let (camera, camera_tsf) = camera.into_inner();
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_tsf, cursor).ok())
+    else {
+        return;
+    };
+
+    let (ship_tsf, ship_heading, ship_vel) = ship.into_inner();
+    input.steer_toward(
+        *ship_heading,
+        ship_vel,
+        cursor - ship_tsf.translation.xy(),
+        MOUSE_AIM_DEADZONE,
+    );
+}
+
+pub fn control_ship(
+    ship: Option<
+        Single<
+            (
+                Entity,
+                &mut PlayerShip,
+                &mut Velocity,
+                &Transform,
+                &Heading,
+                Option<&mut Charging>,
+            ),
+            Without<PendingRespawn>,
+        >,
+    >,
+    input: Res<ShipInput>,
+    time: Res<Time>,
+    virtual_time: Res<Time<Virtual>>,
+    mut cmds: Commands,
+) {
+    // Nothing to steer between the ship dying and its replacement spawning
+    let Some(ship) = ship else {
+        return;
+    };
+    let (ship_ent, mut ship, mut ship_vel, ship_tsf, ship_heading, charging) = ship.into_inner();
+
+    let forward = ship_heading.forward();
+    let linear_accel = match charging {
+        Some(_) => ship.linear_accel * CHARGE_ACCEL_FACTOR,
+        None => ship.linear_accel,
+    };
+    if input.thrust {
+        ship_vel.linear += forward * linear_accel * time.delta_secs();
+    }
+
+    // Reverse thrust, for braking faster than drag does
+    if input.reverse {
+        ship_vel.linear -= forward * linear_accel * time.delta_secs();
+    }
+
+    if input.rotate_right {
+        ship_vel.angular -= time.delta_secs() * ship.angular_accel;
+    }
+
+    if input.rotate_left {
+        ship_vel.angular += time.delta_secs() * ship.angular_accel;
+    }
+
+    let shot = LaserShotConfig {
+        origin: ship_tsf.translation.xy(),
+        heading: ship_heading.0,
+        inherited_linear: ship_vel.linear,
+        inherited_angular: ship_vel.angular,
+        spread_angle: ship.spread_angle,
+        shooter: ship_ent,
+        charge: 0.0,
+    };
+
+    // The gun needs 1 / fire_rate seconds between shots, whatever else the ship is doing
+    let now = time.elapsed_secs();
+    if input.fire && now - ship.last_fired >= 1.0 / ship.fire_rate {
+        ship.last_fired = now;
+        cmds.run_system_cached_with(spawn_laser_shot, shot);
+        // Firing again mid-flash starts it over
+        cmds.entity(ship_ent).insert(GunFlash::default());
+    }
+
+    match (input.fire_held, charging) {
+        (true, Some(mut charging)) => charging.elapsed += time.delta_secs(),
+        (true, None) => {
+            cmds.entity(ship_ent).insert(Charging::default());
+        }
+        (false, Some(charging)) => {
+            cmds.entity(ship_ent).remove::<Charging>();
+
+            // Opening the pause menu lets go of every key, that drops the charge instead
+            if charging.elapsed >= CHARGE_MIN_SECS && !virtual_time.is_paused() {
+                ship.last_fired = now;
+                cmds.run_system_cached_with(
+                    spawn_laser_shot,
+                    LaserShotConfig {
+                        charge: charging.charge(),
+                        ..shot
+                    },
+                );
+                cmds.entity(ship_ent).insert(GunFlash::default());
+            }
+        }
+        (false, None) => {}
+    }
+
+    if input.secondary && ship.secondary_ammo > 0 {
+        ship.secondary_ammo -= 1;
+        cmds.run_system_cached_with(spawn_mine, ship_tsf.translation.xy());
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PlayerShip {
+    /// How many shots per second
+    pub fire_rate: f32,
+    /// Game time in seconds of the last shot
+    pub last_fired: f32,
+    /// Shared by every secondary weapon, refilled by ammo pickups
+    pub secondary_ammo: u32,
+    /// Each shot leaves the gun up to this many radians off the ship's heading
+    pub spread_angle: f32,
+    /// Added to [`CombatConfig::crit_chance`](config::CombatConfig::crit_chance)
+    pub crit_bonus: f64,
+
+    // Movement limitations
+    pub linear_accel: f32,
+    pub angular_accel: f32,
+}
+
+impl Default for PlayerShip {
+    fn default() -> Self {
+        Self {
+            fire_rate: 4.0,
+            // A new ship can fire straight away
+            last_fired: f32::NEG_INFINITY,
+            secondary_ammo: 3,
+            spread_angle: 0.0,
+            crit_bonus: 0.0,
+            linear_accel: 100.0,
+            angular_accel: 2.0 * PI,
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Asteroid;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AsteroidSize {
+    /// Rare wave rock that breaks into larges, never rolled by the random spawner
+    Mega,
+    #[default]
+    Large,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    pub const ALL: [AsteroidSize; 4] = [
+        AsteroidSize::Mega,
+        AsteroidSize::Large,
+        AsteroidSize::Medium,
+        AsteroidSize::Small,
+    ];
+
+    /// The size a destroyed asteroid of this size breaks into, if any
+    pub fn child(self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Mega => Some(AsteroidSize::Large),
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+}
+
+/// Laser hits an asteroid can take before it's destroyed
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Worn down to half or less, but still alive
+    pub fn damaged(&self) -> bool {
+        self.current > 0 && self.current <= self.max / 2
+    }
+}
+
+/// Which of [`GameAssets::meteors`] an asteroid was spawned with, so its damaged look matches
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MeteorVariant(pub usize);
+
+/// Swaps an asteroid to its damaged sprite once it's taken half its health in hits
+pub fn check_damage_state(
+    mut asteroids: Query<(&Health, &MeteorVariant, &mut Sprite), Changed<Health>>,
+    assets: Res<GameAssets>,
+) {
+    for (health, variant, mut sprite) in asteroids.iter_mut() {
+        if health.damaged() {
+            sprite.image = assets.meteors_damaged[variant.0].clone();
+        }
+    }
+}
+
+/// What a [`CollisionEvent`] means for gameplay, with the entities sorted into known roles
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CollisionKind {
+    LaserVsAsteroid { laser: Entity, asteroid: Entity },
+    ShipVsAsteroid { asteroid: Entity },
+    Other,
+}
+
+/// Classifies a collision no matter which order its entities were reported in
+pub fn classify_collision(
+    collision: &CollisionEvent,
+    lasers: &Query<(&Transform, Option<&mut ChargedShot>), With<LaserShot>>,
+    asteroids: &Query<(Entity, &Transform), With<Asteroid>>,
+    ship: Option<Entity>,
+) -> CollisionKind {
+    for (a, b) in [(collision.0, collision.1), (collision.1, collision.0)] {
+        if lasers.contains(a) && asteroids.contains(b) {
+            return CollisionKind::LaserVsAsteroid {
+                laser: a,
+                asteroid: b,
+            };
+        }
+
+        if Some(a) == ship && asteroids.contains(b) {
+            return CollisionKind::ShipVsAsteroid { asteroid: b };
+        }
+    }
+
+    CollisionKind::Other
+}
+
+/// Resolves this frame's collisions.
+///
+/// Every entity can be used up by at most one collision per frame: a laser overlapping two
+/// asteroids destroys only one of them, and two lasers overlapping one asteroid only spend one
+/// laser, leaving the other to fly on. Whichever event is read first wins.
+///
+/// Without a ship, between it dying and respawning, lasers still hit asteroids and anything
+/// involving the ship is ignored.
+pub fn handle_collisions(
+    mut collisions: MessageReader<CollisionEvent>,
+    mut lasers: Query<(&Transform, Option<&mut ChargedShot>), With<LaserShot>>,
+    asteroids: Query<(Entity, &Transform), With<Asteroid>>,
+    mut health: Query<&mut Health>,
+    ship: Option<Single<(Entity, &PlayerShip, &Transform, &mut Velocity)>>,
+    invincible: Query<(), With<Invincible>>,
+    ents: Query<(Entity, Option<&ChildOf>), With<GameCleanup>>,
+    mode: Res<GameMode>,
+    mut cmds: Commands,
+    game_stats: Res<GameStats>,
+    mut doomed: MessageWriter<DestroyAsteroid>,
+    mut hits: MessageWriter<LaserHit>,
+    // Bundled to stay under the system parameter limit
+    (mut died, mut ship_hits): (MessageWriter<PlayerDied>, MessageWriter<ShipHit>),
+    config: Res<GameConfig>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = &mut game_rng.rng;
+    let mut consumed: HashSet<Entity> = HashSet::new();
+    let mut ship = ship.map(Single::into_inner);
+    let crit_bonus = ship.as_ref().map_or(0.0, |(_, pilot, ..)| pilot.crit_bonus);
+    let crit_chance = (config.combat.crit_chance + crit_bonus).clamp(0.0, 1.0);
+    let ship_ent = ship.as_ref().map(|(ent, ..)| *ent);
+
+    for collision in collisions.read() {
+        match classify_collision(collision, &lasers, &asteroids, ship_ent) {
+            CollisionKind::LaserVsAsteroid { laser, asteroid } => {
+                if consumed.contains(&laser)
+                    || consumed.contains(&asteroid)
+                    || invincible.contains(asteroid)
+                {
+                    continue;
+                }
+                let Ok((laser_tsf, mut charged)) = lasers.get_mut(laser) else {
+                    continue;
+                };
+                // A charged shot is still inside the asteroids it went through
+                if charged
+                    .as_ref()
+                    .is_some_and(|shot| shot.hit.contains(&asteroid))
+                {
+                    continue;
+                }
+                consumed.insert(asteroid);
+
+                // Rolled per hit rather than per shot, so every target a shot reaches gets its
+                // own chance
+                let critical = rng.random_bool(crit_chance);
+                hits.write(LaserHit {
+                    position: laser_tsf.translation.xy(),
+                    critical,
+                });
+
+                let base_damage = charged.as_ref().map_or(1, |shot| shot.damage);
+                match charged.as_mut() {
+                    Some(shot) if shot.pierce > 0 => {
+                        shot.pierce -= 1;
+                        shot.hit.push(asteroid);
+                    }
+                    _ => {
+                        consumed.insert(laser);
+                        release(&mut cmds, laser);
+                    }
+                }
+
+                if let Ok(mut health) = health.get_mut(asteroid) {
+                    // A critical hit adds the same on top of a charged shot as on a normal one
+                    let damage = if critical {
+                        base_damage + config.combat.crit_damage.saturating_sub(1)
+                    } else {
+                        base_damage
+                    };
+                    health.current = health.current.saturating_sub(damage);
+                    if health.current > 0 {
+                        continue;
+                    }
+                }
+
+                doomed.write(DestroyAsteroid { asteroid, critical });
+
+                let Ok((_, roid_tsf)) = asteroids.get(asteroid) else {
+                    continue;
+                };
+                let position = roid_tsf.translation.xy();
+
+                if rng.random_bool(POWERUP_DROP_CHANCE) {
+                    let kind = PowerUpKind::random(rng);
+                    cmds.run_system_cached_with(spawn_powerup, (kind, position));
+                }
+            }
+            CollisionKind::ShipVsAsteroid { asteroid } => {
+                let Some((ship, _, ship_tsf, ship_vel)) = ship.as_mut() else {
+                    continue;
+                };
+
+                // A hidden stealth asteroid can't be seen coming, so it can't kill either
+                if invincible.contains(*ship) || invincible.contains(asteroid) {
+                    continue;
+                }
+
+                if *mode == GameMode::Sandbox {
+                    if let Ok((_, roid_tsf)) = asteroids.get(asteroid) {
+                        let away = (ship_tsf.translation - roid_tsf.translation).xy();
+                        ship_vel.linear = away.normalize_or(Vec2::Y) * SANDBOX_BOUNCE_SPEED;
+                    }
+                    ship_hits.write(ShipHit {
+                        position: ship_tsf.translation.xy(),
+                    });
+                    continue;
+                }
+
+                died.write(PlayerDied {
+                    score: game_stats.score,
+                });
+
+                for ent in cleanup_roots(&ents) {
+                    cmds.entity(ent).try_despawn();
+                }
+                // Parked lasers and effects were swept up with everything else
+                cmds.run_system_cached(clear_pools);
+                cmds.run_system_cached(pause::reset_run);
+                cmds.run_system_cached(setup_scene);
+
+                // Everything the remaining events refer to is gone
+                break;
+            }
+            CollisionKind::Other => {}
+        }
+    }
+
+    // Drop anything left unread after a restart
+    collisions.clear();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct GameCleanup;
+
+/// Marked entities whose parent isn't marked too. Despawning is recursive, so sweeping only these
+/// takes everything else down with its parent instead of despawning it twice.
+pub fn cleanup_roots<'a>(
+    ents: &'a Query<(Entity, Option<&ChildOf>), With<GameCleanup>>,
+) -> impl Iterator<Item = Entity> + 'a {
+    ents.iter()
+        .filter(|(_, parent)| parent.is_none_or(|parent| !ents.contains(parent.parent())))
+        .map(|(ent, _)| ent)
+}
+
+/// The camera following the playfield, as opposed to the minimap
+#[derive(Component)]
+pub struct MainCamera;
+
+/// The HUD line showing score (and wave in wave mode)
+#[derive(Component)]
+pub struct ScoreText;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct LaserShot;
+
+/// Decorative asteroid drifting behind the playfield
+#[derive(Component)]
+pub struct BackgroundAsteroid;
+
+/// Lasers that have left the play area can never hit anything again, so they go back to the pool
+pub fn release_offscreen_lasers(
+    lasers: Query<(Entity, &Transform), (With<LaserShot>, Without<Disabled>)>,
+    bounds: Res<PlayBounds>,
+    mut cmds: Commands,
+) {
+    let bounds = bounds.wrap();
+
+    for (laser, tsf) in lasers {
+        if tsf.translation.x.abs() > bounds.x || tsf.translation.y.abs() > bounds.y {
+            release(&mut cmds, laser);
+        }
+    }
+}
+
+/// Lasers leave the gun this far ahead of the middle of the ship
+pub const LASER_MUZZLE_OFFSET: f32 = 50.0;
+/// How fast a laser leaves the gun, before any of the shooter's motion is added
+pub const LASER_SPEED: f32 = 400.0;
+
+/// Everything needed to fire one laser
+#[derive(Clone, Copy, Debug)]
+pub struct LaserShotConfig {
+    /// Center of whatever is firing, the shot appears [`LASER_MUZZLE_OFFSET`] in front of it
+    pub origin: Vec2,
+    pub heading: f32,
+    /// Linear velocity of the shooter, the shot carries
+    /// [`CombatConfig::projectile_velocity_inheritance`](config::CombatConfig::projectile_velocity_inheritance)
+    /// of it
+    pub inherited_linear: Vec2,
+    /// Spin of the shooter, flung into the shot as velocity tangent to the muzzle
+    pub inherited_angular: f32,
+    /// Each shot leaves up to this many radians off `heading`
+    pub spread_angle: f32,
+    /// The shot passes through this entity for a moment after firing
+    pub shooter: Entity,
+    /// How far the shot was charged, from 0 for a normal laser to 1 for a full
+    /// [`ChargedShot`]
+    pub charge: f32,
+}
+
+/// Reuses a parked laser from the pool when there is one, otherwise spawns a new entity
+pub fn spawn_laser_shot(
+    In(LaserShotConfig {
+        origin,
+        heading,
+        inherited_linear,
+        inherited_angular,
+        spread_angle,
+        shooter,
+        charge,
+    }): In<LaserShotConfig>,
+    time: Res<Time>,
+    mut cmds: Commands,
+    game_assets: Res<GameAssets>,
+    palette: Res<Palette>,
+    config: Res<GameConfig>,
+    mut pool: ResMut<Pool<LaserShot>>,
+    parked: Query<(), (With<LaserShot>, With<Disabled>)>,
+    mut shots: MessageWriter<ShotFired>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let spread = if spread_angle > 0.0 {
+        game_rng.rng.random_range(-spread_angle..spread_angle)
+    } else {
+        0.0
+    };
+
+    // The muzzle sits on the nose, whichever way the spread knocks the shot
+    let muzzle = Vec2::new(-heading.sin(), heading.cos()) * LASER_MUZZLE_OFFSET;
+    let loc = origin + muzzle;
+    // A spinning ship swings its nose sideways, and the shot keeps that motion
+    let tangential = inherited_angular * muzzle.perp();
+
+    //Set pos and rot
+    let heading = Heading::new(heading + spread);
+    let tsf =
+        Transform::from_xyz(loc.x, loc.y, 0.0).with_rotation(Quat::from_rotation_z(heading.0));
+
+    shots.write(ShotFired {
+        position: loc,
+        heading: heading.0,
+    });
+
+    let velocity = Velocity {
+        linear: config.combat.projectile_velocity(
+            heading.forward() * LASER_SPEED + tangential,
+            inherited_linear,
+        ),
+        linear_drag: Vec2::ZERO,
+        angular: 0.0,
+        angular_drag: 0.0,
+    };
+
+    let mut laser_sprite = Sprite::from_image(game_assets.laser.clone());
+    let size = 15.0 * charged_scale(charge);
+    laser_sprite.custom_size = Some(Vec2::splat(size));
+    laser_sprite.color = palette.laser;
+
+    let laser = match pool.pop(&parked) {
+        Some(laser) => {
+            cmds.entity(laser).remove::<Disabled>();
+            laser
+        }
+        None => cmds.spawn((LaserShot, GameCleanup)).id(),
+    };
+
+    cmds.entity(laser).insert((
+        velocity,
+        heading,
+        tsf,
+        CircleCollider { radius: size },
+        laser_sprite,
+        AddShadow,
+        Visibility::Visible,
+        ForceAccum::default(),
+        IgnoreCollisionWith {
+            entity: shooter,
+            until: time.elapsed_secs() + LASER_SHOOTER_GRACE_SECS,
+        },
+    ));
+
+    // Pooled lasers may still carry a charge from their last shot
+    if charge > 0.0 {
+        cmds.entity(laser).insert(ChargedShot::new(charge));
+    } else {
+        cmds.entity(laser).remove::<ChargedShot>();
+    }
+}
+
+/// Everything needed to spawn one asteroid
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsteroidConfig {
+    pub location: Vec2,
+    /// Direction of travel in radians, 0 is straight up
+    pub heading: f32,
+    pub speed: f32,
+    /// Spin in radians per second
+    pub angvel: f32,
+    pub size: AsteroidSize,
+    /// Set on fragments so they pass through their siblings while they fan out
+    pub group: Option<CollisionGroup>,
+}
+
+/// Asteroids at or above this speed get the full [`Palette::fast_asteroid`] tint
+pub const FAST_ASTEROID_SPEED: f32 = 200.0;
+
+/// The speed tint is worked out here from the spawn speed and deliberately left alone after,
+/// so an asteroid pushed around by blasts or magnets keeps the color it came in with
+pub fn spawn_asteroid(
+    In(AsteroidConfig {
+        location,
+        heading,
+        speed,
+        angvel,
+        size,
+        group,
+    }): In<AsteroidConfig>,
+    assets: Res<GameAssets>,
+    config: Res<GameConfig>,
+    palette: Res<Palette>,
+    mut spawned: MessageWriter<AsteroidSpawned>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    spawned.write(AsteroidSpawned {
+        position: location,
+        size,
+        speed: speed.abs(),
+    });
+
+    let rng = &mut game_rng.rng;
+    let tier = config.asteroids.get(size);
+    let asteroid_variant = tier.sprites[rng.random_range(0..tier.sprites.len())];
+
+    let mut tsf =
+        Transform::from_xyz(location.x, location.y, 0.0).with_scale(Vec3::splat(tier.scale));
+
+    tsf.rotate_z(heading);
+
+    let euler_rot = tsf.rotation.to_euler(EulerRot::XYZ).2;
+    let velocity = Vec2::new(-euler_rot.sin(), euler_rot.cos()) * speed;
+
+    let stealth = rng.random_bool(STEALTH_CHANCE);
+    let color = if stealth {
+        palette.stealth_asteroid
+    } else {
+        palette.asteroid_tint(speed)
+    };
+
+    let mut asteroid = cmds.spawn((
+        Sprite {
+            color,
+            ..Sprite::from_image(assets.meteors[asteroid_variant].clone())
+        },
+        MeteorVariant(asteroid_variant),
+        Asteroid,
+        Dilatable,
+        AddShadow,
+        // Asteroids bumping into each other doesn't do anything yet
+        LowPriorityCollisions,
+        size,
+        Health::new(tier.health),
+        Velocity {
+            linear: velocity,
+            linear_drag: Vec2::ZERO,
+            angular: angvel,
+            angular_drag: 0.0,
+        },
+        GameCleanup,
+        CircleCollider {
+            radius: tier.scaled_radius(),
+        },
+        tsf,
+    ));
+
+    if let Some(group) = group {
+        asteroid.insert(group);
+    }
+    if stealth {
+        asteroid.insert(stealth_bundle());
+    }
+    if rng.random_bool(MAGNETIC_CHANCE) {
+        asteroid.insert(magnetic_bundle(&assets, &palette));
+    }
+}
+
+/// Fragments of slow asteroids still fly at least this fast, before their tier's multiplier
+pub const MIN_FRAGMENT_SPEED: f32 = 60.0;
+/// How long fragments of one split pass through each other, long enough to fan apart
+pub const FRAGMENT_GRACE_SECS: f32 = 0.5;
+/// A large or mega asteroid breaking up shoves everything this close to it, the ship included
+pub const LARGE_EXPLOSION_PUSH_RADIUS: f32 = 250.0;
+/// Speed added to something right at the center of a large asteroid breaking up
+pub const LARGE_EXPLOSION_PUSH_STRENGTH: f32 = 150.0;
+
+/// Resolves every [`DestroyAsteroid`] sent this frame, whatever sent it.
+///
+/// Requests are gathered into a set first, so an asteroid caught by a blast and a laser in the
+/// same frame is only handled once. Each one is scored, announced with [`AsteroidDestroyed`] and
+/// broken into its tier's fragments while its components can still be read, and only then
+/// despawned.
+pub fn destroy_asteroids(
+    mut requests: MessageReader<DestroyAsteroid>,
+    asteroids: Query<(&Transform, &AsteroidSize, &Velocity), With<Asteroid>>,
+    config: Res<GameConfig>,
+    mut game_stats: ResMut<GameStats>,
+    mut destroyed: MessageWriter<AsteroidDestroyed>,
+    mut impulses: MessageWriter<RadialImpulse>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    // An asteroid asked for more than once counts as a critical kill if any request was one
+    let mut doomed: HashMap<Entity, bool> = HashMap::new();
+    for request in requests.read() {
+        *doomed.entry(request.asteroid).or_default() |= request.critical;
+    }
+    let rng = &mut game_rng.rng;
+
+    for (asteroid, critical) in doomed {
+        // Swept up by a restart earlier in the frame
+        let Ok((tsf, size, vel)) = asteroids.get(asteroid) else {
+            continue;
+        };
+        let position = tsf.translation.xy();
+        let tier = config.asteroids.get(*size);
+
+        // Megas are worth a flat amount, a critical finishing blow doesn't add to it
+        let score = if critical && *size != AsteroidSize::Mega {
+            (tier.score as f32 * config.combat.crit_score_multiplier).round() as u32
+        } else {
+            tier.score
+        };
+        game_stats.award(score);
+        destroyed.write(AsteroidDestroyed {
+            position,
+            size: *size,
+            velocity: vel.linear,
+        });
+        if matches!(size, AsteroidSize::Mega | AsteroidSize::Large) {
+            impulses.write(RadialImpulse {
+                center: position,
+                radius: LARGE_EXPLOSION_PUSH_RADIUS,
+                strength: LARGE_EXPLOSION_PUSH_STRENGTH,
+            });
+        }
+
+        // Fragments fan out evenly so they don't sit on top of each other
+        if let Some(child) = size.child() {
+            let count = tier.split_count;
+            let speed = vel.linear.length().max(MIN_FRAGMENT_SPEED)
+                * config.asteroids.get(child).speed_multiplier;
+            let first_heading = rng.random_range(-PI..PI);
+            let group = CollisionGroup {
+                group: asteroid,
+                until: time.elapsed_secs() + FRAGMENT_GRACE_SECS,
+            };
+
+            for i in 0..count {
+                cmds.run_system_cached_with(
+                    spawn_asteroid,
+                    AsteroidConfig {
+                        location: position,
+                        heading: first_heading + TAU * i as f32 / count as f32,
+                        speed,
+                        angvel: rng.random_range(-PI..PI),
+                        size: child,
+                        group: Some(group),
+                    },
+                );
+            }
+        }
+
+        // Every asteroid is despawned exactly once, so a missing one here is a real bug
+        cmds.entity(asteroid).despawn();
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt::Display;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    asset_source::read_text,
+    palette::Palette,
+    photo::toast,
+    settings::{Language, Settings},
+};
+
+/// Every string the player reads comes from a [`Locale`], picked with [`Settings::language`]
+pub fn locale_plugin(app: &mut App) {
+    let language = app.world().resource::<Settings>().language;
+    app.insert_resource(Locale::load(language));
+
+    app.add_systems(
+        Update,
+        (
+            update_locale.run_if(resource_changed::<Settings>),
+            relabel_text.run_if(resource_changed::<Locale>),
+        )
+            .chain(),
+    );
+}
+
+/// Strings for each language are read from here through [`read_text`], named after
+/// [`Language::code`]
+pub const LOCALE_DIR: &str = "locale";
+
+/// English is built in, so there is always something to fall back to
+const ENGLISH: &str = include_str!("../assets/locale/en.ron");
+
+/// One language's strings, as written in its locale file
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LocaleFile {
+    /// Put between groups of three digits in large numbers
+    pub thousands_separator: String,
+    pub strings: HashMap<String, String>,
+}
+
+impl Default for LocaleFile {
+    fn default() -> Self {
+        Self {
+            thousands_separator: ",".into(),
+            strings: HashMap::default(),
+        }
+    }
+}
+
+impl LocaleFile {
+    pub fn english() -> Self {
+        ron::from_str(ENGLISH).unwrap_or_else(|err| {
+            error!("Could not parse the built in English strings: {err}");
+            Self::default()
+        })
+    }
+}
+
+/// The strings of the selected language, with English filling any gaps
+#[derive(Resource, Debug, Clone)]
+pub struct Locale {
+    pub language: Language,
+    file: LocaleFile,
+    fallback: LocaleFile,
+}
+
+impl Locale {
+    /// Reads the locale file for `language`. Missing keys are logged once here and shown in
+    /// English, a missing or malformed file falls back to English entirely.
+    pub fn load(language: Language) -> Self {
+        let fallback = LocaleFile::english();
+        let path = format!("{LOCALE_DIR}/{}.ron", language.code());
+
+        let file = match read_text(&path) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+                error!("Could not parse {path}, using English: {err}");
+                fallback.clone()
+            }),
+            Err(err) => {
+                warn!("Could not read {path}, using English: {err}");
+                fallback.clone()
+            }
+        };
+
+        for key in fallback.strings.keys() {
+            if !file.strings.contains_key(key) {
+                warn!("{path} has no `{key}`, it will be shown in English");
+            }
+        }
+
+        Self {
+            language,
+            file,
+            fallback,
+        }
+    }
+
+    /// The text for `key`. Unknown keys are shown as the key itself.
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.file
+            .strings
+            .get(key)
+            .or_else(|| self.fallback.strings.get(key))
+            .map(String::as_str)
+            .unwrap_or_else(|| {
+                warn_once!("No string for `{key}` in any language");
+                key
+            })
+    }
+
+    /// The text for `key` with `{0}`, `{1}`, ... replaced by `args`
+    pub fn text_with(&self, key: &str, args: &[&dyn Display]) -> String {
+        let mut text = self.text(key).to_string();
+        for (i, arg) in args.iter().enumerate() {
+            text = text.replace(&format!("{{{i}}}"), &arg.to_string());
+        }
+        text
+    }
+
+    /// `value` with its digits grouped the way the language writes them
+    pub fn number(&self, value: i64) -> String {
+        let grouped = self.format_score(value.unsigned_abs());
+        if value < 0 {
+            format!("-{grouped}")
+        } else {
+            grouped
+        }
+    }
+
+    /// A score grouped like [`Locale::number`]. Scores are unsigned and can outgrow an `i64`.
+    pub fn format_score(&self, score: u64) -> String {
+        let digits = score.to_string();
+        let mut grouped = String::new();
+
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push_str(&self.file.thousands_separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// Minutes and seconds, for the stopwatch
+    pub fn duration(&self, secs: f32) -> String {
+        let secs = secs.max(0.0) as i64;
+        self.text_with(
+            "format.duration",
+            &[&self.number(secs / 60), &format!("{:02}", secs % 60)],
+        )
+    }
+}
+
+/// Text that is just one string from the [`Locale`], redrawn whenever the language changes.
+/// Text built from game state is redrawn by whatever keeps it up to date instead.
+#[derive(Component)]
+pub struct LocalizedText(pub &'static str);
+
+pub fn update_locale(
+    settings: Res<Settings>,
+    mut locale: ResMut<Locale>,
+    palette: Res<Palette>,
+    mut cmds: Commands,
+) {
+    if locale.language == settings.language {
+        return;
+    }
+
+    *locale = Locale::load(settings.language);
+    cmds.spawn(toast(locale.text("language.changed"), &palette));
+}
+
+pub fn relabel_text(locale: Res<Locale>, mut texts: Query<(&LocalizedText, &mut Text)>) {
+    for (label, mut text) in texts.iter_mut() {
+        text.0 = locale.text(label.0).to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_language_has_every_english_string() {
+        let english = LocaleFile::english();
+        assert!(!english.strings.is_empty());
+
+        for language in Language::ALL {
+            let locale = Locale::load(language);
+            for (key, text) in &english.strings {
+                let translated = locale.file.strings.get(key);
+                assert!(translated.is_some(), "{language:?} is missing `{key}`");
+
+                // Every placeholder the English text fills in has to be somewhere in the
+                // translation too
+                for i in 0..4 {
+                    let placeholder = format!("{{{i}}}");
+                    assert_eq!(
+                        text.contains(&placeholder),
+                        translated.unwrap().contains(&placeholder),
+                        "{language:?} `{key}` doesn't use {placeholder} like English does"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn numbers_are_grouped_by_language() {
+        let english = Locale::load(Language::English);
+        assert_eq!(english.number(0), "0");
+        assert_eq!(english.number(999), "999");
+        assert_eq!(english.number(1234567), "1,234,567");
+        assert_eq!(english.number(-1500), "-1,500");
+        assert_eq!(english.format_score(u64::MAX), "18,446,744,073,709,551,615");
+        assert_eq!(english.duration(65.9), "1:05");
+
+        let german = Locale::load(Language::German);
+        assert_eq!(german.number(1234567), "1.234.567");
+    }
+}
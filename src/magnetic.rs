@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use crate::{
+    GameAssets, LaserShot,
+    palette::Palette,
+    physics::{Disabled, ForceAccum, Heading, Velocity, apply_velocity},
+};
+
+pub fn magnetic_plugin(app: &mut App) {
+    app.add_systems(Update, magnetic_repel.before(apply_velocity));
+}
+
+/// Chance that a spawned asteroid is magnetic
+pub const MAGNETIC_CHANCE: f64 = 1.0 / 25.0;
+/// Lasers closer than this to a magnetic asteroid's center are pushed away from it
+pub const MAGNETIC_FIELD_RADIUS: f32 = 100.0;
+/// Repulsion is this divided by the squared distance
+pub const MAGNETIC_STRENGTH: f32 = 4_000_000.0;
+/// Caps the push right next to the asteroid, so a laser fired straight at it from close range
+/// is only slowed down and still gets through
+pub const MAGNETIC_MAX_FORCE: f32 = 1200.0;
+
+/// Asteroid that bends passing lasers away from itself
+#[derive(Component)]
+pub struct MagneticAsteroid;
+
+/// Faint ring showing the reach of a [`MagneticAsteroid`]'s field, a child of the asteroid
+#[derive(Component)]
+pub struct MagneticField;
+
+/// Everything a magnetic asteroid spawns with on top of the usual asteroid components
+pub fn magnetic_bundle(assets: &GameAssets, palette: &Palette) -> impl Bundle {
+    (
+        MagneticAsteroid,
+        children![(
+            MagneticField,
+            Sprite {
+                color: palette.magnetic_field,
+                custom_size: Some(Vec2::splat(MAGNETIC_FIELD_RADIUS * 2.0)),
+                ..Sprite::from_image(assets.magnetic_field.clone())
+            },
+            // Just behind the asteroid it surrounds
+            Transform::from_xyz(0.0, 0.0, -0.1),
+        )],
+    )
+}
+
+/// Pushes lasers inside a field away from the asteroid, falling off with the square of the
+/// distance, and turns each laser to face the way it's now flying
+pub fn magnetic_repel(
+    magnets: Query<&Transform, With<MagneticAsteroid>>,
+    mut lasers: Query<
+        (&Transform, &mut Heading, &Velocity, &mut ForceAccum),
+        (
+            With<LaserShot>,
+            Without<MagneticAsteroid>,
+            Without<Disabled>,
+        ),
+    >,
+) {
+    for (tsf, mut heading, vel, mut force) in lasers.iter_mut() {
+        let pos = tsf.translation.xy();
+
+        for magnet in magnets.iter() {
+            let away = pos - magnet.translation.xy();
+            let distance = away.length();
+            if distance >= MAGNETIC_FIELD_RADIUS || distance <= f32::EPSILON {
+                continue;
+            }
+
+            let push = (MAGNETIC_STRENGTH / (distance * distance)).min(MAGNETIC_MAX_FORCE);
+            force.0 += away / distance * push;
+        }
+
+        if force.0 != Vec2::ZERO && vel.linear != Vec2::ZERO {
+            *heading = Heading(f32::atan2(-vel.linear.x, vel.linear.y));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::{
+    Asteroid, GameAssets, GameCleanup,
+    effects::Lifetime,
+    events::DestroyAsteroid,
+    palette::Palette,
+    physics::{Disabled, RadialImpulse},
+    pool::{Pool, Pooled},
+};
+
+pub fn mines_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (arm_mines, detonate_mines, blink_mines, expand_shockwaves).chain(),
+    );
+}
+
+pub const MINE_ARM_SECS: f32 = 1.0;
+pub const MINE_LIFETIME_SECS: f32 = 20.0;
+/// An asteroid this close to an armed mine sets it off
+pub const MINE_TRIGGER_RADIUS: f32 = 60.0;
+/// Everything this close to a detonating mine is destroyed, including other mines
+pub const MINE_BLAST_RADIUS: f32 = 180.0;
+/// Arming a mine past this count removes the oldest armed one
+pub const MAX_ARMED_MINES: usize = 3;
+pub const SHOCKWAVE_SECS: f32 = 0.4;
+/// Things outside the blast but within this are shoved away from it
+pub const MINE_PUSH_RADIUS: f32 = 350.0;
+/// Speed added to something right at the center of a blast
+pub const MINE_PUSH_STRENGTH: f32 = 300.0;
+
+#[derive(Component)]
+pub struct Mine {
+    pub arm_timer: Timer,
+}
+
+impl Mine {
+    pub fn armed(&self) -> bool {
+        self.arm_timer.is_finished()
+    }
+}
+
+/// Expanding ring left behind by a blast or a laser hit, purely visual
+#[derive(Component)]
+pub struct Shockwave {
+    pub radius: f32,
+    pub elapsed: f32,
+    /// Faded out over the ring's life
+    pub color: Color,
+}
+
+/// Everything needed to set off one [`Shockwave`]
+#[derive(Clone, Copy, Debug)]
+pub struct ShockwaveConfig {
+    /// Center of the ring, z included so it can sit over or under what set it off
+    pub position: Vec3,
+    pub radius: f32,
+    pub color: Color,
+}
+
+/// Reuses a parked ring from the pool when there is one, otherwise spawns a new entity
+pub fn spawn_shockwave(
+    In(ShockwaveConfig {
+        position,
+        radius,
+        color,
+    }): In<ShockwaveConfig>,
+    assets: Res<GameAssets>,
+    mut pool: ResMut<Pool<Shockwave>>,
+    parked: Query<(), (With<Shockwave>, With<Disabled>)>,
+    mut cmds: Commands,
+) {
+    let wave = match pool.pop(&parked) {
+        Some(wave) => {
+            cmds.entity(wave).remove::<Disabled>();
+            wave
+        }
+        None => cmds.spawn((Pooled, GameCleanup)).id(),
+    };
+
+    cmds.entity(wave).insert((
+        Shockwave {
+            radius,
+            elapsed: 0.0,
+            color,
+        },
+        Sprite {
+            custom_size: Some(Vec2::ZERO),
+            ..Sprite::from_image(assets.shockwave.clone())
+        },
+        Transform::from_translation(position),
+        Visibility::Visible,
+        Lifetime {
+            secs: SHOCKWAVE_SECS,
+        },
+    ));
+}
+
+pub fn spawn_mine(In(location): In<Vec2>, assets: Res<GameAssets>, mut cmds: Commands) {
+    let mut sprite = Sprite::from_image(assets.mine.clone());
+    sprite.custom_size = Some(Vec2::splat(30.0));
+
+    cmds.spawn((
+        Mine {
+            arm_timer: Timer::from_seconds(MINE_ARM_SECS, TimerMode::Once),
+        },
+        Lifetime {
+            secs: MINE_LIFETIME_SECS,
+        },
+        GameCleanup,
+        sprite,
+        // Sit underneath the ship and asteroids
+        Transform::from_xyz(location.x, location.y, -1.0),
+    ));
+}
+
+/// Ticks arming timers, then enforces [`MAX_ARMED_MINES`] by removing the oldest armed mines
+pub fn arm_mines(
+    mut mines: Query<(Entity, &mut Mine, &Lifetime)>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    let mut armed = vec![];
+
+    for (ent, mut mine, lifetime) in mines.iter_mut() {
+        mine.arm_timer.tick(time.delta());
+
+        if mine.armed() {
+            armed.push((ent, lifetime.secs));
+        }
+    }
+
+    if armed.len() > MAX_ARMED_MINES {
+        // Least lifetime left means it was laid first
+        armed.sort_by(|a, b| a.1.total_cmp(&b.1));
+        for (ent, _) in &armed[..armed.len() - MAX_ARMED_MINES] {
+            cmds.entity(*ent).try_despawn();
+        }
+    }
+}
+
+/// Sets off any armed mine with an asteroid inside its trigger radius.
+///
+/// Victims are gathered up front and only handed to [`destroy_asteroids`](crate::destroy_asteroids)
+/// once every blast has been resolved, so a blast that catches another mine chains into it
+/// without touching despawned entities.
+pub fn detonate_mines(
+    mines: Query<(Entity, &Mine, &Transform)>,
+    asteroids: Query<(Entity, &Transform), With<Asteroid>>,
+    palette: Res<Palette>,
+    mut doomed: MessageWriter<DestroyAsteroid>,
+    mut impulses: MessageWriter<RadialImpulse>,
+    mut cmds: Commands,
+) {
+    let asteroid_positions: Vec<(Entity, Vec2)> = asteroids
+        .iter()
+        .map(|(ent, tsf)| (ent, tsf.translation.xy()))
+        .collect();
+
+    let mut pending: Vec<(Entity, Vec2)> = mines
+        .iter()
+        .filter(|(_, mine, _)| mine.armed())
+        .map(|(ent, _, tsf)| (ent, tsf.translation.xy()))
+        .filter(|(_, pos)| {
+            asteroid_positions
+                .iter()
+                .any(|(_, roid)| roid.distance(*pos) < MINE_TRIGGER_RADIUS)
+        })
+        .collect();
+
+    let mut detonated: HashSet<Entity> = pending.iter().map(|(ent, _)| *ent).collect();
+    let mut victims: HashSet<Entity> = HashSet::new();
+    let mut blasts = vec![];
+
+    while let Some((_, center)) = pending.pop() {
+        blasts.push(center);
+
+        for (roid, pos) in asteroid_positions.iter() {
+            if pos.distance(center) < MINE_BLAST_RADIUS {
+                victims.insert(*roid);
+            }
+        }
+
+        // Armed or not, a mine caught in a blast goes off too
+        for (other, _, tsf) in mines.iter() {
+            let pos = tsf.translation.xy();
+            if pos.distance(center) < MINE_BLAST_RADIUS && detonated.insert(other) {
+                pending.push((other, pos));
+            }
+        }
+    }
+
+    for mine in detonated {
+        cmds.entity(mine).try_despawn();
+    }
+
+    // Blasts ignore health, anything caught in one is destroyed outright
+    for asteroid in victims {
+        doomed.write(DestroyAsteroid {
+            asteroid,
+            critical: false,
+        });
+    }
+
+    for center in blasts {
+        impulses.write(RadialImpulse {
+            center,
+            radius: MINE_PUSH_RADIUS,
+            strength: MINE_PUSH_STRENGTH,
+        });
+        cmds.run_system_cached_with(
+            spawn_shockwave,
+            ShockwaveConfig {
+                position: center.extend(-1.0),
+                radius: MINE_BLAST_RADIUS,
+                color: palette.blast,
+            },
+        );
+    }
+}
+
+/// Unarmed mines flash quickly, armed ones pulse slowly
+pub fn blink_mines(mut mines: Query<(&Mine, &mut Sprite)>, time: Res<Time>) {
+    for (mine, mut sprite) in mines.iter_mut() {
+        let hz = if mine.armed() { 1.0 } else { 6.0 };
+        let on = (time.elapsed_secs() * hz).fract() < 0.5;
+        sprite.color = Color::WHITE.with_alpha(if on { 1.0 } else { 0.3 });
+    }
+}
+
+pub fn expand_shockwaves(
+    mut waves: Query<(&mut Shockwave, &mut Sprite), Without<Disabled>>,
+    time: Res<Time>,
+) {
+    for (mut wave, mut sprite) in waves.iter_mut() {
+        wave.elapsed += time.delta_secs();
+        let t = (wave.elapsed / SHOCKWAVE_SECS).min(1.0);
+
+        sprite.custom_size = Some(Vec2::splat(wave.radius * 2.0 * t));
+        sprite.color = wave.color.with_alpha(1.0 - t);
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::{camera::Viewport, prelude::*, window::PrimaryWindow};
+
+use crate::{HudRoot, palette::Palette};
+
+pub fn minimap_plugin(app: &mut App) {
+    app.add_systems(Startup, spawn_minimap);
+    app.add_systems(Update, position_minimap);
+}
+
+/// Side length of the minimap in logical pixels
+pub const MINIMAP_SIZE: f32 = 200.0;
+/// Gap between the minimap and the window corner
+pub const MINIMAP_MARGIN: f32 = 12.0;
+/// How much more of the world the minimap sees than the main camera
+pub const MINIMAP_ZOOM: f32 = 5.0;
+const MINIMAP_BORDER: f32 = 2.0;
+
+/// Second camera drawing the whole world into a small viewport in the top-right corner
+#[derive(Component)]
+pub struct MinimapCamera;
+
+/// Frame drawn by the main camera's UI pass, underneath the minimap
+#[derive(Component)]
+pub struct MinimapBorder;
+
+/// Spawned once and kept across restarts, the minimap doesn't follow anything
+pub fn spawn_minimap(
+    window: Single<&Window, With<PrimaryWindow>>,
+    palette: Res<Palette>,
+    hud: Single<Entity, With<HudRoot>>,
+    mut cmds: Commands,
+) {
+    cmds.spawn((
+        MinimapCamera,
+        Camera2d,
+        Camera {
+            // Drawn after the main camera so it lands on top of it
+            order: 1,
+            viewport: Some(minimap_viewport(&window)),
+            clear_color: ClearColorConfig::Custom(palette.minimap_background),
+            ..default()
+        },
+        Transform::from_scale(Vec3::splat(MINIMAP_ZOOM)),
+    ));
+
+    cmds.spawn((
+        MinimapBorder,
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(MINIMAP_MARGIN - MINIMAP_BORDER),
+            right: px(MINIMAP_MARGIN - MINIMAP_BORDER),
+            width: px(MINIMAP_SIZE + 2.0 * MINIMAP_BORDER),
+            height: px(MINIMAP_SIZE + 2.0 * MINIMAP_BORDER),
+            ..default()
+        },
+        BackgroundColor(palette.minimap_border),
+        ChildOf(*hud),
+    ));
+}
+
+/// The top-right corner of `window`, in physical pixels
+pub fn minimap_viewport(window: &Window) -> Viewport {
+    let scale = window.scale_factor();
+    let size = (MINIMAP_SIZE * scale) as u32;
+    let margin = (MINIMAP_MARGIN * scale) as u32;
+
+    let position = UVec2::new(
+        window.physical_width().saturating_sub(size + margin),
+        margin,
+    );
+
+    Viewport {
+        physical_position: position,
+        physical_size: UVec2::splat(size).min(window.physical_size().saturating_sub(position)),
+        ..default()
+    }
+}
+
+/// Keeps the viewport pinned to the top-right corner as the window is resized
+pub fn position_minimap(
+    mut minimap: Single<&mut Camera, With<MinimapCamera>>,
+    window: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+) {
+    let Ok(window) = window.single() else {
+        return;
+    };
+
+    minimap.viewport = Some(minimap_viewport(window));
+}
@@ -0,0 +1,395 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+use serde::Serialize;
+
+use crate::{
+    Asteroid, Health, HudRoot, LaserShot, PlayerShip,
+    locale::{Locale, LocalizedText},
+    palette::Palette,
+    pause::{PauseMenu, restart_run},
+    physics::{CircleCollider, Disabled, SpeedScale, Velocity, apply_velocity},
+};
+
+/// Run modifiers picked from the pause menu before starting a run. They're applied to each
+/// asteroid, ship and laser as it appears, and change how much every point is worth.
+pub fn mutators_plugin(app: &mut App) {
+    app.init_resource::<RunModifiers>();
+    app.init_resource::<MutatorScreen>();
+
+    app.add_systems(Startup, (spawn_mutator_ui, spawn_mutator_badge));
+    app.add_systems(
+        Update,
+        (
+            (modify_new_asteroids, modify_new_ships, pull_lasers_down).before(apply_velocity),
+            click_mutator_buttons,
+            update_mutator_ui
+                .run_if(resource_changed::<MutatorScreen>.or(resource_changed::<Locale>)),
+            update_mutator_badge
+                .run_if(resource_changed::<RunModifiers>.or(resource_changed::<Locale>)),
+        )
+            .chain(),
+    );
+}
+
+/// How much faster asteroids fly with [`Mutator::DoubleSpeed`]
+pub const DOUBLE_SPEED_SCALE: f32 = 2.0;
+/// Ship sprite and collider scale with [`Mutator::TinyShip`]
+pub const TINY_SHIP_SCALE: f32 = 0.5;
+/// Downward acceleration of lasers with [`Mutator::LaserGravity`]
+pub const LASER_GRAVITY: f32 = 250.0;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Mutator {
+    DoubleSpeed,
+    /// Ship keeps drifting at whatever speed it's left at
+    NoDrag,
+    /// Every asteroid breaks on its first hit. The ship already dies on its first.
+    OneHit,
+    /// Half size ship, collider included
+    TinyShip,
+    /// Shots arc down toward the bottom of the screen
+    LaserGravity,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 5] = [
+        Mutator::DoubleSpeed,
+        Mutator::NoDrag,
+        Mutator::OneHit,
+        Mutator::TinyShip,
+        Mutator::LaserGravity,
+    ];
+
+    /// [`Locale`] key for the mutator's name
+    pub fn label(self) -> &'static str {
+        match self {
+            Mutator::DoubleSpeed => "mutator.double_speed",
+            Mutator::NoDrag => "mutator.no_drag",
+            Mutator::OneHit => "mutator.one_hit",
+            Mutator::TinyShip => "mutator.tiny_ship",
+            Mutator::LaserGravity => "mutator.laser_gravity",
+        }
+    }
+
+    /// Harder mutators are worth more points, easier ones less
+    pub fn score_multiplier(self) -> f32 {
+        match self {
+            Mutator::DoubleSpeed => 1.5,
+            Mutator::NoDrag => 1.25,
+            Mutator::OneHit => 0.75,
+            Mutator::TinyShip => 0.75,
+            Mutator::LaserGravity => 1.5,
+        }
+    }
+}
+
+/// Mutators of the current run. A restart keeps them, the daily challenge always runs without.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RunModifiers {
+    pub double_speed: bool,
+    pub no_drag: bool,
+    pub one_hit: bool,
+    pub tiny_ship: bool,
+    pub laser_gravity: bool,
+}
+
+impl RunModifiers {
+    pub fn is_on(&self, mutator: Mutator) -> bool {
+        match mutator {
+            Mutator::DoubleSpeed => self.double_speed,
+            Mutator::NoDrag => self.no_drag,
+            Mutator::OneHit => self.one_hit,
+            Mutator::TinyShip => self.tiny_ship,
+            Mutator::LaserGravity => self.laser_gravity,
+        }
+    }
+
+    fn flag_mut(&mut self, mutator: Mutator) -> &mut bool {
+        match mutator {
+            Mutator::DoubleSpeed => &mut self.double_speed,
+            Mutator::NoDrag => &mut self.no_drag,
+            Mutator::OneHit => &mut self.one_hit,
+            Mutator::TinyShip => &mut self.tiny_ship,
+            Mutator::LaserGravity => &mut self.laser_gravity,
+        }
+    }
+
+    pub fn toggle(&mut self, mutator: Mutator) {
+        let flag = self.flag_mut(mutator);
+        *flag = !*flag;
+    }
+
+    pub fn active(self) -> impl Iterator<Item = Mutator> {
+        Mutator::ALL
+            .into_iter()
+            .filter(move |mutator| self.is_on(*mutator))
+    }
+
+    /// Every active mutator's multiplier together, applied to all points scored
+    pub fn score_multiplier(self) -> f32 {
+        self.active().map(Mutator::score_multiplier).product()
+    }
+}
+
+/// Gives each new asteroid its run's speed and health
+pub fn modify_new_asteroids(
+    modifiers: Res<RunModifiers>,
+    mut asteroids: Query<(Entity, &mut Health), Added<Asteroid>>,
+    mut cmds: Commands,
+) {
+    for (asteroid, mut health) in asteroids.iter_mut() {
+        if modifiers.double_speed {
+            cmds.entity(asteroid).insert(SpeedScale(DOUBLE_SPEED_SCALE));
+        }
+        if modifiers.one_hit {
+            *health = Health::new(1);
+        }
+    }
+}
+
+/// Applies the ship mutators to each ship as it spawns, respawns included
+pub fn modify_new_ships(
+    modifiers: Res<RunModifiers>,
+    mut ships: Query<(&mut Velocity, &mut Transform, &mut CircleCollider), Added<PlayerShip>>,
+) {
+    for (mut vel, mut tsf, mut collider) in ships.iter_mut() {
+        if modifiers.no_drag {
+            vel.linear_drag = Vec2::ZERO;
+            vel.angular_drag = 0.0;
+        }
+        if modifiers.tiny_ship {
+            tsf.scale *= TINY_SHIP_SCALE;
+            collider.radius *= TINY_SHIP_SCALE;
+        }
+    }
+}
+
+pub fn pull_lasers_down(
+    modifiers: Res<RunModifiers>,
+    mut lasers: Query<&mut Velocity, (With<LaserShot>, Without<Disabled>)>,
+    time: Res<Time>,
+) {
+    if !modifiers.laser_gravity {
+        return;
+    }
+
+    for mut vel in lasers.iter_mut() {
+        vel.linear.y -= LASER_GRAVITY * time.delta_secs();
+    }
+}
+
+/// Mutator picker, opened from the pause menu. Changes only take effect once a run is started
+/// from it.
+#[derive(Resource, Default)]
+pub struct MutatorScreen {
+    pub open: bool,
+    /// What the next run will use
+    pub selected: RunModifiers,
+}
+
+#[derive(Component)]
+pub struct MutatorRoot;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MutatorButton {
+    Toggle(Mutator),
+    Start,
+    Back,
+}
+
+/// Total multiplier of the selection, under the toggles
+#[derive(Component)]
+pub struct MutatorTotal;
+
+/// Active mutators and their multiplier, under the score
+#[derive(Component)]
+pub struct MutatorBadge;
+
+pub fn spawn_mutator_ui(locale: Res<Locale>, mut cmds: Commands) {
+    let button_node = Node {
+        padding: UiRect::axes(px(12), px(4)),
+        ..default()
+    };
+
+    cmds.spawn((
+        MutatorRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(20),
+            left: percent(30),
+            width: percent(40),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(4),
+            padding: UiRect::all(px(16)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.9)),
+        // Keeps clicks off the pause menu underneath
+        FocusPolicy::Block,
+        GlobalZIndex(9),
+    ))
+    .with_children(|root| {
+        root.spawn((
+            LocalizedText("mutators.title"),
+            Text::new(locale.text("mutators.title")),
+        ));
+
+        for mutator in Mutator::ALL {
+            root.spawn((
+                MutatorButton::Toggle(mutator),
+                Button,
+                Text::default(),
+                button_node.clone(),
+                BackgroundColor(Color::NONE),
+            ));
+        }
+
+        root.spawn((MutatorTotal, Text::default()));
+        for (button, label) in [
+            (MutatorButton::Start, "mutators.start"),
+            (MutatorButton::Back, "mutators.back"),
+        ] {
+            root.spawn((
+                button,
+                Button,
+                LocalizedText(label),
+                Text::new(locale.text(label)),
+                button_node.clone(),
+                BackgroundColor(Color::NONE),
+            ));
+        }
+    });
+}
+
+pub fn spawn_mutator_badge(
+    palette: Res<Palette>,
+    hud: Single<Entity, With<HudRoot>>,
+    mut cmds: Commands,
+) {
+    cmds.spawn((
+        MutatorBadge,
+        Text::default(),
+        TextColor(palette.warning),
+        palette.hud_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(40),
+            left: px(12),
+            ..default()
+        },
+        ChildOf(*hud),
+    ));
+}
+
+pub fn click_mutator_buttons(
+    buttons: Query<(&Interaction, &MutatorButton), Changed<Interaction>>,
+    mut screen: ResMut<MutatorScreen>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut menu: ResMut<PauseMenu>,
+    mut cmds: Commands,
+) {
+    for (interaction, button) in buttons {
+        if *interaction != Interaction::Pressed || !screen.open {
+            continue;
+        }
+
+        match button {
+            MutatorButton::Toggle(mutator) => screen.selected.toggle(*mutator),
+            MutatorButton::Start => {
+                screen.open = false;
+                menu.open = false;
+                *modifiers = screen.selected;
+                cmds.run_system_cached(restart_run);
+            }
+            MutatorButton::Back => screen.open = false,
+        }
+    }
+}
+
+/// Formats a multiplier the way the mutator screens show it, like `x1.5`
+fn multiplier_text(locale: &Locale, multiplier: f32) -> String {
+    locale.text_with("mutators.multiplier", &[&format!("{multiplier:.2}")])
+}
+
+pub fn update_mutator_ui(
+    screen: Res<MutatorScreen>,
+    locale: Res<Locale>,
+    mut root: Single<&mut Node, With<MutatorRoot>>,
+    mut buttons: Query<(&MutatorButton, &mut Text), Without<MutatorTotal>>,
+    mut total: Single<&mut Text, With<MutatorTotal>>,
+) {
+    root.display = if screen.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    total.0 = locale.text_with(
+        "mutators.total",
+        &[&multiplier_text(
+            &locale,
+            screen.selected.score_multiplier(),
+        )],
+    );
+
+    for (button, mut text) in buttons.iter_mut() {
+        let MutatorButton::Toggle(mutator) = *button else {
+            continue;
+        };
+
+        let state = if screen.selected.is_on(mutator) {
+            locale.text("settings.on")
+        } else {
+            locale.text("settings.off")
+        };
+        text.0 = locale.text_with(
+            "mutators.row",
+            &[
+                &locale.text(mutator.label()),
+                &state,
+                &multiplier_text(&locale, mutator.score_multiplier()),
+            ],
+        );
+    }
+}
+
+pub fn update_mutator_badge(
+    modifiers: Res<RunModifiers>,
+    locale: Res<Locale>,
+    mut badge: Single<&mut Text, With<MutatorBadge>>,
+) {
+    let names: Vec<&str> = modifiers
+        .active()
+        .map(|mutator| locale.text(mutator.label()))
+        .collect();
+    badge.0 = if names.is_empty() {
+        String::new()
+    } else {
+        locale.text_with(
+            "mutators.badge",
+            &[
+                &names.join(", "),
+                &multiplier_text(&locale, modifiers.score_multiplier()),
+            ],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipliers_stack() {
+        let mut modifiers = RunModifiers::default();
+        assert_eq!(modifiers.score_multiplier(), 1.0);
+
+        modifiers.toggle(Mutator::DoubleSpeed);
+        modifiers.toggle(Mutator::TinyShip);
+        assert!((modifiers.score_multiplier() - 1.125).abs() < 1e-6);
+        assert_eq!(
+            modifiers.active().collect::<Vec<_>>(),
+            [Mutator::DoubleSpeed, Mutator::TinyShip]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use bevy::prelude::*;
+
+use crate::{
+    Asteroid, BackgroundAsteroid, FAST_ASTEROID_SPEED, LaserShot, ScoreText,
+    belt::BeltBanner,
+    bloom::glow,
+    display::FpsText,
+    magnetic::MagneticField,
+    minimap::{MinimapBorder, MinimapCamera},
+    physics::Velocity,
+    powerups::{Speedometer, SpeedometerFill, TimeDilationEffect},
+    settings::{ColorPalette, Settings},
+    stealth::StealthAsteroid,
+};
+
+pub fn palette_plugin(app: &mut App) {
+    app.init_resource::<Palette>();
+
+    app.add_systems(
+        Update,
+        (
+            update_palette.run_if(resource_changed::<Settings>),
+            (recolor_sprites, recolor_hud).run_if(resource_changed::<Palette>),
+        )
+            .chain(),
+    );
+}
+
+/// Every color the game tints things with. Systems read colors from here instead of using
+/// literals, so switching palettes recolors everything in one place.
+#[derive(Resource, Clone, Debug)]
+pub struct Palette {
+    /// Multiplied over the laser sprite
+    pub laser: Color,
+    /// Warm tint the ship flashes to for a moment after firing
+    pub gun_flash: Color,
+    /// Belt banner and edge arrows
+    pub warning: Color,
+    /// Rings and arrows marking the last asteroids of a wave
+    pub highlight: Color,
+    pub velocity_indicator: Color,
+    pub speedometer: Color,
+    /// Tint the fastest asteroids are pushed toward
+    pub fast_asteroid: Color,
+    /// Replaces the speed tint on stealth asteroids
+    pub stealth_asteroid: Color,
+    /// Ring around magnetic asteroids
+    pub magnetic_field: Color,
+    /// Lines between neighbors in a swarm
+    pub swarm_link: Color,
+    /// Flash left by a critical laser hit
+    pub critical: Color,
+    /// Shockwave left by a mine going off
+    pub blast: Color,
+    /// Edges of the screen when the ship survives a hit, faded out from this alpha
+    pub damage_flash: Color,
+    pub minimap_border: Color,
+    pub minimap_background: Color,
+    pub hud_text: Color,
+    /// Drawn behind HUD text, transparent unless high contrast is on
+    pub hud_outline: Color,
+    pub dilation_overlay: Color,
+    pub background: Color,
+    pub background_asteroid: Color,
+    /// Lines of the play area grid
+    pub grid: Color,
+}
+
+impl Palette {
+    pub fn new(palette: ColorPalette, high_contrast: bool) -> Self {
+        let mut colors = match palette {
+            ColorPalette::Standard => Self {
+                laser: Color::WHITE,
+                gun_flash: Color::srgb(1.0, 0.55, 0.35),
+                warning: Color::srgb(1.0, 0.6, 0.1),
+                highlight: Color::srgb(0.4, 0.8, 1.0),
+                velocity_indicator: Color::srgb(0.3, 1.0, 0.5),
+                speedometer: Color::srgb(0.3, 1.0, 0.5),
+                fast_asteroid: Color::srgb(1.0, 0.3, 0.3),
+                stealth_asteroid: Color::srgb(0.75, 0.85, 1.0),
+                magnetic_field: Color::srgba(0.4, 0.8, 1.0, 0.3),
+                swarm_link: Color::srgba(0.8, 0.4, 0.0, 0.3),
+                critical: Color::srgb(1.0, 0.55, 0.1),
+                blast: Color::WHITE,
+                damage_flash: Color::srgba(0.9, 0.05, 0.05, 0.7),
+                minimap_border: Color::srgb(0.4, 0.8, 1.0),
+                minimap_background: Color::srgb(0.02, 0.02, 0.06),
+                hud_text: Color::WHITE,
+                hud_outline: Color::NONE,
+                dilation_overlay: Color::srgba(0.5, 0.5, 0.5, 0.2),
+                background: ClearColor::default().0,
+                background_asteroid: Color::WHITE.with_alpha(0.25),
+                grid: Color::srgba(0.1, 0.3, 0.1, 0.3),
+            },
+            // Okabe-Ito colors, which stay apart for deuteranopia and protanopia. Red and green
+            // are never used to tell things apart.
+            ColorPalette::ColorBlind => Self {
+                laser: Color::srgb(1.0, 0.85, 0.4),
+                gun_flash: Color::srgb(1.0, 0.8, 0.4),
+                warning: Color::srgb(0.9, 0.6, 0.0),
+                highlight: Color::srgb(0.35, 0.7, 0.9),
+                velocity_indicator: Color::srgb(0.95, 0.9, 0.25),
+                speedometer: Color::srgb(0.95, 0.9, 0.25),
+                fast_asteroid: Color::srgb(0.0, 0.45, 0.7),
+                stealth_asteroid: Color::srgb(0.8, 0.9, 1.0),
+                magnetic_field: Color::srgba(0.35, 0.7, 0.9, 0.3),
+                swarm_link: Color::srgba(0.9, 0.6, 0.0, 0.3),
+                critical: Color::srgb(0.9, 0.6, 0.0),
+                blast: Color::WHITE,
+                damage_flash: Color::srgba(0.85, 0.37, 0.0, 0.7),
+                minimap_border: Color::srgb(0.35, 0.7, 0.9),
+                minimap_background: Color::srgb(0.02, 0.02, 0.06),
+                hud_text: Color::WHITE,
+                hud_outline: Color::NONE,
+                dilation_overlay: Color::srgba(0.5, 0.5, 0.5, 0.2),
+                background: ClearColor::default().0,
+                background_asteroid: Color::WHITE.with_alpha(0.25),
+                grid: Color::srgba(0.1, 0.25, 0.35, 0.3),
+            },
+        };
+
+        if high_contrast {
+            colors.hud_outline = Color::BLACK;
+            colors.background = Color::BLACK;
+            colors.minimap_background = Color::BLACK;
+            colors.background_asteroid = Color::WHITE.with_alpha(0.1);
+        }
+
+        colors
+    }
+
+    pub fn from_settings(settings: &Settings) -> Self {
+        let mut colors = Self::new(settings.palette, settings.high_contrast);
+
+        // Only the effects glow, ships and asteroids keep their plain tints
+        if settings.bloom.0 > 0 {
+            colors.laser = glow(colors.laser);
+            colors.gun_flash = glow(colors.gun_flash);
+            colors.critical = glow(colors.critical);
+            colors.blast = glow(colors.blast);
+        }
+
+        colors
+    }
+
+    /// Tint for an asteroid moving at `speed`. Ordinary asteroids are tinted by their spawn speed
+    /// and keep it, see [`spawn_asteroid`](crate::spawn_asteroid).
+    pub fn asteroid_tint(&self, speed: f32) -> Color {
+        Color::WHITE.mix(
+            &self.fast_asteroid,
+            (speed.abs() / FAST_ASTEROID_SPEED).min(1.0),
+        )
+    }
+
+    pub fn hud_shadow(&self) -> TextShadow {
+        TextShadow {
+            offset: Vec2::splat(2.0),
+            color: self.hud_outline,
+        }
+    }
+}
+
+impl FromWorld for Palette {
+    fn from_world(world: &mut World) -> Self {
+        Self::from_settings(world.resource::<Settings>())
+    }
+}
+
+pub fn update_palette(settings: Res<Settings>, mut palette: ResMut<Palette>) {
+    *palette = Palette::from_settings(&settings);
+}
+
+/// Sprites that were tinted when they spawned
+pub fn recolor_sprites(
+    palette: Res<Palette>,
+    mut clear_color: ResMut<ClearColor>,
+    mut sprites: ParamSet<(
+        Query<&mut Sprite, With<LaserShot>>,
+        Query<(&mut Sprite, &Velocity, Has<StealthAsteroid>), With<Asteroid>>,
+        Query<&mut Sprite, With<BackgroundAsteroid>>,
+        Query<&mut Sprite, With<MagneticField>>,
+    )>,
+) {
+    clear_color.0 = palette.background;
+
+    for mut sprite in sprites.p0().iter_mut() {
+        sprite.color = palette.laser;
+    }
+
+    for (mut sprite, vel, stealth) in sprites.p1().iter_mut() {
+        // Invincible asteroids flicker through alpha, leave that alone
+        let alpha = sprite.color.alpha();
+        let color = if stealth {
+            palette.stealth_asteroid
+        } else {
+            // The one time the tint catches up with how fast the asteroid is going now
+            palette.asteroid_tint(vel.linear.length())
+        };
+        sprite.color = color.with_alpha(alpha);
+    }
+
+    for mut sprite in sprites.p2().iter_mut() {
+        sprite.color = palette.background_asteroid;
+    }
+
+    for mut sprite in sprites.p3().iter_mut() {
+        sprite.color = palette.magnetic_field;
+    }
+}
+
+pub fn recolor_hud(
+    palette: Res<Palette>,
+    mut texts: ParamSet<(
+        Query<(&mut TextColor, &mut TextShadow), With<ScoreText>>,
+        Query<(&mut TextColor, &mut TextShadow), With<BeltBanner>>,
+        Query<(&mut TextColor, &mut TextShadow), With<FpsText>>,
+    )>,
+    mut backgrounds: ParamSet<(
+        Query<&mut BackgroundColor, With<Speedometer>>,
+        Query<&mut BackgroundColor, With<SpeedometerFill>>,
+        Query<&mut BackgroundColor, With<MinimapBorder>>,
+        Query<&mut BackgroundColor, With<TimeDilationEffect>>,
+    )>,
+    mut minimap: Query<&mut Camera, With<MinimapCamera>>,
+) {
+    for (mut color, mut shadow) in texts.p0().iter_mut() {
+        color.0 = palette.hud_text;
+        *shadow = palette.hud_shadow();
+    }
+    for (mut color, mut shadow) in texts.p1().iter_mut() {
+        color.0 = palette.warning;
+        *shadow = palette.hud_shadow();
+    }
+    for (mut color, mut shadow) in texts.p2().iter_mut() {
+        color.0 = palette.hud_text;
+        *shadow = palette.hud_shadow();
+    }
+
+    for mut bg in backgrounds.p0().iter_mut() {
+        bg.0 = palette.speedometer.with_alpha(0.2);
+    }
+    for mut bg in backgrounds.p1().iter_mut() {
+        bg.0 = palette.speedometer;
+    }
+    for mut bg in backgrounds.p2().iter_mut() {
+        bg.0 = palette.minimap_border;
+    }
+    for mut bg in backgrounds.p3().iter_mut() {
+        bg.0 = palette.dilation_overlay;
+    }
+
+    for mut camera in minimap.iter_mut() {
+        camera.clear_color = ClearColorConfig::Custom(palette.minimap_background);
+    }
+}
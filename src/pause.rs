@@ -0,0 +1,294 @@
+use bevy::{input::InputSystems, prelude::*, window::WindowFocused};
+
+use crate::{
+    GameCleanup, GameStats,
+    belt::AsteroidBelt,
+    cleanup_roots,
+    comet::CometTimer,
+    config::GameConfig,
+    controls::{ControlsScreen, read_controls_input},
+    credits::Credits,
+    daily::{DailyChallenge, DailyStatus, daily_available, start_daily},
+    frenzy::Frenzy,
+    high_scores::HighScoreScreen,
+    locale::{Locale, LocalizedText},
+    mutators::{MutatorScreen, RunModifiers},
+    photo::{PhotoMode, toggle_photo_mode},
+    pool::clear_pools,
+    settings::{Settings, SettingsScreen},
+    setup_scene,
+    shop::Shop,
+    waves::WaveSpawner,
+};
+
+/// Pause menu, opened and closed with Escape
+pub fn pause_plugin(app: &mut App) {
+    app.init_resource::<PauseMenu>();
+
+    app.add_systems(Startup, spawn_pause_menu);
+    app.add_systems(
+        PreUpdate,
+        (
+            toggle_pause_menu,
+            suppress_gameplay_input.run_if(|menu: Res<PauseMenu>| menu.open),
+        )
+            .chain()
+            .after(InputSystems)
+            // Escape closes the controls screen first, and photo mode can't start while paused
+            .before(read_controls_input)
+            .before(toggle_photo_mode),
+    );
+    app.add_systems(
+        Update,
+        (
+            pause_on_focus_loss,
+            pause_menu_interaction,
+            highlight_pause_buttons,
+            update_pause_menu.run_if(resource_changed::<PauseMenu>),
+        )
+            .chain(),
+    );
+}
+
+#[derive(Resource, Default)]
+pub struct PauseMenu {
+    pub open: bool,
+}
+
+#[derive(Component)]
+pub struct PauseRoot;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseButton {
+    Resume,
+    Restart,
+    Daily,
+    Mutators,
+    HighScores,
+    Settings,
+    Quit,
+}
+
+impl PauseButton {
+    pub const ALL: [PauseButton; 7] = [
+        PauseButton::Resume,
+        PauseButton::Restart,
+        PauseButton::Daily,
+        PauseButton::Mutators,
+        PauseButton::HighScores,
+        PauseButton::Settings,
+        PauseButton::Quit,
+    ];
+
+    /// [`Locale`] key for the button's text
+    pub fn label(self) -> &'static str {
+        match self {
+            PauseButton::Resume => "pause.resume",
+            PauseButton::Restart => "pause.restart",
+            PauseButton::Daily => "pause.daily",
+            PauseButton::Mutators => "pause.mutators",
+            PauseButton::HighScores => "pause.high_scores",
+            PauseButton::Settings => "pause.settings",
+            PauseButton::Quit => "pause.quit",
+        }
+    }
+}
+
+const BUTTON_COLOR: Color = Color::NONE;
+const HOVERED_BUTTON_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.2);
+
+pub fn spawn_pause_menu(locale: Res<Locale>, mut cmds: Commands) {
+    cmds.spawn((
+        PauseRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(30),
+            left: percent(40),
+            width: percent(20),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(8),
+            padding: UiRect::all(px(16)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.85)),
+        // Underneath the settings and controls screens, which open on top of it
+        GlobalZIndex(8),
+    ))
+    .with_children(|root| {
+        root.spawn((
+            LocalizedText("pause.title"),
+            Text::new(locale.text("pause.title")),
+        ));
+
+        for button in PauseButton::ALL {
+            root.spawn((
+                button,
+                Button,
+                LocalizedText(button.label()),
+                Text::new(locale.text(button.label())),
+                Node {
+                    padding: UiRect::axes(px(16), px(6)),
+                    ..default()
+                },
+                BackgroundColor(BUTTON_COLOR),
+            ));
+        }
+        root.spawn((DailyStatus, Text::default(), TextFont::from_font_size(14.0)));
+    });
+}
+
+/// Escape opens and closes the menu, unless it's closing the controls screen. With the settings,
+/// mutator or high score screen up it goes back to the menu instead.
+pub fn toggle_pause_menu(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    controls: Res<ControlsScreen>,
+    photo: Res<PhotoMode>,
+    mut settings: ResMut<SettingsScreen>,
+    mut mutators: ResMut<MutatorScreen>,
+    mut high_scores: ResMut<HighScoreScreen>,
+    mut menu: ResMut<PauseMenu>,
+) {
+    if !btn_input.just_pressed(KeyCode::Escape) || controls.open || photo.active {
+        return;
+    }
+
+    if settings.open {
+        settings.open = false;
+    } else if mutators.open {
+        mutators.open = false;
+    } else if high_scores.open {
+        high_scores.open = false;
+    } else {
+        menu.open = !menu.open;
+    }
+}
+
+/// Opens the menu when the window loses focus, so alt-tabbing away doesn't leave the ship to
+/// die. Getting focus back leaves it open, the game only carries on once it's closed. Photo mode
+/// already freezes the game and is left alone.
+pub fn pause_on_focus_loss(
+    mut focus: MessageReader<WindowFocused>,
+    settings: Res<Settings>,
+    photo: Res<PhotoMode>,
+    mut menu: ResMut<PauseMenu>,
+) {
+    let lost_focus = focus.read().any(|event| !event.focused);
+    if lost_focus && settings.pause_on_focus_loss && !photo.active && !menu.open {
+        menu.open = true;
+    }
+}
+
+/// Releases every key before gameplay systems see them, so nothing moves behind the menu
+pub fn suppress_gameplay_input(mut btn_input: ResMut<ButtonInput<KeyCode>>) {
+    btn_input.reset_all();
+}
+
+pub fn pause_menu_interaction(
+    buttons: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut menu: ResMut<PauseMenu>,
+    mut settings: ResMut<SettingsScreen>,
+    mut mutators: ResMut<MutatorScreen>,
+    mut high_scores: ResMut<HighScoreScreen>,
+    modifiers: Res<RunModifiers>,
+    mut exit: MessageWriter<AppExit>,
+    mut cmds: Commands,
+) {
+    for (interaction, button) in buttons {
+        if *interaction != Interaction::Pressed || !menu.open {
+            continue;
+        }
+
+        match button {
+            PauseButton::Resume => menu.open = false,
+            PauseButton::Restart => {
+                menu.open = false;
+                cmds.run_system_cached(restart_run);
+            }
+            // A used up attempt leaves the menu open, its status line says why
+            PauseButton::Daily if daily_available() => {
+                menu.open = false;
+                cmds.run_system_cached(start_daily);
+            }
+            PauseButton::Daily => {}
+            PauseButton::Mutators => {
+                mutators.selected = *modifiers;
+                mutators.open = true;
+            }
+            PauseButton::HighScores => high_scores.open = true,
+            PauseButton::Settings => settings.open = true,
+            PauseButton::Quit => {
+                exit.write(AppExit::Success);
+            }
+        }
+    }
+}
+
+pub fn highlight_pause_buttons(
+    mut buttons: Query<
+        (&Interaction, &mut BackgroundColor),
+        (With<PauseButton>, Changed<Interaction>),
+    >,
+) {
+    for (interaction, mut bg) in buttons.iter_mut() {
+        bg.0 = match interaction {
+            Interaction::Hovered | Interaction::Pressed => HOVERED_BUTTON_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        };
+    }
+}
+
+/// Shows or hides the menu and pauses the game while it's up
+pub fn update_pause_menu(
+    menu: Res<PauseMenu>,
+    mut time: ResMut<Time<Virtual>>,
+    mut root: Single<&mut Node, With<PauseRoot>>,
+) {
+    if menu.open {
+        time.pause();
+        root.display = Display::Flex;
+    } else {
+        time.unpause();
+        root.display = Display::None;
+    }
+}
+
+/// Throws the current run away and starts a fresh one, score, credits and waves included. A daily
+/// run in progress ends without its score.
+pub fn restart_run(ents: Query<(Entity, Option<&ChildOf>), With<GameCleanup>>, mut cmds: Commands) {
+    for ent in cleanup_roots(&ents) {
+        cmds.entity(ent).try_despawn();
+    }
+    // Parked lasers and effects were swept up with everything else
+    cmds.run_system_cached(clear_pools);
+    cmds.run_system_cached(reset_run);
+    cmds.remove_resource::<DailyChallenge>();
+    cmds.run_system_cached(setup_scene);
+}
+
+/// Puts everything a run keeps track of back to how a new run starts, for both restarting and
+/// dying. Only the mutators picked for it carry over.
+pub fn reset_run(
+    mut game_stats: ResMut<GameStats>,
+    mut credits: ResMut<Credits>,
+    mut shop: ResMut<Shop>,
+    mut spawner: ResMut<WaveSpawner>,
+    mut frenzy: ResMut<Frenzy>,
+    mut belt: ResMut<AsteroidBelt>,
+    mut comet_timer: ResMut<CometTimer>,
+    config: Res<GameConfig>,
+    modifiers: Res<RunModifiers>,
+) {
+    *game_stats = GameStats {
+        spawn_rate: config.spawning.spawn_rate,
+        mutator_multiplier: modifiers.score_multiplier(),
+        ..default()
+    };
+    *credits = Credits::default();
+    *shop = Shop::default();
+    *spawner = WaveSpawner::default();
+    *frenzy = Frenzy::default();
+    *belt = AsteroidBelt::new(&config.belt);
+    *comet_timer = CometTimer::default();
+}
@@ -0,0 +1,255 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    input::{
+        InputSystems,
+        mouse::{AccumulatedMouseScroll, MouseScrollUnit},
+    },
+    prelude::*,
+    render::view::screenshot::{Screenshot, ScreenshotCaptured},
+};
+
+use crate::{HudRoot, MainCamera, locale::Locale, minimap::MinimapCamera, palette::Palette};
+
+/// F12 saves a screenshot, F9 or P toggles photo mode
+pub fn photo_plugin(app: &mut App) {
+    app.init_resource::<PhotoMode>();
+
+    app.add_systems(
+        PreUpdate,
+        (
+            take_screenshot,
+            toggle_photo_mode,
+            (move_photo_camera, suppress_gameplay_input).run_if(photo_mode_active),
+        )
+            .chain()
+            .after(InputSystems),
+    );
+    app.add_systems(Update, expire_toasts);
+}
+
+/// Screenshots are saved here, relative to the working directory
+pub const SCREENSHOT_DIR: &str = "screenshots";
+pub const TOAST_SECS: f32 = 2.5;
+/// World units per second at 1x zoom
+const PHOTO_PAN_SPEED: f32 = 600.0;
+/// How fast holding +/- zooms, doubling roughly once a second
+const PHOTO_ZOOM_RATE: f32 = 0.7;
+const PHOTO_MIN_ZOOM: f32 = 0.25;
+const PHOTO_MAX_ZOOM: f32 = 4.0;
+/// Zoom change per notch of the mouse wheel
+const PHOTO_SCROLL_ZOOM: f32 = 0.1;
+/// Touchpads scroll in pixels, this many make up one notch
+const PIXELS_PER_SCROLL_LINE: f32 = 20.0;
+
+/// Paused game with the HUD hidden and a free camera
+#[derive(Resource, Default)]
+pub struct PhotoMode {
+    pub active: bool,
+    /// Where the main camera was and its zoom, put back when photo mode ends
+    pub saved_camera: Option<(Transform, f32)>,
+}
+
+pub fn photo_mode_active(photo: Res<PhotoMode>) -> bool {
+    photo.active
+}
+
+/// Short message at the top of the screen, outside the HUD so it still shows in photo mode
+#[derive(Component)]
+pub struct Toast {
+    pub timer: Timer,
+}
+
+pub fn toast(message: impl Into<String>, palette: &Palette) -> impl Bundle {
+    (
+        Toast {
+            timer: Timer::from_seconds(TOAST_SECS, TimerMode::Once),
+        },
+        Text::new(message),
+        TextColor(palette.hud_text),
+        palette.hud_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(10),
+            width: percent(100),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+        GlobalZIndex(8),
+    )
+}
+
+/// Toasts run on real time, they have to go away while the game is paused too
+pub fn expire_toasts(
+    mut toasts: Query<(Entity, &mut Toast)>,
+    time: Res<Time<Real>>,
+    mut cmds: Commands,
+) {
+    for (ent, mut toast) in toasts.iter_mut() {
+        toast.timer.tick(time.delta());
+        if toast.timer.is_finished() {
+            cmds.entity(ent).despawn();
+        }
+    }
+}
+
+/// F12 anywhere, or S in photo mode
+pub fn take_screenshot(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    photo: Res<PhotoMode>,
+    palette: Res<Palette>,
+    locale: Res<Locale>,
+    mut cmds: Commands,
+) {
+    let photo_shortcut = photo.active && btn_input.just_pressed(KeyCode::KeyS);
+    if !btn_input.just_pressed(KeyCode::F12) && !photo_shortcut {
+        return;
+    }
+
+    if let Err(err) = std::fs::create_dir_all(SCREENSHOT_DIR) {
+        error!("Could not create {SCREENSHOT_DIR}: {err}");
+        cmds.spawn(toast(locale.text_with("photo.failed", &[&err]), &palette));
+        return;
+    }
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = Path::new(SCREENSHOT_DIR).join(format!("screenshot-{millis}.png"));
+
+    cmds.spawn(Screenshot::primary_window())
+        .observe(save_screenshot(path));
+}
+
+/// Writes the captured frame to `path` and reports how it went with a toast
+fn save_screenshot(
+    path: PathBuf,
+) -> impl FnMut(On<ScreenshotCaptured>, Res<Palette>, Res<Locale>, Commands) {
+    move |captured, palette, locale, mut cmds| {
+        let saved = captured
+            .image
+            .clone()
+            .try_into_dynamic()
+            .map_err(|err| err.to_string())
+            .and_then(|image| image.to_rgb8().save(&path).map_err(|err| err.to_string()));
+
+        let message = match saved {
+            Ok(()) => {
+                info!("Screenshot saved to {}", path.display());
+                locale.text_with("photo.saved", &[&path.display()])
+            }
+            Err(err) => {
+                error!("Could not save screenshot to {}: {err}", path.display());
+                locale.text_with("photo.failed", &[&err])
+            }
+        };
+        cmds.spawn(toast(message, &palette));
+    }
+}
+
+/// Pauses the game, hides the HUD, gizmos and minimap, and frees the camera. Leaving puts
+/// everything back, including the camera.
+pub fn toggle_photo_mode(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    mut photo: ResMut<PhotoMode>,
+    mut time: ResMut<Time<Virtual>>,
+    mut hud: Single<&mut Visibility, With<HudRoot>>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+    mut minimap: Query<&mut Camera, With<MinimapCamera>>,
+    camera: Single<(&mut Transform, &mut Projection), With<MainCamera>>,
+) {
+    if !btn_input.any_just_pressed([KeyCode::F9, KeyCode::KeyP]) {
+        return;
+    }
+
+    photo.active = !photo.active;
+    let active = photo.active;
+    let (mut camera_tsf, mut projection) = camera.into_inner();
+
+    if active {
+        time.pause();
+        if let Projection::Orthographic(ortho) = &*projection {
+            photo.saved_camera = Some((*camera_tsf, ortho.scale));
+        }
+    } else {
+        time.unpause();
+        if let Some((tsf, scale)) = photo.saved_camera.take() {
+            *camera_tsf = tsf;
+            if let Projection::Orthographic(ortho) = &mut *projection {
+                ortho.scale = scale;
+            }
+        }
+    }
+
+    **hud = if active {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    gizmos.config_mut::<DefaultGizmoConfigGroup>().0.enabled = !active;
+    for mut camera in minimap.iter_mut() {
+        camera.is_active = !active;
+    }
+}
+
+/// Arrow keys or WASD pan, +/- or the mouse wheel zoom. Runs on real time since the game is
+/// paused.
+pub fn move_photo_camera(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    scroll: Res<AccumulatedMouseScroll>,
+    time: Res<Time<Real>>,
+    camera: Single<(&mut Transform, &mut Projection), With<MainCamera>>,
+) {
+    let (mut tsf, mut projection) = camera.into_inner();
+    let Projection::Orthographic(ortho) = &mut *projection else {
+        return;
+    };
+    let dt = time.delta_secs();
+
+    let mut pan = Vec2::ZERO;
+    if btn_input.any_pressed([KeyCode::ArrowLeft, KeyCode::KeyA]) {
+        pan.x -= 1.0;
+    }
+    if btn_input.any_pressed([KeyCode::ArrowRight, KeyCode::KeyD]) {
+        pan.x += 1.0;
+    }
+    if btn_input.any_pressed([KeyCode::ArrowDown, KeyCode::KeyS]) {
+        pan.y -= 1.0;
+    }
+    if btn_input.any_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) {
+        pan.y += 1.0;
+    }
+    tsf.translation += (pan * PHOTO_PAN_SPEED * ortho.scale * dt).extend(0.0);
+
+    let zoom_in = btn_input.any_pressed([KeyCode::Equal, KeyCode::NumpadAdd]);
+    let zoom_out = btn_input.any_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]);
+    if zoom_in != zoom_out {
+        let factor = (PHOTO_ZOOM_RATE * dt).exp();
+        let scale = if zoom_in {
+            ortho.scale / factor
+        } else {
+            ortho.scale * factor
+        };
+        ortho.scale = scale.clamp(PHOTO_MIN_ZOOM, PHOTO_MAX_ZOOM);
+    }
+
+    let notches = match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta.y,
+        MouseScrollUnit::Pixel => scroll.delta.y / PIXELS_PER_SCROLL_LINE,
+    };
+    if notches != 0.0 {
+        // Scrolling up zooms in
+        let scale = ortho.scale * (-notches * PHOTO_SCROLL_ZOOM).exp();
+        ortho.scale = scale.clamp(PHOTO_MIN_ZOOM, PHOTO_MAX_ZOOM);
+    }
+}
+
+/// Keeps the paused game from reacting to keys while the camera is being moved around
+pub fn suppress_gameplay_input(mut btn_input: ResMut<ButtonInput<KeyCode>>) {
+    btn_input.reset_all();
+}
@@ -1,12 +1,37 @@
+use std::{
+    f32::consts::{PI, TAU},
+    time::{Duration, Instant},
+};
+
 use bevy::{platform::collections::HashMap, prelude::*};
 
+use rand::Rng;
+
+use crate::{config::GameConfig, playfield::PlayBounds, settings::Settings, sleep::Sleeping};
+
 pub fn physics_plugin(app: &mut App) {
     app.add_message::<CollisionEvent>();
+    app.add_message::<RadialImpulse>();
+    app.init_resource::<CollisionBudget>();
+    app.register_type::<Velocity>();
+    app.register_type::<CircleCollider>();
 
-    app.add_systems(Update, (apply_velocity, detect_collisions));
+    app.add_systems(
+        Update,
+        (
+            (apply_radial_impulses, apply_velocity).chain(),
+            screen_wrap.after(apply_velocity),
+            (expire_collision_ignores, detect_collisions).chain(),
+            draw_colliders.run_if(|settings: Res<Settings>| settings.show_colliders),
+        ),
+    );
 }
 
-#[derive(Component)]
+/// How far past the edge of the window something can drift before it wraps around
+pub const WRAP_MARGIN: f32 = 50.0;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
 pub struct Velocity {
     pub linear: Vec2,
     pub linear_drag: Vec2,
@@ -26,7 +51,119 @@ impl Default for Velocity {
     }
 }
 
+impl Velocity {
+    /// Slows `linear` by `drag` per second on each axis
+    pub fn apply_linear_drag(&mut self, drag: Vec2, delta: f32) {
+        self.linear *= 1.0 - (drag * delta);
+    }
+
+    /// Slows `angular` by `drag` per second
+    pub fn apply_angular_drag(&mut self, drag: f32, delta: f32) {
+        self.angular *= 1.0 - (drag * delta);
+    }
+
+    /// Caps the speed and spin, keeping their direction
+    pub fn clamp_to_max(&mut self, max_linear: f32, max_angular: f32) {
+        self.linear = self.linear.clamp_length_max(max_linear);
+        self.angular = self.angular.clamp(-max_angular, max_angular);
+    }
+}
+
+/// Which way an entity faces in radians, zero points up. [`apply_velocity`] turns it by the
+/// angular velocity, keeps it wrapped into -π..π and writes it into the `Transform`, so anything
+/// that needs a facing reads this instead of pulling euler angles back out of the quaternion.
+#[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+pub struct Heading(pub f32);
+
+impl Heading {
+    pub fn new(radians: f32) -> Self {
+        Self(wrap_angle(radians))
+    }
+
+    /// Unit vector the entity is facing along
+    pub fn forward(self) -> Vec2 {
+        Vec2::new(-self.0.sin(), self.0.cos())
+    }
+}
+
+/// Wraps an angle into -π..π
+pub fn wrap_angle(radians: f32) -> f32 {
+    (radians + PI).rem_euclid(TAU) - PI
+}
+
+/// Forces pushed onto an entity during a frame. `apply_velocity` integrates them into the
+/// entity's `Velocity` and then clears them, so steering systems just add to it every frame.
+#[derive(Component, Default)]
+pub struct ForceAccum(pub Vec2);
+
+/// Scales how quickly simulation time passes for a single entity, entities without one run at 1.0
+#[derive(Component, Clone, Copy)]
+pub struct TimeScale(pub f32);
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Caps how fast an entity actually moves, after any [`SpeedBoost`] is applied
+#[derive(Component, Clone, Copy)]
+pub struct MaxSpeed(pub f32);
+
+/// Scales how far an entity moves each frame without touching its stored [`Velocity`], so the
+/// boost never compounds and the entity drops straight back to its old speed once it's removed
 #[derive(Component)]
+pub struct SpeedBoost {
+    pub multiplier: f32,
+    pub timer: Timer,
+}
+
+/// Scales how far an entity moves each frame for the whole of its life. Like [`SpeedBoost`] it
+/// leaves the stored [`Velocity`] alone, so fragments inheriting it don't compound the scale.
+#[derive(Component, Clone, Copy)]
+pub struct SpeedScale(pub f32);
+
+/// Parked entities (see `pool.rs`) carry this, physics skips them entirely so they never move
+/// or produce collision events
+#[derive(Component)]
+pub struct Disabled;
+
+/// Entities with this marker leave one side of the [`PlayBounds`] and come back in on the other
+#[derive(Component)]
+pub struct ScreenWrap;
+
+/// A random point on the edge of the area inside `bounds` half extents
+pub fn random_edge_position(rng: &mut impl Rng, bounds: Vec2) -> Vec2 {
+    let side = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+
+    if rng.random_bool(0.5) {
+        Vec2::new(bounds.x * side, rng.random_range(-bounds.y..bounds.y))
+    } else {
+        Vec2::new(rng.random_range(-bounds.x..bounds.x), bounds.y * side)
+    }
+}
+
+/// Tries up to `attempts` random points on the edge of `bounds` and returns the first one
+/// further than `min_separation` from the edge of every circle in `existing`, given as center and
+/// radius. `None` if every attempt landed too close to something.
+pub fn find_safe_spawn_position(
+    rng: &mut impl Rng,
+    bounds: Vec2,
+    attempts: u32,
+    min_separation: f32,
+    existing: &[(Vec2, f32)],
+) -> Option<Vec2> {
+    (0..attempts)
+        .map(|_| random_edge_position(rng, bounds))
+        .find(|pos| {
+            existing
+                .iter()
+                .all(|(other, radius)| pos.distance(*other) > min_separation + radius)
+        })
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
 pub struct CircleCollider {
     pub radius: f32,
 }
@@ -37,38 +174,307 @@ impl Default for CircleCollider {
     }
 }
 
+impl CircleCollider {
+    /// Whether this collider placed at `pos` overlaps `other` placed at `other_pos`
+    pub fn overlaps(self, pos: Vec2, other: CircleCollider, other_pos: Vec2) -> bool {
+        pos.distance(other_pos) < self.radius + other.radius
+    }
+}
+
+/// Tighter outline on top of an entity's [`CircleCollider`], in local space. The circle still
+/// decides what's close enough to check, and a hit only counts once the other collider's circle
+/// also overlaps this polygon.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct PolygonCollider {
+    /// Corners of a convex polygon, in order around it
+    pub vertices: Vec<Vec2>,
+}
+
+impl PolygonCollider {
+    /// The corners placed by `tsf`
+    pub fn world_vertices(&self, tsf: &Transform) -> Vec<Vec2> {
+        self.vertices
+            .iter()
+            .map(|vertex| tsf.transform_point(vertex.extend(0.0)).xy())
+            .collect()
+    }
+
+    /// Separating axis test against a circle. The polygon's edge normals are tried, and the axis
+    /// from its nearest corner to the circle covers the corner regions.
+    pub fn overlaps_circle(&self, tsf: &Transform, center: Vec2, radius: f32) -> bool {
+        let vertices = self.world_vertices(tsf);
+        let Some(nearest) = vertices.iter().min_by(|a, b| {
+            a.distance_squared(center)
+                .total_cmp(&b.distance_squared(center))
+        }) else {
+            return false;
+        };
+
+        let edge_normals = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(a, b)| (*b - *a).perp());
+        let corner_axis = std::iter::once(center - *nearest);
+
+        edge_normals.chain(corner_axis).all(|axis| {
+            let Some(axis) = axis.try_normalize() else {
+                // The circle's center sits right on a corner
+                return true;
+            };
+            let (min, max) = vertices
+                .iter()
+                .map(|vertex| vertex.dot(axis))
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), d| {
+                    (min.min(d), max.max(d))
+                });
+            let projected = center.dot(axis);
+            projected + radius > min && projected - radius < max
+        })
+    }
+}
+
+/// Stops this entity colliding with `entity` until `until`, in seconds of elapsed game time.
+///
+/// Shots carry one pointing at whoever fired them, so a ship reversing faster than its lasers
+/// leave the muzzle doesn't run into its own fire. Expired ones are removed by
+/// [`expire_collision_ignores`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct IgnoreCollisionWith {
+    pub entity: Entity,
+    pub until: f32,
+}
+
+impl IgnoreCollisionWith {
+    fn ignores(ignore: Option<&Self>, other: Entity, now: f32) -> bool {
+        ignore.is_some_and(|ignore| ignore.entity == other && now < ignore.until)
+    }
+}
+
+/// Stops entities in the same `group` colliding with each other until `until`, in seconds of
+/// elapsed game time.
+///
+/// Fragments of a split share one, keyed on the asteroid they came from, so siblings spawned on
+/// top of each other don't report collisions while they fan out. Expired ones are removed by
+/// [`expire_collision_ignores`].
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub struct CollisionGroup {
+    pub group: Entity,
+    pub until: f32,
+}
+
+impl CollisionGroup {
+    fn ignores(a: Option<&Self>, b: Option<&Self>, now: f32) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => a.group == b.group && now < a.until.min(b.until),
+            _ => false,
+        }
+    }
+}
+
+/// Pairs where both entities carry this are the first thing dropped when collision detection
+/// runs over budget. Nothing that involves the ship should ever have it.
+#[derive(Component)]
+pub struct LowPriorityCollisions;
+
+/// How long collision detection has been taking, and whether it's cutting corners because of it
+#[derive(Resource, Default, Debug)]
+pub struct CollisionBudget {
+    pub last_frame: Duration,
+    /// Pairs of colliders compared last frame, skipped ones included
+    pub pairs_tested: u32,
+    /// [`CollisionEvent`]s written last frame
+    pub collisions: u32,
+    /// Skipping pairs of [`LowPriorityCollisions`] entities, and spawning is held back
+    pub degraded: bool,
+    /// Frames in a row spent on the wrong side of the budget for the current mode
+    streak: u32,
+}
+
+impl CollisionBudget {
+    /// Records a frame's timing and flips modes once it's been over (or back under) `limit`
+    /// for `frames` frames in a row
+    pub fn record(&mut self, elapsed: Duration, limit: Duration, frames: u32) {
+        self.last_frame = elapsed;
+
+        if (elapsed > limit) == self.degraded {
+            self.streak = 0;
+            return;
+        }
+
+        self.streak += 1;
+        if self.streak < frames {
+            return;
+        }
+
+        self.streak = 0;
+        self.degraded = !self.degraded;
+        if self.degraded {
+            warn!(
+                "Collision detection took {:.2}ms, over the {:.2}ms budget. Skipping low \
+                priority pairs until it recovers",
+                elapsed.as_secs_f32() * 1000.0,
+                limit.as_secs_f32() * 1000.0
+            );
+        } else {
+            info!("Collision detection is back under budget");
+        }
+    }
+}
+
 #[derive(Message)]
 pub struct CollisionEvent(pub Entity, pub Entity);
 
+/// Outward shove from an explosion, `strength` at the center fading linearly to nothing at
+/// `radius`. Handled by [`apply_radial_impulses`] for everything with a collider.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct RadialImpulse {
+    pub center: Vec2,
+    pub radius: f32,
+    pub strength: f32,
+}
+
+impl RadialImpulse {
+    /// Change in velocity for something at `pos`, zero outside the radius
+    pub fn impulse_at(&self, pos: Vec2) -> Vec2 {
+        let offset = pos - self.center;
+        let distance = offset.length();
+        if distance >= self.radius {
+            return Vec2::ZERO;
+        }
+
+        // Nothing to push away from right on the center, so it goes straight up
+        let direction = offset.try_normalize().unwrap_or(Vec2::Y);
+        direction * self.strength * (1.0 - distance / self.radius)
+    }
+}
+
+/// Adds every [`RadialImpulse`] to the velocity of whatever it reaches. An impulse can't push
+/// anything past its [`MaxSpeed`], but doesn't slow down something already over it either.
+pub fn apply_radial_impulses(
+    mut impulses: MessageReader<RadialImpulse>,
+    mut bodies: Query<
+        (&Transform, &mut Velocity, Option<&MaxSpeed>),
+        (With<CircleCollider>, Without<Disabled>),
+    >,
+) {
+    for impulse in impulses.read() {
+        for (tsf, mut vel, max_speed) in bodies.iter_mut() {
+            let push = impulse.impulse_at(tsf.translation.xy());
+            if push == Vec2::ZERO {
+                continue;
+            }
+
+            let pushed = vel.linear + push;
+            vel.linear = match max_speed {
+                Some(max_speed) => pushed.clamp_length_max(max_speed.0.max(vel.linear.length())),
+                None => pushed,
+            };
+        }
+    }
+}
+
+pub fn expire_collision_ignores(
+    ignores: Query<(Entity, &IgnoreCollisionWith)>,
+    groups: Query<(Entity, &CollisionGroup)>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    for (ent, ignore) in ignores {
+        if time.elapsed_secs() >= ignore.until {
+            cmds.entity(ent).remove::<IgnoreCollisionWith>();
+        }
+    }
+    for (ent, group) in groups {
+        if time.elapsed_secs() >= group.until {
+            cmds.entity(ent).remove::<CollisionGroup>();
+        }
+    }
+}
+
 pub fn detect_collisions(
-    physical: Query<(&Transform, &CircleCollider, Entity)>,
+    physical: Query<
+        (
+            &Transform,
+            &CircleCollider,
+            Option<&PolygonCollider>,
+            Entity,
+            Option<&IgnoreCollisionWith>,
+            Option<&CollisionGroup>,
+            Has<LowPriorityCollisions>,
+        ),
+        (Without<Disabled>, Without<Sleeping>),
+    >,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut budget: ResMut<CollisionBudget>,
     mut events: MessageWriter<CollisionEvent>,
 ) {
+    // Only used for diagnostics, so wall clock time is fine here
+    let start = Instant::now();
+    let skip_low_priority = budget.degraded;
+    let now = time.elapsed_secs();
     let mut collisions: HashMap<Entity, Vec<Entity>> = HashMap::new();
+    let mut pairs_tested = 0;
 
-    for (tsf, collider, entity) in physical.iter() {
-        if !collisions.contains_key(&entity) {
-            collisions.insert(entity, vec![]);
-        }
-
-        for (tsf_b, _collider_b, ent_b) in physical.iter() {
+    for (tsf, collider, polygon, entity, ignore, group, low_priority) in physical.iter() {
+        for (tsf_b, collider_b, polygon_b, ent_b, ignore_b, group_b, low_priority_b) in
+            physical.iter()
+        {
             //Don't collide with self
             if entity == ent_b {
                 continue;
             }
+            pairs_tested += 1;
 
-            if tsf.translation.distance(tsf_b.translation) < collider.radius {
+            if skip_low_priority && low_priority && low_priority_b {
+                continue;
+            }
+
+            if IgnoreCollisionWith::ignores(ignore, ent_b, now)
+                || IgnoreCollisionWith::ignores(ignore_b, entity, now)
+                || CollisionGroup::ignores(group, group_b, now)
+            {
+                continue;
+            }
+
+            // Polygons narrow the hit down further. Two polygons are rare enough that the
+            // second one is treated as its circle.
+            let narrow_hit = || match (polygon, polygon_b) {
+                (Some(polygon), _) => {
+                    polygon.overlaps_circle(tsf, tsf_b.translation.xy(), collider_b.radius)
+                }
+                (None, Some(polygon_b)) => {
+                    polygon_b.overlaps_circle(tsf_b, tsf.translation.xy(), collider.radius)
+                }
+                (None, None) => true,
+            };
+
+            // A polygon's circle bounds the whole hull, so something grazing its edge can sit
+            // further out than either radius alone
+            let reach = if polygon.is_some() || polygon_b.is_some() {
+                collider.radius + collider_b.radius
+            } else {
+                collider.radius
+            };
+
+            if tsf.translation.distance(tsf_b.translation) < reach && narrow_hit() {
                 if let Some(collisions_entb) = collisions.get(&ent_b)
                     && collisions_entb.contains(&entity)
                 {
                     continue;
                 }
 
-                collisions.get_mut(&entity).unwrap().push(ent_b)
+                collisions.entry(entity).or_default().push(ent_b);
             }
         }
     }
 
+    budget.record(
+        start.elapsed(),
+        Duration::from_secs_f32(config.physics.collision_budget_ms / 1000.0),
+        config.physics.budget_frames,
+    );
+
     let mut events_to_send = vec![];
     for (ent, collided_with) in collisions.iter() {
         collided_with.iter().for_each(|entb| {
@@ -76,17 +482,391 @@ pub fn detect_collisions(
         });
     }
 
+    budget.pairs_tested = pairs_tested;
+    budget.collisions = events_to_send.len() as u32;
     events.write_batch(events_to_send);
 }
 
-pub fn apply_velocity(mut movers: Query<(&mut Transform, &mut Velocity)>, time: Res<Time>) {
-    for (mut tsf, mut vel) in movers.iter_mut() {
-        let vel_drag = vel.linear_drag;
-        vel.linear *= 1.0 - (vel_drag * time.delta_secs());
-        let ang_drag = vel.angular_drag;
-        vel.angular *= 1.0 - (ang_drag * time.delta_secs());
+pub fn apply_velocity(
+    mut movers: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            Option<&mut Heading>,
+            Option<&TimeScale>,
+            Option<&mut ForceAccum>,
+            Option<&SpeedBoost>,
+            Option<&SpeedScale>,
+            Option<&MaxSpeed>,
+        ),
+        (Without<Disabled>, Without<Sleeping>),
+    >,
+    time: Res<Time>,
+) {
+    for (mut tsf, mut vel, heading, time_scale, force, boost, scale, max_speed) in movers.iter_mut()
+    {
+        let delta = time.delta_secs() * time_scale.copied().unwrap_or_default().0;
+
+        if let Some(mut force) = force {
+            vel.linear += force.0 * delta;
+            force.0 = Vec2::ZERO;
+        }
+
+        let (linear_drag, angular_drag) = (vel.linear_drag, vel.angular_drag);
+        vel.apply_linear_drag(linear_drag, delta);
+        vel.apply_angular_drag(angular_drag, delta);
+
+        // What actually moves the entity this frame, the stored velocity stays unboosted
+        let mut motion = *vel;
+        if let Some(boost) = boost {
+            motion.linear *= boost.multiplier;
+        }
+        if let Some(scale) = scale {
+            motion.linear *= scale.0;
+        }
+        if let Some(max_speed) = max_speed {
+            motion.clamp_to_max(max_speed.0, f32::INFINITY);
+        }
+
+        tsf.translation += motion.linear.extend(0.0) * delta;
+
+        // Accumulating spin into the quaternion drifts it off unit length over a long run
+        match heading {
+            Some(mut heading) => {
+                heading.0 = wrap_angle(heading.0 + vel.angular * delta);
+                tsf.rotation = Quat::from_rotation_z(heading.0);
+            }
+            None => {
+                tsf.rotate_z(vel.angular * delta);
+                tsf.rotation = tsf.rotation.normalize();
+            }
+        }
+    }
+}
+
+pub fn screen_wrap(mut wrappers: Query<&mut Transform, With<ScreenWrap>>, bounds: Res<PlayBounds>) {
+    let bounds = bounds.wrap();
+
+    for mut tsf in wrappers.iter_mut() {
+        if tsf.translation.x.abs() > bounds.x {
+            tsf.translation.x = -bounds.x * tsf.translation.x.signum();
+        }
+
+        if tsf.translation.y.abs() > bounds.y {
+            tsf.translation.y = -bounds.y * tsf.translation.y.signum();
+        }
+    }
+}
+
+/// Outlines every collider, turned on from the settings screen. Sleeping ones are drawn dimmer.
+pub fn draw_colliders(
+    colliders: Query<
+        (
+            &GlobalTransform,
+            &CircleCollider,
+            Option<&PolygonCollider>,
+            Has<Sleeping>,
+        ),
+        Without<Disabled>,
+    >,
+    mut gizmos: Gizmos,
+) {
+    for (tsf, collider, polygon, sleeping) in colliders {
+        let color = if sleeping {
+            Color::srgb(0.3, 0.4, 1.0)
+        } else {
+            Color::srgb(0.0, 1.0, 0.0)
+        };
+        gizmos.circle_2d(tsf.translation().xy(), collider.radius, color);
+
+        if let Some(polygon) = polygon {
+            let vertices = polygon.world_vertices(&tsf.compute_transform());
+            if let Some(first) = vertices.first() {
+                gizmos.linestrip_2d(vertices.iter().copied().chain([*first]), Color::WHITE);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn world_with_time(delta: Duration) -> World {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(delta);
+        world.insert_resource(time);
+        world
+    }
+
+    fn collision_world(a: Vec2, b: Vec2) -> World {
+        let mut world = world_with_time(Duration::ZERO);
+        world.init_resource::<GameConfig>();
+        world.init_resource::<CollisionBudget>();
+        world.init_resource::<Messages<CollisionEvent>>();
+
+        for pos in [a, b] {
+            world.spawn((
+                Transform::from_xyz(pos.x, pos.y, 0.0),
+                CircleCollider { radius: 10.0 },
+            ));
+        }
+
+        world
+    }
+
+    #[test]
+    fn clamp_to_max_keeps_direction() {
+        let mut vel = Velocity {
+            linear: Vec2::new(300.0, 400.0),
+            angular: -5.0,
+            ..default()
+        };
+        vel.clamp_to_max(100.0, 2.0);
+
+        assert!((vel.linear - Vec2::new(60.0, 80.0)).length() < 1e-4);
+        assert_eq!(vel.angular, -2.0);
+    }
+
+    #[test]
+    fn apply_velocity_moves_by_dragged_velocity() {
+        let mut world = world_with_time(Duration::from_millis(100));
+        let ent = world
+            .spawn((
+                Transform::default(),
+                Velocity {
+                    linear: Vec2::new(100.0, 0.0),
+                    ..Default::default()
+                },
+            ))
+            .id();
+
+        world.run_system_once(apply_velocity).unwrap();
+
+        // Drag is applied before moving: 100 * (1 - 0.5 * 0.1) * 0.1
+        let tsf = world.get::<Transform>(ent).unwrap();
+        assert!((tsf.translation.x - 9.5).abs() < 1e-4);
+        assert_eq!(tsf.translation.y, 0.0);
+    }
+
+    #[test]
+    fn time_scale_slows_movement() {
+        let mut world = world_with_time(Duration::from_millis(100));
+        let velocity = Velocity {
+            linear: Vec2::new(100.0, 0.0),
+            linear_drag: Vec2::ZERO,
+            ..default()
+        };
+        let normal = world.spawn((Transform::default(), velocity)).id();
+        let slowed = world
+            .spawn((Transform::default(), velocity, TimeScale(0.4)))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_velocity);
+        for _ in 0..10 {
+            schedule.run(&mut world);
+        }
+
+        let distance = |ent| world.get::<Transform>(ent).unwrap().translation.x;
+        assert!((distance(normal) - 100.0).abs() < 1e-3);
+        assert!((distance(slowed) - 0.4 * distance(normal)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn radial_impulse_falls_off_and_respects_max_speed() {
+        let mut world = world_with_time(Duration::ZERO);
+        world.init_resource::<Messages<RadialImpulse>>();
+        let spawn = |world: &mut World, pos: Vec2| {
+            world
+                .spawn((
+                    Transform::from_xyz(pos.x, pos.y, 0.0),
+                    Velocity::default(),
+                    CircleCollider::default(),
+                    MaxSpeed(150.0),
+                ))
+                .id()
+        };
+        let center = spawn(&mut world, Vec2::ZERO);
+        let halfway = spawn(&mut world, Vec2::new(-50.0, 0.0));
+        let outside = spawn(&mut world, Vec2::new(0.0, 120.0));
+
+        world.write_message(RadialImpulse {
+            center: Vec2::ZERO,
+            radius: 100.0,
+            strength: 200.0,
+        });
+        world.run_system_once(apply_radial_impulses).unwrap();
+
+        let velocity = |ent| world.get::<Velocity>(ent).unwrap().linear;
+        // Full strength straight up, cut down to the max speed
+        assert_eq!(velocity(center), Vec2::new(0.0, 150.0));
+        assert_eq!(velocity(halfway), Vec2::new(-100.0, 0.0));
+        assert_eq!(velocity(outside), Vec2::ZERO);
+    }
+
+    #[test]
+    fn heading_survives_millions_of_tiny_rotations() {
+        const FRAMES: u32 = 2_000_000;
+        const ANGULAR: f32 = 0.7;
+
+        let mut world = world_with_time(Duration::from_millis(1));
+        let ent = world
+            .spawn((
+                Transform::default(),
+                Heading::default(),
+                Velocity {
+                    angular: ANGULAR,
+                    angular_drag: 0.0,
+                    ..Default::default()
+                },
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_velocity);
+        for _ in 0..FRAMES {
+            schedule.run(&mut world);
+        }
+
+        let expected = (f64::from(FRAMES) * f64::from(ANGULAR) * 0.001 + f64::from(PI))
+            .rem_euclid(f64::from(TAU))
+            - f64::from(PI);
+        let heading = world.get::<Heading>(ent).unwrap().0;
+        assert!(heading.abs() <= PI);
+        assert!(
+            (f64::from(heading) - expected).abs() < 1e-2,
+            "{heading} vs {expected}"
+        );
+
+        let rotation = world.get::<Transform>(ent).unwrap().rotation;
+        assert!(rotation.is_normalized());
+        assert!((rotation.to_euler(EulerRot::XYZ).2 - heading).abs() < 1e-5);
+    }
+
+    #[test]
+    fn overlapping_colliders_collide_once() {
+        let mut world = collision_world(Vec2::ZERO, Vec2::new(5.0, 0.0));
+
+        world.run_system_once(detect_collisions).unwrap();
+
+        assert_eq!(world.resource::<Messages<CollisionEvent>>().len(), 1);
+    }
+
+    #[test]
+    fn collision_counts_are_recorded() {
+        let mut world = collision_world(Vec2::ZERO, Vec2::new(5.0, 0.0));
+        world.spawn((
+            Transform::from_xyz(500.0, 0.0, 0.0),
+            CircleCollider::default(),
+        ));
+
+        world.run_system_once(detect_collisions).unwrap();
+
+        // Every ordered pair of the three is tested, only the overlapping two collide
+        let budget = world.resource::<CollisionBudget>();
+        assert_eq!(budget.pairs_tested, 6);
+        assert_eq!(budget.collisions, 1);
+    }
+
+    #[test]
+    fn polygon_narrows_circle_hits() {
+        let mut world = collision_world(Vec2::new(900.0, 0.0), Vec2::new(-900.0, 0.0));
+        let ship = world
+            .spawn((
+                Transform::default(),
+                CircleCollider { radius: 50.0 },
+                PolygonCollider {
+                    vertices: vec![
+                        Vec2::new(0.0, 30.0),
+                        Vec2::new(-20.0, -25.0),
+                        Vec2::new(20.0, -25.0),
+                    ],
+                },
+            ))
+            .id();
+        let polygon = world.get::<PolygonCollider>(ship).unwrap().clone();
+
+        // Inside the circle either way, but only one of them reaches the hull
+        let beside_nose = Vec2::new(30.0, 25.0);
+        let ahead_of_nose = Vec2::new(0.0, 35.0);
+        assert!(!polygon.overlaps_circle(&Transform::default(), beside_nose, 10.0));
+        assert!(polygon.overlaps_circle(&Transform::default(), ahead_of_nose, 10.0));
+
+        for pos in [beside_nose, ahead_of_nose] {
+            world.spawn((
+                Transform::from_xyz(pos.x, pos.y, 0.0),
+                CircleCollider { radius: 10.0 },
+            ));
+        }
+        world.run_system_once(detect_collisions).unwrap();
+
+        let events = world.resource::<Messages<CollisionEvent>>();
+        let hits: Vec<_> = events
+            .iter_current_update_messages()
+            .filter(|event| event.0 == ship || event.1 == ship)
+            .collect();
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn large_asteroid_grazing_the_hull_hits() {
+        let mut world = collision_world(Vec2::new(900.0, 0.0), Vec2::new(-900.0, 0.0));
+        let ship = world
+            .spawn((
+                Transform::default(),
+                CircleCollider { radius: 50.0 },
+                PolygonCollider {
+                    vertices: vec![
+                        Vec2::new(0.0, 30.0),
+                        Vec2::new(-20.0, -25.0),
+                        Vec2::new(20.0, -25.0),
+                    ],
+                },
+            ))
+            .id();
+
+        // Further from the ship than either radius, but its edge reaches past the nose
+        world.spawn((
+            Transform::from_xyz(0.0, 85.0, 0.0),
+            CircleCollider { radius: 60.0 },
+        ));
+        world.run_system_once(detect_collisions).unwrap();
+
+        let events = world.resource::<Messages<CollisionEvent>>();
+        let hits = events
+            .iter_current_update_messages()
+            .filter(|event| event.0 == ship || event.1 == ship)
+            .count();
+        assert_eq!(hits, 1);
+    }
+
+    #[test]
+    fn safe_spawn_gives_up_when_everything_is_taken() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let bounds = Vec2::new(400.0, 300.0);
+
+        let blanket = [(Vec2::ZERO, 1000.0)];
+        assert_eq!(
+            find_safe_spawn_position(&mut rng, bounds, 20, 10.0, &blanket),
+            None
+        );
+
+        let corner = [(Vec2::new(400.0, 300.0), 50.0)];
+        let pos = find_safe_spawn_position(&mut rng, bounds, 20, 10.0, &corner).unwrap();
+        assert!(pos.distance(corner[0].0) > 60.0);
+    }
+
+    #[test]
+    fn separate_colliders_do_not_collide() {
+        let mut world = collision_world(Vec2::ZERO, Vec2::new(50.0, 0.0));
+
+        world.run_system_once(detect_collisions).unwrap();
 
-        tsf.translation += Vec3::new(vel.linear.x, vel.linear.y, 0.0) * time.delta_secs();
-        tsf.rotate_z(vel.angular * time.delta_secs());
+        assert!(world.resource::<Messages<CollisionEvent>>().is_empty());
     }
 }
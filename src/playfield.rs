@@ -0,0 +1,207 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
+
+use crate::{
+    MainCamera, PlayerShip, SHIP_MAX_SPEED,
+    photo::photo_mode_active,
+    physics::{ScreenWrap, Velocity, WRAP_MARGIN, screen_wrap},
+};
+
+pub fn playfield_plugin(app: &mut App) {
+    app.insert_resource(PlayField::from_args());
+    app.init_resource::<PlayBounds>();
+
+    app.add_systems(PreStartup, update_play_bounds);
+    app.add_systems(
+        PreUpdate,
+        update_play_bounds.run_if(on_message::<WindowResized>),
+    );
+    app.add_systems(
+        Update,
+        (
+            wrap_ship,
+            (dynamic_zoom, follow_ship)
+                .chain()
+                .after(screen_wrap)
+                .run_if(not(photo_mode_active)),
+        )
+            .run_if(resource_equals(PlayField::OpenSpace)),
+    );
+}
+
+/// The window can't be resized smaller than this, spawning and the HUD are laid out for at least
+/// this much room
+pub const MIN_WINDOW_SIZE: Vec2 = Vec2::new(800.0, 600.0);
+/// How many screens across the world is in open space
+pub const OPEN_SPACE_SCREENS: f32 = 3.5;
+/// How far ahead of the ship the camera aims, along the way it's moving
+pub const CAMERA_LEAD_DISTANCE: f32 = 80.0;
+/// How quickly the camera catches up with where it's aiming, higher is snappier
+pub const CAMERA_FOLLOW_RATE: f32 = 3.0;
+/// Camera zoom with the ship at rest, below 1 is zoomed in
+pub const ZOOM_SLOW_SCALE: f32 = 0.9;
+/// Camera zoom with the ship at top speed
+pub const ZOOM_FAST_SCALE: f32 = 1.4;
+/// How quickly the zoom catches up with the ship's speed
+pub const ZOOM_RATE: f32 = 2.0;
+/// The zoom never leaves this range, whatever the ship does
+pub const ZOOM_MIN_SCALE: f32 = 0.85;
+pub const ZOOM_MAX_SCALE: f32 = 1.5;
+
+/// How big the world is, picked once at startup
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlayField {
+    /// The world is the window, the camera never moves
+    #[default]
+    Classic,
+    /// The world is several screens across and the camera follows the ship
+    OpenSpace,
+}
+
+impl PlayField {
+    /// Open space is opted into with `--open-space`
+    pub fn from_args() -> Self {
+        if std::env::args().any(|arg| arg == "--open-space") {
+            PlayField::OpenSpace
+        } else {
+            PlayField::Classic
+        }
+    }
+}
+
+/// The area the game is played in, centered on the origin. Spawning, wrapping and culling all
+/// work off this instead of the window, so they behave the same whichever [`PlayField`] is used.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Debug)]
+pub struct PlayBounds {
+    pub half_extents: Vec2,
+}
+
+impl PlayBounds {
+    pub fn new(field: PlayField, window: &Window) -> Self {
+        let screen = window.size() / 2.0;
+        let half_extents = match field {
+            PlayField::Classic => screen,
+            PlayField::OpenSpace => screen * OPEN_SPACE_SCREENS,
+        };
+        Self { half_extents }
+    }
+
+    /// Half extents of the area wrapping entities are kept inside, a little past the edge so
+    /// nothing visibly pops from one side to the other
+    pub fn wrap(&self) -> Vec2 {
+        self.half_extents + Vec2::splat(WRAP_MARGIN)
+    }
+}
+
+/// Keeps [`PlayBounds`] in step with the window as it's resized
+pub fn update_play_bounds(
+    field: Res<PlayField>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut bounds: ResMut<PlayBounds>,
+) {
+    bounds.set_if_neq(PlayBounds::new(*field, &window));
+}
+
+/// The ship can't fly off the screen in open space, it comes back around the far side
+pub fn wrap_ship(ships: Query<Entity, Added<PlayerShip>>, mut cmds: Commands) {
+    for ship in ships {
+        cmds.entity(ship).insert(ScreenWrap);
+    }
+}
+
+/// Half the size of the area `projection` shows in world units, for a camera rendering to `window`
+pub fn view_half_extents(window: &Window, projection: &Projection) -> Vec2 {
+    let scale = match projection {
+        Projection::Orthographic(ortho) => ortho.scale,
+        _ => 1.0,
+    };
+    window.size() / 2.0 * scale
+}
+
+/// Camera zoom for a ship flying at `speed`, further out the faster it goes
+pub fn zoom_target(speed: f32) -> f32 {
+    let t = (speed / SHIP_MAX_SPEED).clamp(0.0, 1.0);
+    ZOOM_SLOW_SCALE.lerp(ZOOM_FAST_SCALE, t)
+}
+
+/// Zooms out while the ship is moving fast so more of what's coming shows, and back in as it
+/// slows down
+pub fn dynamic_zoom(
+    mut projection: Single<&mut Projection, With<MainCamera>>,
+    ship: Single<&Velocity, With<PlayerShip>>,
+    time: Res<Time>,
+) {
+    let Projection::Orthographic(ortho) = &mut **projection else {
+        return;
+    };
+
+    let target = zoom_target(ship.linear.length());
+    let scale = ortho
+        .scale
+        .lerp(target, 1.0 - (-ZOOM_RATE * time.delta_secs()).exp());
+    ortho.scale = scale.clamp(ZOOM_MIN_SCALE, ZOOM_MAX_SCALE);
+}
+
+/// Where the camera aims for a ship at `pos` moving at `velocity`: a fixed distance ahead of it
+/// however fast it goes, kept within `limit` of the origin
+pub fn camera_target(pos: Vec2, velocity: Vec2, limit: Vec2) -> Vec2 {
+    (pos + velocity.normalize_or_zero() * CAMERA_LEAD_DISTANCE).clamp(-limit, limit)
+}
+
+/// Eases the camera toward a point a little ahead of the ship, never showing past the edge of
+/// the world
+pub fn follow_ship(
+    camera: Single<(&mut Transform, &Projection), With<MainCamera>>,
+    ship: Single<(&Transform, &Velocity), (With<PlayerShip>, Without<MainCamera>)>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    bounds: Res<PlayBounds>,
+    time: Res<Time>,
+) {
+    let (ship_tsf, ship_vel) = ship.into_inner();
+    let (mut camera, projection) = camera.into_inner();
+
+    let limit = (bounds.half_extents - view_half_extents(&window, projection)).max(Vec2::ZERO);
+    let target = camera_target(ship_tsf.translation.xy(), ship_vel.linear, limit);
+
+    let current = camera.translation.xy();
+    // The ship wrapped around, sweeping the camera across the whole world would be disorienting
+    let next = if current.distance(target) > bounds.half_extents.min_element() {
+        target
+    } else {
+        current.lerp(
+            target,
+            1.0 - (-CAMERA_FOLLOW_RATE * time.delta_secs()).exp(),
+        )
+    };
+
+    camera.translation = next.extend(camera.translation.z);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camera_leads_by_a_fixed_distance() {
+        let limit = Vec2::splat(1000.0);
+        for speed in [10.0, 500.0] {
+            let target = camera_target(Vec2::ZERO, Vec2::X * speed, limit);
+            assert_eq!(target, Vec2::X * CAMERA_LEAD_DISTANCE);
+        }
+
+        assert_eq!(camera_target(Vec2::ONE, Vec2::ZERO, limit), Vec2::ONE);
+        assert_eq!(
+            camera_target(Vec2::new(990.0, 0.0), Vec2::X, limit),
+            Vec2::new(1000.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn zoom_follows_speed_within_limits() {
+        assert_eq!(zoom_target(0.0), ZOOM_SLOW_SCALE);
+        assert_eq!(zoom_target(SHIP_MAX_SPEED * 2.0), ZOOM_FAST_SCALE);
+        assert!((ZOOM_MIN_SCALE..=ZOOM_MAX_SCALE).contains(&zoom_target(SHIP_MAX_SPEED / 2.0)));
+    }
+}
@@ -0,0 +1,116 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{
+    LaserShot,
+    comet::CometTrail,
+    handle_collisions,
+    mines::Shockwave,
+    physics::{Disabled, Velocity},
+};
+
+pub fn pool_plugin(app: &mut App) {
+    app.init_resource::<Pool<LaserShot>>();
+    app.init_resource::<Pool<Shockwave>>();
+    app.init_resource::<Pool<CometTrail>>();
+
+    // Death and restart sweep the pools along with everything else, anything recycled after that
+    // would point at a despawned entity
+    app.add_systems(
+        Update,
+        (
+            recycle::<LaserShot>,
+            recycle::<Shockwave>,
+            recycle::<CometTrail>,
+        )
+            .before(handle_collisions),
+    );
+}
+
+/// Short-lived effects carrying this are parked once their [`Lifetime`](crate::effects::Lifetime)
+/// runs out, instead of being despawned
+#[derive(Component)]
+pub struct Pooled;
+
+/// Free list of parked entities carrying a `T`, handed back out by spawners before they fall
+/// back to spawning fresh entities.
+///
+/// Parked entities keep their `GameCleanup` marker, so anything that sweeps them away must also
+/// [`clear`](Pool::clear) the pool.
+#[derive(Resource)]
+pub struct Pool<T: Component> {
+    free: Vec<Entity>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Component> Default for Pool<T> {
+    fn default() -> Self {
+        Self {
+            free: vec![],
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Component> Pool<T> {
+    /// Takes a parked entity out of the pool, the caller must remove its [`Disabled`] marker
+    /// and reinitialize its components. Entries that are no longer parked, like ones swept away
+    /// since, are dropped on the way.
+    pub fn pop(&mut self, parked: &Query<(), (With<T>, With<Disabled>)>) -> Option<Entity> {
+        while let Some(entity) = self.free.pop() {
+            if parked.contains(entity) {
+                return Some(entity);
+            }
+        }
+        None
+    }
+
+    pub fn clear(&mut self) {
+        self.free.clear();
+    }
+
+    /// How many parked entities are waiting to be reused
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+}
+
+/// Empties every pool, for when a death or restart has just swept the parked entities away
+pub fn clear_pools(
+    mut lasers: ResMut<Pool<LaserShot>>,
+    mut shockwaves: ResMut<Pool<Shockwave>>,
+    mut trails: ResMut<Pool<CometTrail>>,
+) {
+    lasers.clear();
+    shockwaves.clear();
+    trails.clear();
+}
+
+/// Parks an entity instead of despawning it. It is hidden, stopped, and ignored by physics
+/// until a spawner pops it back out of its pool.
+pub fn release(cmds: &mut Commands, entity: Entity) {
+    cmds.entity(entity).try_insert((
+        Disabled,
+        Visibility::Hidden,
+        Velocity {
+            linear: Vec2::ZERO,
+            linear_drag: Vec2::ZERO,
+            angular: 0.0,
+            angular_drag: 0.0,
+        },
+    ));
+}
+
+/// Moves freshly released entities onto the free list. Going through `Added` means an entity
+/// released twice in one frame still only lands in the pool once.
+pub fn recycle<T: Component>(
+    released: Query<Entity, (With<T>, Added<Disabled>)>,
+    mut pool: ResMut<Pool<T>>,
+) {
+    pool.free.extend(released.iter());
+}
@@ -0,0 +1,325 @@
+use std::{f32::consts::PI, time::Duration};
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Serialize;
+
+use crate::{
+    GameAssets, GameCleanup, HudRoot, PlayerShip,
+    effects::{Lifetime, PulseScale},
+    events::PowerUpCollected,
+    palette::Palette,
+    physics::{CircleCollider, CollisionEvent, MaxSpeed, SpeedBoost, TimeScale, Velocity},
+    shadows::AddShadow,
+};
+
+pub fn powerups_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            collect_powerups,
+            tick_time_dilation,
+            tick_speed_boost,
+            update_speedometer,
+        )
+            .chain(),
+    );
+}
+
+/// How much slower asteroids move while time dilation is active
+pub const TIME_DILATION_SCALE: f32 = 0.4;
+pub const TIME_DILATION_SECS: f32 = 5.0;
+/// Chance that a destroyed asteroid leaves a power-up behind
+pub const POWERUP_DROP_CHANCE: f64 = 0.05;
+/// Seconds between power-ups appearing on their own
+pub const POWERUP_INTERVAL_SECS: f32 = 20.0;
+/// Uncollected power-ups vanish after this long
+pub const POWERUP_LIFETIME_SECS: f32 = 10.0;
+/// Timed power-ups never appear closer than this to the ship, so they can't be grabbed by accident
+pub const POWERUP_MIN_SHIP_DISTANCE: f32 = 200.0;
+pub const AMMO_PER_PICKUP: u32 = 3;
+pub const MAX_SECONDARY_AMMO: u32 = 9;
+/// Laser spread after picking up [`PowerUpKind::Spread`], in radians
+pub const SPREAD_POWERUP_ANGLE: f32 = PI / 30.0;
+pub const SPEED_BOOST_MULTIPLIER: f32 = 2.0;
+pub const SPEED_BOOST_SECS: f32 = 5.0;
+/// Critical hit chance added by [`PowerUpKind::Critical`], it doesn't stack
+pub const CRIT_POWERUP_BONUS: f64 = 0.15;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerUpKind {
+    TimeDilation,
+    /// Refills secondary weapon ammo
+    Ammo,
+    /// Loosens the gun so shots scatter a little around the heading
+    Spread,
+    /// Doubles how fast the ship moves for a few seconds
+    SpeedBoost,
+    /// Raises the critical hit chance for the rest of the ship's life
+    Critical,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 5] = [
+        PowerUpKind::TimeDilation,
+        PowerUpKind::Ammo,
+        PowerUpKind::Spread,
+        PowerUpKind::SpeedBoost,
+        PowerUpKind::Critical,
+    ];
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self::ALL[rng.random_range(0..Self::ALL.len())]
+    }
+
+    pub fn sprite_path(self) -> &'static str {
+        match self {
+            PowerUpKind::TimeDilation => "kenney-space/PNG/Power-ups/powerupBlue_star.png",
+            PowerUpKind::Ammo => "kenney-space/PNG/Power-ups/bolt_gold.png",
+            PowerUpKind::Spread => "kenney-space/PNG/Power-ups/powerupRed_star.png",
+            PowerUpKind::SpeedBoost => "kenney-space/PNG/Power-ups/powerupGreen_bolt.png",
+            PowerUpKind::Critical => "kenney-space/PNG/Power-ups/powerupYellow_star.png",
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct PowerUp(pub PowerUpKind);
+
+/// Lives on the desaturation overlay, the effect ends when this entity goes away
+#[derive(Component)]
+pub struct TimeDilationEffect {
+    pub timer: Timer,
+}
+
+/// Opts an entity into being slowed by a [`TimeDilationEffect`]. Every hazard gets one when it
+/// spawns: asteroids, which covers the boss and its shards, and comets. The ship and its lasers
+/// never do.
+#[derive(Component)]
+pub struct Dilatable;
+
+/// HUD bar shown while the ship is boosted, despawned along with the boost
+#[derive(Component)]
+pub struct Speedometer;
+
+/// The filled part of the [`Speedometer`], full when the ship is at its [`MaxSpeed`]
+#[derive(Component)]
+pub struct SpeedometerFill;
+
+pub fn spawn_powerup(
+    In((kind, location)): In<(PowerUpKind, Vec2)>,
+    assets: Res<GameAssets>,
+    mut cmds: Commands,
+) {
+    let mut sprite = Sprite::from_image(assets.powerups[kind as usize].clone());
+    let size = 30.0;
+    sprite.custom_size = Some(Vec2::splat(size));
+
+    cmds.spawn((
+        PowerUp(kind),
+        GameCleanup,
+        sprite,
+        AddShadow,
+        Transform::from_xyz(location.x, location.y, 0.0),
+        CircleCollider { radius: size },
+        Velocity {
+            angular: 1.5,
+            angular_drag: 0.0,
+            ..default()
+        },
+        PulseScale {
+            min: 0.9,
+            max: 1.1,
+            hz: 2.0,
+        },
+        Lifetime {
+            secs: POWERUP_LIFETIME_SECS,
+        },
+    ));
+}
+
+/// Picks a spot inside `half_extents` that is at least [`POWERUP_MIN_SHIP_DISTANCE`] away
+/// from the ship
+pub fn random_powerup_position(rng: &mut impl Rng, half_extents: Vec2, ship_pos: Vec2) -> Vec2 {
+    for _ in 0..20 {
+        let pos = Vec2::new(
+            rng.random_range(-half_extents.x..half_extents.x),
+            rng.random_range(-half_extents.y..half_extents.y),
+        );
+        if pos.distance(ship_pos) >= POWERUP_MIN_SHIP_DISTANCE {
+            return pos;
+        }
+    }
+
+    // Nowhere far enough in a tiny window, so push it out from the ship instead
+    let away = -ship_pos.try_normalize().unwrap_or(Vec2::Y);
+    ship_pos + away * POWERUP_MIN_SHIP_DISTANCE
+}
+
+pub fn collect_powerups(
+    mut collisions: MessageReader<CollisionEvent>,
+    powerups: Query<&PowerUp>,
+    ship: Single<(Entity, &mut PlayerShip, Option<&mut SpeedBoost>)>,
+    mut dilation: Query<&mut TimeDilationEffect>,
+    palette: Res<Palette>,
+    hud: Single<Entity, With<HudRoot>>,
+    mut collected: MessageWriter<PowerUpCollected>,
+    mut cmds: Commands,
+) {
+    let (ship, mut player, mut boost) = ship.into_inner();
+
+    for collision in collisions.read() {
+        let powerup = if collision.0 == ship {
+            collision.1
+        } else if collision.1 == ship {
+            collision.0
+        } else {
+            continue;
+        };
+
+        let Ok(PowerUp(kind)) = powerups.get(powerup) else {
+            continue;
+        };
+        cmds.entity(powerup).try_despawn();
+        collected.write(PowerUpCollected { kind: *kind });
+
+        match kind {
+            PowerUpKind::Spread => {
+                player.spread_angle = SPREAD_POWERUP_ANGLE;
+            }
+            PowerUpKind::Critical => {
+                player.crit_bonus = CRIT_POWERUP_BONUS;
+            }
+            PowerUpKind::Ammo => {
+                player.secondary_ammo =
+                    (player.secondary_ammo + AMMO_PER_PICKUP).min(MAX_SECONDARY_AMMO);
+            }
+            PowerUpKind::SpeedBoost => {
+                if let Some(boost) = boost.as_mut() {
+                    boost.timer.reset();
+                    continue;
+                }
+
+                cmds.entity(ship).insert(SpeedBoost {
+                    multiplier: SPEED_BOOST_MULTIPLIER,
+                    timer: Timer::from_seconds(SPEED_BOOST_SECS, TimerMode::Once),
+                });
+                cmds.spawn((
+                    Speedometer,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        bottom: px(12),
+                        left: px(12),
+                        width: px(150),
+                        height: px(10),
+                        ..default()
+                    },
+                    BackgroundColor(palette.speedometer.with_alpha(0.2)),
+                    GameCleanup,
+                    ChildOf(*hud),
+                    children![(
+                        SpeedometerFill,
+                        Node {
+                            width: percent(0),
+                            height: percent(100),
+                            ..default()
+                        },
+                        BackgroundColor(palette.speedometer),
+                    )],
+                ));
+            }
+            PowerUpKind::TimeDilation => {
+                // Picking up a second one while active just restarts the clock
+                if let Ok(mut effect) = dilation.single_mut() {
+                    effect.timer.reset();
+                    continue;
+                }
+
+                cmds.spawn((
+                    TimeDilationEffect {
+                        timer: Timer::new(
+                            Duration::from_secs_f32(TIME_DILATION_SECS),
+                            TimerMode::Once,
+                        ),
+                    },
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: percent(100),
+                        height: percent(100),
+                        ..default()
+                    },
+                    BackgroundColor(palette.dilation_overlay),
+                    GlobalZIndex(-1),
+                    GameCleanup,
+                    ChildOf(*hud),
+                ));
+            }
+        }
+    }
+}
+
+/// Slows everything [`Dilatable`] while a [`TimeDilationEffect`] is alive. The ship and its
+/// lasers never get a [`TimeScale`], so they keep moving and firing at full speed.
+pub fn tick_time_dilation(
+    time: Res<Time>,
+    effect: Option<Single<(Entity, &mut TimeDilationEffect)>>,
+    slowed: Query<Entity, (With<Dilatable>, With<TimeScale>)>,
+    unslowed: Query<Entity, (With<Dilatable>, Without<TimeScale>)>,
+    mut cmds: Commands,
+) {
+    let Some(effect) = effect else {
+        return;
+    };
+    let (effect_ent, mut effect) = effect.into_inner();
+
+    effect.timer.tick(time.delta());
+
+    if effect.timer.is_finished() {
+        for ent in slowed {
+            cmds.entity(ent).try_remove::<TimeScale>();
+        }
+        cmds.entity(effect_ent).try_despawn();
+        return;
+    }
+
+    // Catches hazards that spawned after the pickup as well
+    for ent in unslowed {
+        cmds.entity(ent).try_insert(TimeScale(TIME_DILATION_SCALE));
+    }
+}
+
+pub fn tick_speed_boost(
+    time: Res<Time>,
+    mut boosted: Query<(Entity, &mut SpeedBoost)>,
+    speedometers: Query<Entity, With<Speedometer>>,
+    mut cmds: Commands,
+) {
+    for (ent, mut boost) in boosted.iter_mut() {
+        boost.timer.tick(time.delta());
+
+        if boost.timer.is_finished() {
+            cmds.entity(ent).remove::<SpeedBoost>();
+            for speedometer in speedometers {
+                cmds.entity(speedometer).try_despawn();
+            }
+        }
+    }
+}
+
+/// Fills the speedometer with the ship's boosted speed. The ship's [`MaxSpeed`] is twice what
+/// thrust alone can reach, so the bar only tops out when the boost is doing its job.
+pub fn update_speedometer(
+    ship: Option<Single<(&Velocity, &SpeedBoost, &MaxSpeed), With<PlayerShip>>>,
+    mut fill: Query<&mut Node, With<SpeedometerFill>>,
+) {
+    let Some(ship) = ship else {
+        return;
+    };
+    let (vel, boost, max_speed) = ship.into_inner();
+
+    let speed = (vel.linear.length() * boost.multiplier).min(max_speed.0);
+    for mut node in fill.iter_mut() {
+        node.width = percent(100.0 * speed / max_speed.0);
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What the game remembers about the player between runs, apart from their [`Settings`]
+///
+/// [`Settings`]: crate::settings::Settings
+pub fn profile_plugin(app: &mut App) {
+    app.insert_resource(PlayerProfile::load());
+
+    app.add_systems(
+        Update,
+        save_profile
+            .run_if(resource_changed::<PlayerProfile>.and(not(resource_added::<PlayerProfile>))),
+    );
+}
+
+/// Where the profile is kept between runs
+pub const PROFILE_PATH: &str = "profile.ron";
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PlayerProfile {
+    /// Nobody has finished or skipped the tutorial yet
+    pub first_run: bool,
+}
+
+impl Default for PlayerProfile {
+    fn default() -> Self {
+        Self { first_run: true }
+    }
+}
+
+impl PlayerProfile {
+    /// Reads [`PROFILE_PATH`], a missing file means a new player
+    pub fn load() -> Self {
+        let Ok(contents) = std::fs::read_to_string(PROFILE_PATH) else {
+            return Self::default();
+        };
+
+        match ron::from_str(&contents) {
+            Ok(profile) => profile,
+            Err(err) => {
+                error!("Could not parse {PROFILE_PATH}, starting a new profile: {err}");
+                Self::default()
+            }
+        }
+    }
+}
+
+pub fn save_profile(profile: Res<PlayerProfile>) {
+    let contents = match ron::ser::to_string_pretty(&*profile, default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            error!("Could not serialize the player profile: {err}");
+            return;
+        }
+    };
+
+    if let Err(err) = std::fs::write(PROFILE_PATH, contents) {
+        error!("Could not write {PROFILE_PATH}: {err}");
+    }
+}
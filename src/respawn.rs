@@ -0,0 +1,131 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    Asteroid, PlayerShip, SPAWN_INVINCIBILITY_SECS, effects::Invincible, physics::CircleCollider,
+    playfield::PlayBounds,
+};
+
+/// Keeps a fresh ship from appearing on top of an asteroid
+pub fn respawn_plugin(app: &mut App) {
+    app.add_systems(Update, (place_new_ships, retry_respawns).chain());
+}
+
+/// Nothing may be inside this circle around a spot for the ship to appear there
+pub const RESPAWN_SAFE_RADIUS: f32 = 150.0;
+/// Gap between the rings of spots tried when the center is taken
+pub const RESPAWN_RING_STEP: f32 = 100.0;
+/// The first ring tries this many spots, each ring after adds as many again
+pub const RESPAWN_RING_SPOTS: usize = 8;
+/// How long to wait before looking again when the whole play area is taken
+pub const RESPAWN_RETRY_SECS: f32 = 0.5;
+/// Outlasts any wait, the normal spawn invincibility replaces it once the ship is placed
+const WAITING_INVINCIBILITY_SECS: f32 = 1_000_000.0;
+
+/// A ship that found nowhere safe to appear. It's hidden, invincible and can't be flown until
+/// [`retry_respawns`] places it.
+#[derive(Component)]
+pub struct PendingRespawn {
+    pub retry: Timer,
+}
+
+/// The center if it's clear, otherwise the closest clear spot on rings around it, or `None` if
+/// every spot inside `half_extents` is taken
+pub fn find_respawn_position(
+    blockers: &[(Vec2, CircleCollider)],
+    half_extents: Vec2,
+) -> Option<Vec2> {
+    let safe = CircleCollider {
+        radius: RESPAWN_SAFE_RADIUS,
+    };
+    let is_clear = |spot: Vec2| {
+        blockers
+            .iter()
+            .all(|(pos, collider)| !safe.overlaps(spot, *collider, *pos))
+    };
+
+    if is_clear(Vec2::ZERO) {
+        return Some(Vec2::ZERO);
+    }
+
+    let max_distance = half_extents.length();
+    (1..)
+        .map(|ring| ring as f32 * RESPAWN_RING_STEP)
+        .take_while(|distance| *distance <= max_distance)
+        .enumerate()
+        .flat_map(|(ring, distance)| {
+            let spots = RESPAWN_RING_SPOTS * (ring + 1);
+            (0..spots).map(move |i| Vec2::from_angle(i as f32 / spots as f32 * TAU) * distance)
+        })
+        .filter(|spot| spot.abs().cmple(half_extents).all())
+        .find(|spot| is_clear(*spot))
+}
+
+type Blockers<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static CircleCollider),
+    (With<Asteroid>, Without<PlayerShip>),
+>;
+
+fn blockers(asteroids: &Blockers) -> Vec<(Vec2, CircleCollider)> {
+    asteroids
+        .iter()
+        .map(|(tsf, collider)| (tsf.translation.xy(), *collider))
+        .collect()
+}
+
+/// Moves every newly spawned ship somewhere safe, or holds it back if there's nowhere yet
+pub fn place_new_ships(
+    mut ships: Query<(Entity, &mut Transform, &mut Visibility), Added<PlayerShip>>,
+    asteroids: Blockers,
+    bounds: Res<PlayBounds>,
+    mut cmds: Commands,
+) {
+    let blockers = blockers(&asteroids);
+
+    for (ship, mut tsf, mut visibility) in ships.iter_mut() {
+        match find_respawn_position(&blockers, bounds.half_extents) {
+            Some(spot) => tsf.translation = spot.extend(tsf.translation.z),
+            None => {
+                *visibility = Visibility::Hidden;
+                cmds.entity(ship).insert((
+                    PendingRespawn {
+                        retry: Timer::from_seconds(RESPAWN_RETRY_SECS, TimerMode::Repeating),
+                    },
+                    // Can't be hit while it's waiting, wherever the asteroids drift
+                    Invincible::new(WAITING_INVINCIBILITY_SECS),
+                ));
+            }
+        }
+    }
+}
+
+pub fn retry_respawns(
+    mut ships: Query<
+        (Entity, &mut PendingRespawn, &mut Transform, &mut Visibility),
+        With<PlayerShip>,
+    >,
+    asteroids: Blockers,
+    bounds: Res<PlayBounds>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    for (ship, mut pending, mut tsf, mut visibility) in ships.iter_mut() {
+        pending.retry.tick(time.delta());
+        if !pending.retry.just_finished() {
+            continue;
+        }
+
+        let Some(spot) = find_respawn_position(&blockers(&asteroids), bounds.half_extents) else {
+            continue;
+        };
+
+        tsf.translation = spot.extend(tsf.translation.z);
+        *visibility = Visibility::Inherited;
+        cmds.entity(ship)
+            .remove::<PendingRespawn>()
+            .insert(Invincible::new(SPAWN_INVINCIBILITY_SECS));
+    }
+}
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use bevy::{
+    input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest},
+    prelude::*,
+};
+
+use crate::{
+    events::{AsteroidDestroyed, PlayerDied},
+    settings::Settings,
+};
+
+/// Force feedback for gamepads. Gameplay moments are turned into [`RumbleEvent`]s, and only
+/// [`play_rumble`] talks to the gamepads.
+pub fn rumble_plugin(app: &mut App) {
+    app.add_message::<RumbleEvent>();
+    app.init_resource::<RumbleMix>();
+
+    app.add_systems(Update, (rumble_on_gameplay, play_rumble).chain());
+}
+
+/// Asks every connected gamepad to rumble
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct RumbleEvent {
+    /// From 0 to 1, drives both motors
+    pub intensity: f32,
+    pub duration: Duration,
+}
+
+/// Short tick for every asteroid destroyed
+pub const DESTROYED_RUMBLE: RumbleEvent = RumbleEvent {
+    intensity: 0.25,
+    duration: Duration::from_millis(120),
+};
+/// Long, full strength shake when the ship is lost
+pub const DEATH_RUMBLE: RumbleEvent = RumbleEvent {
+    intensity: 1.0,
+    duration: Duration::from_millis(900),
+};
+
+/// Rumbles that haven't run out yet. Overlapping ones don't add up or cut each other off, the
+/// strongest plays until it ends and then the next strongest carries on.
+#[derive(Resource, Default, Debug)]
+pub struct RumbleMix {
+    /// Intensity and the real time in seconds it runs until
+    requests: Vec<(f32, f32)>,
+    /// What the gamepads were last told to play
+    playing: Option<(f32, f32)>,
+}
+
+impl RumbleMix {
+    pub fn add(&mut self, rumble: RumbleEvent, now: f32) {
+        self.requests
+            .push((rumble.intensity, now + rumble.duration.as_secs_f32()));
+    }
+
+    /// The strongest rumble still running at `now` and when it ends, dropping the ones that
+    /// have finished
+    pub fn strongest(&mut self, now: f32) -> Option<(f32, f32)> {
+        self.requests.retain(|(_, until)| *until > now);
+
+        let intensity = self
+            .requests
+            .iter()
+            .map(|(intensity, _)| *intensity)
+            .max_by(f32::total_cmp)?;
+        let until = self
+            .requests
+            .iter()
+            .filter(|(other, _)| *other == intensity)
+            .map(|(_, until)| *until)
+            .max_by(f32::total_cmp)?;
+        Some((intensity, until))
+    }
+}
+
+pub fn rumble_on_gameplay(
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    mut deaths: MessageReader<PlayerDied>,
+    mut rumble: MessageWriter<RumbleEvent>,
+) {
+    // A blast taking out a dozen asteroids is still one tick
+    if destroyed.read().count() > 0 {
+        rumble.write(DESTROYED_RUMBLE);
+    }
+    if deaths.read().count() > 0 {
+        rumble.write(DEATH_RUMBLE);
+    }
+}
+
+/// Plays the strongest running rumble on every gamepad, only sending requests when that changes
+pub fn play_rumble(
+    mut events: MessageReader<RumbleEvent>,
+    mut mix: ResMut<RumbleMix>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut requests: MessageWriter<GamepadRumbleRequest>,
+) {
+    let now = time.elapsed_secs();
+    if settings.rumble && !gamepads.is_empty() {
+        for event in events.read() {
+            mix.add(*event, now);
+        }
+    } else {
+        events.clear();
+        mix.requests.clear();
+    }
+
+    let strongest = mix.strongest(now);
+    if strongest == mix.playing {
+        return;
+    }
+    mix.playing = strongest;
+
+    for gamepad in gamepads {
+        requests.write(GamepadRumbleRequest::Stop { gamepad });
+        if let Some((intensity, until)) = strongest {
+            requests.write(GamepadRumbleRequest::Add {
+                duration: Duration::from_secs_f32(until - now),
+                intensity: GamepadRumbleIntensity {
+                    strong_motor: intensity,
+                    weak_motor: intensity,
+                },
+                gamepad,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strongest_rumble_wins_then_hands_back() {
+        let mut mix = RumbleMix::default();
+        mix.add(
+            RumbleEvent {
+                intensity: 0.3,
+                duration: Duration::from_secs(2),
+            },
+            0.0,
+        );
+        mix.add(DEATH_RUMBLE, 0.5);
+
+        let (intensity, until) = mix.strongest(0.6).unwrap();
+        assert_eq!(intensity, 1.0);
+        assert!((until - 1.4).abs() < 1e-5);
+        // The death shake is over, the weaker one carries on until its own end
+        assert_eq!(mix.strongest(1.5), Some((0.3, 2.0)));
+        assert_eq!(mix.strongest(2.5), None);
+    }
+}
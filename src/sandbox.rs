@@ -0,0 +1,121 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    AsteroidConfig, AsteroidSize, GameMode, HudRoot, MainCamera, PlayerShip, locale::Locale,
+    palette::Palette, physics::Velocity, spawn_asteroid,
+};
+
+/// Practice mode: nothing spawns on its own, asteroids are dragged out with the mouse and
+/// running into one bounces the ship off instead of ending the run
+pub fn sandbox_plugin(app: &mut App) {
+    app.add_systems(
+        Startup,
+        spawn_sandbox_readout.run_if(resource_equals(GameMode::Sandbox)),
+    );
+    app.add_systems(
+        Update,
+        (drag_spawn_asteroids, update_sandbox_readout).run_if(resource_equals(GameMode::Sandbox)),
+    );
+}
+
+/// Asteroid speed per unit of mouse drag
+pub const DRAG_SPEED_SCALE: f32 = 1.0;
+pub const MAX_DRAG_SPEED: f32 = 400.0;
+/// How fast the ship is knocked away from an asteroid it runs into
+pub const SANDBOX_BOUNCE_SPEED: f32 = 200.0;
+
+/// Shows the ship's speed and position
+#[derive(Component)]
+pub struct SandboxReadout;
+
+pub fn spawn_sandbox_readout(
+    mut cmds: Commands,
+    palette: Res<Palette>,
+    hud: Single<Entity, With<HudRoot>>,
+) {
+    cmds.spawn((
+        SandboxReadout,
+        Text::default(),
+        TextColor(palette.hud_text),
+        palette.hud_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(12),
+            right: px(12),
+            ..default()
+        },
+        ChildOf(*hud),
+    ));
+}
+
+/// Left mouse button down picks where the asteroid goes, releasing it sets the heading and
+/// speed from how far the mouse was dragged
+pub fn drag_spawn_asteroids(
+    buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    palette: Res<Palette>,
+    mut drag_start: Local<Option<Vec2>>,
+    mut gizmos: Gizmos,
+    mut cmds: Commands,
+) {
+    let (camera, camera_tsf) = camera.into_inner();
+    let cursor = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_tsf, cursor).ok());
+
+    if buttons.just_pressed(MouseButton::Left) {
+        *drag_start = cursor;
+    }
+
+    let (Some(start), Some(end)) = (*drag_start, cursor) else {
+        return;
+    };
+
+    gizmos.arrow_2d(start, end, palette.highlight);
+
+    if !buttons.just_released(MouseButton::Left) {
+        return;
+    }
+    *drag_start = None;
+
+    let drag = end - start;
+    let config = AsteroidConfig {
+        location: start,
+        heading: f32::atan2(-drag.x, drag.y),
+        speed: (drag.length() * DRAG_SPEED_SCALE).min(MAX_DRAG_SPEED),
+        angvel: 0.0,
+        size: AsteroidSize::Large,
+        group: None,
+    };
+
+    // Printed as Rust so a repro can be pasted straight into a call to `spawn_asteroid`
+    info!(
+        "AsteroidConfig {{ location: Vec2::new({:?}, {:?}), heading: {:?}, speed: {:?}, \
+        angvel: {:?}, size: AsteroidSize::{:?}, group: None }}",
+        config.location.x,
+        config.location.y,
+        config.heading,
+        config.speed,
+        config.angvel,
+        config.size
+    );
+    cmds.run_system_cached_with(spawn_asteroid, config);
+}
+
+pub fn update_sandbox_readout(
+    ship: Single<(&Transform, &Velocity), With<PlayerShip>>,
+    mut readout: Single<&mut Text, With<SandboxReadout>>,
+    locale: Res<Locale>,
+) {
+    let (tsf, vel) = ship.into_inner();
+    let number = |value: f32| locale.number(value.round() as i64);
+    readout.0 = locale.text_with(
+        "hud.sandbox_readout",
+        &[
+            &number(vel.linear.length()),
+            &number(tsf.translation.x),
+            &number(tsf.translation.y),
+        ],
+    );
+}
@@ -0,0 +1,521 @@
+use bevy::{
+    prelude::*,
+    ui::{FocusPolicy, RelativeCursorPosition},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controls::{ControlsScreen, InputBindings},
+    locale::{Locale, LocalizedText},
+};
+
+pub fn settings_plugin(app: &mut App) {
+    app.insert_resource(Settings::load());
+    app.init_resource::<SettingsScreen>();
+
+    app.add_systems(Startup, spawn_settings_ui);
+    app.add_systems(
+        Update,
+        (
+            toggle_settings_hotkeys,
+            drag_settings_sliders,
+            click_settings_buttons,
+            update_settings_ui.run_if(
+                resource_changed::<SettingsScreen>
+                    .or(resource_changed::<Settings>)
+                    .or(resource_changed::<Locale>),
+            ),
+            save_settings.run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+        )
+            .chain(),
+    );
+}
+
+/// Where settings are kept between runs
+pub const SETTINGS_PATH: &str = "settings.ron";
+
+/// Which set of colors the [`Palette`](crate::palette::Palette) is built from
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    /// Safe for deuteranopia and protanopia
+    ColorBlind,
+}
+
+/// Which [`Locale`](crate::locale::Locale) the game's text comes from
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Name of the language's file in [`LOCALE_DIR`](crate::locale::LOCALE_DIR)
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+}
+
+/// Whether frames wait for the display before being shown
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SyncMode {
+    #[default]
+    Vsync,
+    /// Presents as soon as a frame is ready, may tear
+    Uncapped,
+}
+
+/// How the ship is turned
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ControlMode {
+    /// Rotate with the bound keys
+    #[default]
+    Keyboard,
+    /// The ship turns to face the mouse cursor, the rotate keys are ignored
+    MouseAim,
+}
+
+/// A slider setting from 0 to 100
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(transparent)]
+pub struct Percent(pub u8);
+
+impl Percent {
+    pub const MAX: u8 = 100;
+}
+
+impl Default for Percent {
+    fn default() -> Self {
+        Self(80)
+    }
+}
+
+/// Frame rate caps F3 cycles through, `None` is no cap
+pub const FPS_LIMITS: [Option<u32>; 3] = [None, Some(60), Some(120)];
+
+/// Player facing options
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Settings {
+    /// Draw the ship's travel direction and facing around it
+    pub show_velocity_indicator: bool,
+    pub palette: ColorPalette,
+    /// Outlines HUD text and darkens the background
+    pub high_contrast: bool,
+    pub show_fps: bool,
+    pub sync: SyncMode,
+    /// Frames per second the game is held to on top of the sync mode, `None` runs as fast as it
+    /// can
+    pub fps_limit: Option<u32>,
+    pub bindings: InputBindings,
+    pub language: Language,
+    pub control_mode: ControlMode,
+    pub music_volume: Percent,
+    pub sfx_volume: Percent,
+    /// Glow around lasers and blasts, see [`bloom`](crate::bloom). 0 turns HDR off entirely.
+    pub bloom: Percent,
+    /// Outline every collider, for debugging
+    pub show_colliders: bool,
+    /// Soft shadows under gameplay sprites, see [`shadows`](crate::shadows)
+    pub drop_shadows: bool,
+    /// Gamepad force feedback, see [`rumble`](crate::rumble)
+    pub rumble: bool,
+    /// Open the pause menu when the window loses focus
+    pub pause_on_focus_loss: bool,
+    /// Freeze the game for a moment when the ship survives a hit, see [`impact`](crate::impact)
+    pub hit_stop: bool,
+    /// Flash the screen edges red when the ship survives a hit
+    pub damage_flash: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_velocity_indicator: false,
+            palette: default(),
+            high_contrast: false,
+            show_fps: false,
+            sync: default(),
+            fps_limit: None,
+            bindings: default(),
+            language: default(),
+            control_mode: default(),
+            music_volume: default(),
+            sfx_volume: default(),
+            bloom: Percent(30),
+            show_colliders: false,
+            drop_shadows: true,
+            rumble: true,
+            pause_on_focus_loss: true,
+            hit_stop: true,
+            damage_flash: true,
+        }
+    }
+}
+
+impl Settings {
+    /// Reads [`SETTINGS_PATH`], falling back to defaults if there isn't one yet
+    pub fn load() -> Self {
+        let Ok(contents) = std::fs::read_to_string(SETTINGS_PATH) else {
+            return Self::default();
+        };
+
+        match ron::from_str(&contents) {
+            Ok(settings) => settings,
+            Err(err) => {
+                error!("Could not parse {SETTINGS_PATH}, using default settings: {err}");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Keyboard shortcuts for flipping settings mid-game
+pub fn toggle_settings_hotkeys(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<Settings>,
+) {
+    if btn_input.just_pressed(KeyCode::KeyV) {
+        settings.show_velocity_indicator = !settings.show_velocity_indicator;
+    }
+
+    if btn_input.just_pressed(KeyCode::KeyC) {
+        settings.palette = match settings.palette {
+            ColorPalette::Standard => ColorPalette::ColorBlind,
+            ColorPalette::ColorBlind => ColorPalette::Standard,
+        };
+    }
+
+    if btn_input.just_pressed(KeyCode::KeyH) {
+        settings.high_contrast = !settings.high_contrast;
+    }
+
+    if btn_input.just_pressed(KeyCode::KeyL) {
+        let current = Language::ALL
+            .iter()
+            .position(|language| *language == settings.language)
+            .unwrap_or(0);
+        settings.language = Language::ALL[(current + 1) % Language::ALL.len()];
+    }
+
+    if btn_input.just_pressed(KeyCode::F1) {
+        settings.show_fps = !settings.show_fps;
+    }
+
+    if btn_input.just_pressed(KeyCode::F2) {
+        settings.sync = match settings.sync {
+            SyncMode::Vsync => SyncMode::Uncapped,
+            SyncMode::Uncapped => SyncMode::Vsync,
+        };
+    }
+
+    if btn_input.just_pressed(KeyCode::F3) {
+        let current = FPS_LIMITS
+            .iter()
+            .position(|limit| *limit == settings.fps_limit)
+            .unwrap_or(0);
+        settings.fps_limit = FPS_LIMITS[(current + 1) % FPS_LIMITS.len()];
+    }
+}
+
+pub fn save_settings(settings: Res<Settings>) {
+    let contents = match ron::ser::to_string_pretty(&*settings, default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            error!("Could not serialize settings: {err}");
+            return;
+        }
+    };
+
+    if let Err(err) = std::fs::write(SETTINGS_PATH, contents) {
+        error!("Could not write {SETTINGS_PATH}: {err}");
+    }
+}
+
+/// Settings screen, opened from the pause menu
+#[derive(Resource, Default)]
+pub struct SettingsScreen {
+    pub open: bool,
+}
+
+#[derive(Component)]
+pub struct SettingsRoot;
+
+/// One row of the settings screen. Volumes and bloom are sliders, the rest are buttons.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingsItem {
+    MusicVolume,
+    SfxVolume,
+    Bloom,
+    ControlMode,
+    ColorBlind,
+    ShowColliders,
+    DropShadows,
+    Rumble,
+    PauseOnFocusLoss,
+    HitStop,
+    DamageFlash,
+    /// Opens the controls screen in place of this one
+    Rebind,
+    Back,
+}
+
+/// Text above a slider
+#[derive(Component)]
+pub struct SliderLabel(pub SettingsItem);
+
+/// The handle of a slider, moved along its bar to show the value
+#[derive(Component)]
+pub struct SliderThumb;
+
+const SLIDER_WIDTH: f32 = 240.0;
+const THUMB_WIDTH: f32 = 12.0;
+
+impl Settings {
+    fn slider_mut(&mut self, item: SettingsItem) -> Option<&mut Percent> {
+        match item {
+            SettingsItem::MusicVolume => Some(&mut self.music_volume),
+            SettingsItem::SfxVolume => Some(&mut self.sfx_volume),
+            SettingsItem::Bloom => Some(&mut self.bloom),
+            _ => None,
+        }
+    }
+}
+
+pub fn spawn_settings_ui(locale: Res<Locale>, mut cmds: Commands) {
+    let button_node = Node {
+        padding: UiRect::axes(px(12), px(4)),
+        ..default()
+    };
+
+    cmds.spawn((
+        SettingsRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(20),
+            left: percent(30),
+            width: percent(40),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(6),
+            padding: UiRect::all(px(16)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.9)),
+        // Keeps clicks off the pause menu underneath
+        FocusPolicy::Block,
+        GlobalZIndex(9),
+    ))
+    .with_children(|root| {
+        root.spawn((
+            LocalizedText("settings.title"),
+            Text::new(locale.text("settings.title")),
+        ));
+
+        for slider in [
+            SettingsItem::MusicVolume,
+            SettingsItem::SfxVolume,
+            SettingsItem::Bloom,
+        ] {
+            root.spawn((SliderLabel(slider), Text::default()));
+            root.spawn((
+                slider,
+                Button,
+                RelativeCursorPosition::default(),
+                Node {
+                    width: px(SLIDER_WIDTH),
+                    height: px(12),
+                    ..default()
+                },
+                BackgroundColor(Color::WHITE.with_alpha(0.2)),
+                children![(
+                    SliderThumb,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: px(THUMB_WIDTH),
+                        height: percent(100),
+                        ..default()
+                    },
+                    BackgroundColor(Color::WHITE),
+                )],
+            ));
+        }
+
+        for button in [
+            SettingsItem::ControlMode,
+            SettingsItem::ColorBlind,
+            SettingsItem::ShowColliders,
+            SettingsItem::DropShadows,
+            SettingsItem::Rumble,
+            SettingsItem::PauseOnFocusLoss,
+            SettingsItem::HitStop,
+            SettingsItem::DamageFlash,
+            SettingsItem::Rebind,
+            SettingsItem::Back,
+        ] {
+            root.spawn((
+                button,
+                Button,
+                Text::default(),
+                button_node.clone(),
+                BackgroundColor(Color::NONE),
+            ));
+        }
+    });
+}
+
+/// Holding the mouse down on a slider's bar drags its thumb to the cursor
+pub fn drag_settings_sliders(
+    sliders: Query<(&SettingsItem, &Interaction, &RelativeCursorPosition)>,
+    screen: Res<SettingsScreen>,
+    mut settings: ResMut<Settings>,
+) {
+    if !screen.open {
+        return;
+    }
+
+    for (item, interaction, cursor) in sliders {
+        let (Interaction::Pressed, Some(cursor)) = (interaction, cursor.normalized) else {
+            continue;
+        };
+
+        // The cursor is measured from the middle of the bar
+        let value = ((cursor.x + 0.5).clamp(0.0, 1.0) * Percent::MAX as f32).round() as u8;
+        if let Some(slider) = settings.bypass_change_detection().slider_mut(*item)
+            && slider.0 != value
+        {
+            slider.0 = value;
+            settings.set_changed();
+        }
+    }
+}
+
+pub fn click_settings_buttons(
+    buttons: Query<(&Interaction, &SettingsItem), Changed<Interaction>>,
+    mut screen: ResMut<SettingsScreen>,
+    mut controls: ResMut<ControlsScreen>,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, item) in buttons {
+        if *interaction != Interaction::Pressed || !screen.open {
+            continue;
+        }
+
+        match item {
+            SettingsItem::MusicVolume | SettingsItem::SfxVolume | SettingsItem::Bloom => {}
+            SettingsItem::ControlMode => {
+                settings.control_mode = match settings.control_mode {
+                    ControlMode::Keyboard => ControlMode::MouseAim,
+                    ControlMode::MouseAim => ControlMode::Keyboard,
+                };
+            }
+            SettingsItem::ColorBlind => {
+                settings.palette = match settings.palette {
+                    ColorPalette::Standard => ColorPalette::ColorBlind,
+                    ColorPalette::ColorBlind => ColorPalette::Standard,
+                };
+            }
+            SettingsItem::ShowColliders => settings.show_colliders = !settings.show_colliders,
+            SettingsItem::DropShadows => settings.drop_shadows = !settings.drop_shadows,
+            SettingsItem::Rumble => settings.rumble = !settings.rumble,
+            SettingsItem::PauseOnFocusLoss => {
+                settings.pause_on_focus_loss = !settings.pause_on_focus_loss;
+            }
+            SettingsItem::HitStop => settings.hit_stop = !settings.hit_stop,
+            SettingsItem::DamageFlash => settings.damage_flash = !settings.damage_flash,
+            SettingsItem::Rebind => {
+                screen.open = false;
+                controls.open = true;
+            }
+            SettingsItem::Back => screen.open = false,
+        }
+    }
+}
+
+pub fn update_settings_ui(
+    screen: Res<SettingsScreen>,
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut root: Single<&mut Node, (With<SettingsRoot>, Without<SliderThumb>)>,
+    mut labels: Query<(&SliderLabel, &mut Text), Without<SettingsItem>>,
+    mut buttons: Query<(&SettingsItem, &mut Text)>,
+    sliders: Query<(&SettingsItem, &Children)>,
+    mut thumbs: Query<&mut Node, (With<SliderThumb>, Without<SettingsRoot>)>,
+) {
+    root.display = if screen.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    let slider = |item| match item {
+        SettingsItem::MusicVolume => settings.music_volume,
+        SettingsItem::SfxVolume => settings.sfx_volume,
+        _ => settings.bloom,
+    };
+    let on_off = |on| locale.text(if on { "settings.on" } else { "settings.off" });
+
+    for (label, mut text) in labels.iter_mut() {
+        let key = match label.0 {
+            SettingsItem::MusicVolume => "settings.music_volume",
+            SettingsItem::SfxVolume => "settings.sfx_volume",
+            _ => "settings.bloom",
+        };
+        text.0 = locale.text_with(key, &[&slider(label.0).0]);
+    }
+
+    for (item, mut text) in buttons.iter_mut() {
+        text.0 = match item {
+            SettingsItem::MusicVolume | SettingsItem::SfxVolume | SettingsItem::Bloom => continue,
+            SettingsItem::ControlMode => {
+                let mode = match settings.control_mode {
+                    ControlMode::Keyboard => "settings.control_mode.keyboard",
+                    ControlMode::MouseAim => "settings.control_mode.mouse_aim",
+                };
+                locale.text_with("settings.control_mode", &[&locale.text(mode)])
+            }
+            SettingsItem::ColorBlind => locale.text_with(
+                "settings.color_blind",
+                &[&on_off(settings.palette == ColorPalette::ColorBlind)],
+            ),
+            SettingsItem::ShowColliders => locale.text_with(
+                "settings.show_colliders",
+                &[&on_off(settings.show_colliders)],
+            ),
+            SettingsItem::DropShadows => {
+                locale.text_with("settings.drop_shadows", &[&on_off(settings.drop_shadows)])
+            }
+            SettingsItem::Rumble => {
+                locale.text_with("settings.rumble", &[&on_off(settings.rumble)])
+            }
+            SettingsItem::PauseOnFocusLoss => locale.text_with(
+                "settings.pause_on_focus_loss",
+                &[&on_off(settings.pause_on_focus_loss)],
+            ),
+            SettingsItem::HitStop => {
+                locale.text_with("settings.hit_stop", &[&on_off(settings.hit_stop)])
+            }
+            SettingsItem::DamageFlash => {
+                locale.text_with("settings.damage_flash", &[&on_off(settings.damage_flash)])
+            }
+            SettingsItem::Rebind => locale.text("settings.rebind").into(),
+            SettingsItem::Back => locale.text("settings.back").into(),
+        };
+    }
+
+    for (item, children) in sliders {
+        let fraction = slider(*item).0 as f32 / Percent::MAX as f32;
+        for child in children {
+            if let Ok(mut thumb) = thumbs.get_mut(*child) {
+                thumb.left = px((SLIDER_WIDTH - THUMB_WIDTH) * fraction);
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{
+    audio::{AudioPlayer, PlaybackSettings, SpatialListener, Volume},
+    ecs::system::SystemParam,
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    MainCamera,
+    asset_source::asset_path,
+    events::{AsteroidDestroyed, LaserHit, ShipHit, ShotFired},
+    playfield::view_half_extents,
+    settings::{Percent, Settings},
+};
+
+/// Sound effects for shots, hits, asteroids breaking up and the ship surviving a hit. Each one is
+/// panned toward where it happened on screen and gets quieter the further off screen that was.
+pub fn sfx_plugin(app: &mut App) {
+    app.add_systems(Startup, load_sfx);
+    app.add_systems(
+        Update,
+        (
+            add_sfx_listener,
+            (
+                play_shot_sfx,
+                play_hit_sfx,
+                play_ship_hit_sfx,
+                play_destroyed_sfx,
+            ),
+        )
+            .chain(),
+    );
+}
+
+/// Distance between the camera's ears, in world units. Emitters are placed between them, close
+/// enough that nothing gets quieter with distance and only the pan is heard.
+pub const SFX_EAR_GAP: f32 = 1.0;
+/// How many view widths off screen a sound takes to fade out completely
+pub const SFX_FALLOFF_VIEWS: f32 = 1.0;
+/// Anything quieter than this isn't played at all
+pub const SFX_MIN_VOLUME: f32 = 0.01;
+const SHOT_VOLUME: f32 = 0.4;
+const HIT_VOLUME: f32 = 0.5;
+const DESTROYED_VOLUME: f32 = 0.7;
+/// The ship taking a hit is the heaviest sound there is
+const SHIP_HIT_VOLUME: f32 = 1.0;
+
+#[derive(Resource)]
+pub struct SfxAssets {
+    pub shot: Handle<AudioSource>,
+    pub hit: Handle<AudioSource>,
+    pub destroyed: Handle<AudioSource>,
+    pub ship_hit: Handle<AudioSource>,
+}
+
+pub fn load_sfx(asset_server: Res<AssetServer>, mut cmds: Commands) {
+    cmds.insert_resource(SfxAssets {
+        shot: asset_server.load(asset_path("kenney-space/Bonus/sfx_laser1.ogg")),
+        hit: asset_server.load(asset_path("kenney-space/Bonus/sfx_zap.ogg")),
+        destroyed: asset_server.load(asset_path("kenney-space/Bonus/sfx_twoTone.ogg")),
+        ship_hit: asset_server.load(asset_path("kenney-space/Bonus/sfx_shieldDown.ogg")),
+    });
+}
+
+/// How a sound is mixed, from -1 all the way left to 1 all the way right
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SfxMix {
+    pub pan: f32,
+    pub volume: f32,
+}
+
+/// Mix for a sound `offset` from the middle of a view `half_view` across. Sounds on screen play
+/// at full `volume`, past the edge they fade out over [`SFX_FALLOFF_VIEWS`]. `None` if that
+/// leaves nothing to hear.
+pub fn sfx_mix(offset: Vec2, half_view: Vec2, volume: f32) -> Option<SfxMix> {
+    let half_view = half_view.max(Vec2::ONE);
+    let pan = (offset.x / half_view.x).clamp(-1.0, 1.0);
+    // How far past the nearest edge, in view widths
+    let outside = ((offset.abs() - half_view) / (half_view * 2.0))
+        .max(Vec2::ZERO)
+        .max_element();
+    let volume = volume * (1.0 - outside / SFX_FALLOFF_VIEWS).max(0.0);
+
+    (volume >= SFX_MIN_VOLUME).then_some(SfxMix { pan, volume })
+}
+
+fn percent_volume(percent: Percent) -> f32 {
+    percent.0 as f32 / Percent::MAX as f32
+}
+
+/// Plays sound effects at a position in the world
+#[derive(SystemParam)]
+pub struct Sfx<'w, 's> {
+    camera: Query<'w, 's, (&'static Transform, &'static Projection), With<MainCamera>>,
+    window: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    settings: Res<'w, Settings>,
+    cmds: Commands<'w, 's>,
+}
+
+impl Sfx<'_, '_> {
+    pub fn play(&mut self, sound: &Handle<AudioSource>, position: Vec2, volume: f32) {
+        let (Ok((camera, projection)), Ok(window)) = (self.camera.single(), self.window.single())
+        else {
+            return;
+        };
+        let volume = volume * percent_volume(self.settings.sfx_volume);
+        let offset = position - camera.translation.xy();
+        let Some(mix) = sfx_mix(offset, view_half_extents(window, projection), volume) else {
+            return;
+        };
+
+        let ear = Vec3::X * mix.pan * SFX_EAR_GAP / 2.0;
+        self.cmds.spawn((
+            AudioPlayer(sound.clone()),
+            PlaybackSettings::DESPAWN
+                .with_spatial(true)
+                .with_volume(Volume::Linear(mix.volume)),
+            Transform::from_translation(camera.translation + ear),
+        ));
+    }
+}
+
+/// The camera is respawned with every ship, it hears from the middle of the screen
+pub fn add_sfx_listener(cameras: Query<Entity, Added<MainCamera>>, mut cmds: Commands) {
+    for camera in cameras {
+        cmds.entity(camera)
+            .insert(SpatialListener::new(SFX_EAR_GAP));
+    }
+}
+
+pub fn play_shot_sfx(mut shots: MessageReader<ShotFired>, assets: Res<SfxAssets>, mut sfx: Sfx) {
+    for shot in shots.read() {
+        sfx.play(&assets.shot, shot.position, SHOT_VOLUME);
+    }
+}
+
+pub fn play_hit_sfx(mut hits: MessageReader<LaserHit>, assets: Res<SfxAssets>, mut sfx: Sfx) {
+    for hit in hits.read() {
+        sfx.play(&assets.hit, hit.position, HIT_VOLUME);
+    }
+}
+
+pub fn play_ship_hit_sfx(mut hits: MessageReader<ShipHit>, assets: Res<SfxAssets>, mut sfx: Sfx) {
+    for hit in hits.read() {
+        sfx.play(&assets.ship_hit, hit.position, SHIP_HIT_VOLUME);
+    }
+}
+
+pub fn play_destroyed_sfx(
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    assets: Res<SfxAssets>,
+    mut sfx: Sfx,
+) {
+    for asteroid in destroyed.read() {
+        sfx.play(&assets.destroyed, asteroid.position, DESTROYED_VOLUME);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_VIEW: Vec2 = Vec2::new(400.0, 300.0);
+
+    #[test]
+    fn pans_with_screen_position() {
+        let middle = sfx_mix(Vec2::ZERO, HALF_VIEW, 1.0).unwrap();
+        assert_eq!(
+            middle,
+            SfxMix {
+                pan: 0.0,
+                volume: 1.0
+            }
+        );
+
+        let left_edge = sfx_mix(Vec2::new(-400.0, 0.0), HALF_VIEW, 1.0).unwrap();
+        assert_eq!(
+            left_edge,
+            SfxMix {
+                pan: -1.0,
+                volume: 1.0
+            }
+        );
+
+        let halfway_right = sfx_mix(Vec2::new(200.0, 100.0), HALF_VIEW, 0.5).unwrap();
+        assert_eq!(
+            halfway_right,
+            SfxMix {
+                pan: 0.5,
+                volume: 0.5
+            }
+        );
+    }
+
+    #[test]
+    fn fades_out_off_screen() {
+        // Half a view width past the right edge, still panned hard right
+        let off_screen = sfx_mix(Vec2::new(800.0, 0.0), HALF_VIEW, 1.0).unwrap();
+        assert_eq!(off_screen.pan, 1.0);
+        assert!((off_screen.volume - 0.5).abs() < 1e-5);
+
+        assert_eq!(sfx_mix(Vec2::new(0.0, -1000.0), HALF_VIEW, 1.0), None);
+        assert_eq!(sfx_mix(Vec2::ZERO, HALF_VIEW, 0.0), None);
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+
+/// Soft drop shadows under gameplay sprites, so they stand out from the background. Turned off
+/// from the settings screen.
+pub fn shadows_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            remove_shadows.run_if(resource_changed::<Settings>),
+            (add_shadows, sync_shadow_sprites, orient_shadows)
+                .chain()
+                .run_if(|settings: Res<Settings>| settings.drop_shadows),
+        )
+            .chain(),
+    );
+}
+
+/// How far down and right of its caster a shadow falls, in world units
+pub const SHADOW_OFFSET: Vec2 = Vec2::new(4.0, -4.0);
+pub const SHADOW_ALPHA: f32 = 0.35;
+/// Shadows are drawn a little larger than their caster, which softens the edge
+pub const SHADOW_SCALE: f32 = 1.08;
+/// Just far enough below the caster to sort underneath it
+const SHADOW_DEPTH: f32 = 0.01;
+
+/// Gives a sprite a drop shadow, spawn sites only need to add this
+#[derive(Component, Default)]
+pub struct AddShadow;
+
+/// Marks a sprite whose shadow has been spawned
+#[derive(Component)]
+pub struct HasShadow;
+
+/// The shadow child of an [`AddShadow`] sprite. It has no collider, so physics never sees it.
+#[derive(Component)]
+pub struct Shadow;
+
+pub fn add_shadows(
+    casters: Query<(Entity, &Sprite), (With<AddShadow>, Without<HasShadow>)>,
+    mut cmds: Commands,
+) {
+    for (caster, sprite) in casters {
+        cmds.entity(caster).insert(HasShadow);
+        cmds.spawn((
+            Shadow,
+            shadow_sprite(sprite),
+            Transform::from_translation(SHADOW_OFFSET.extend(-SHADOW_DEPTH))
+                .with_scale(Vec3::splat(SHADOW_SCALE)),
+            ChildOf(caster),
+        ));
+    }
+}
+
+fn shadow_sprite(caster: &Sprite) -> Sprite {
+    Sprite {
+        color: Color::BLACK.with_alpha(SHADOW_ALPHA),
+        ..caster.clone()
+    }
+}
+
+/// Keeps shadows matching sprites that change image, like damaged asteroids and reused lasers
+pub fn sync_shadow_sprites(
+    casters: Query<(&Sprite, &Children), (With<HasShadow>, Changed<Sprite>)>,
+    mut shadows: Query<&mut Sprite, (With<Shadow>, Without<HasShadow>)>,
+) {
+    for (sprite, children) in casters {
+        for child in children {
+            if let Ok(mut shadow) = shadows.get_mut(*child) {
+                *shadow = shadow_sprite(sprite);
+            }
+        }
+    }
+}
+
+/// Shadows would spin around their caster with the hierarchy alone, this keeps them falling the
+/// same way on screen
+pub fn orient_shadows(
+    mut shadows: Query<(&mut Transform, &ChildOf), With<Shadow>>,
+    casters: Query<&Transform, (With<HasShadow>, Without<Shadow>)>,
+) {
+    for (mut tsf, parent) in shadows.iter_mut() {
+        let Ok(caster) = casters.get(parent.parent()) else {
+            continue;
+        };
+
+        let local = caster.rotation.inverse() * SHADOW_OFFSET.extend(0.0) / caster.scale;
+        tsf.translation = local.xy().extend(-SHADOW_DEPTH);
+    }
+}
+
+/// Despawns every shadow once they're turned off, they're added back when turned on again
+pub fn remove_shadows(
+    settings: Res<Settings>,
+    shadows: Query<Entity, With<Shadow>>,
+    casters: Query<Entity, With<HasShadow>>,
+    mut cmds: Commands,
+) {
+    if settings.drop_shadows {
+        return;
+    }
+
+    for shadow in shadows {
+        cmds.entity(shadow).try_despawn();
+    }
+    for caster in casters {
+        cmds.entity(caster).try_remove::<HasShadow>();
+    }
+}
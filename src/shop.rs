@@ -0,0 +1,308 @@
+use std::f32::consts::PI;
+
+use bevy::{input::InputSystems, prelude::*};
+use rand::seq::index::sample;
+
+use crate::{
+    Asteroid, GameMode, GameRng, GameStats, PlayerShip,
+    credits::Credits,
+    frenzy::frenzy_active,
+    locale::{Locale, LocalizedText},
+    palette::Palette,
+    powerups::MAX_SECONDARY_AMMO,
+    waves::{WaveSpawner, spawn_wave_groups, start_next_wave},
+};
+
+/// Upgrade shop shown between waves, paid for with [`Credits`]
+pub fn shop_plugin(app: &mut App) {
+    app.init_resource::<Shop>();
+
+    app.add_systems(Startup, spawn_shop_ui);
+    app.add_systems(
+        PreUpdate,
+        (read_shop_keys, suppress_gameplay_input)
+            .chain()
+            .run_if(|shop: Res<Shop>| shop.open)
+            .after(InputSystems),
+    );
+    app.add_systems(
+        Update,
+        (
+            open_shop
+                .after(spawn_wave_groups)
+                .before(start_next_wave)
+                .run_if(resource_equals(GameMode::Waves))
+                .run_if(not(frenzy_active)),
+            click_shop_cards,
+            update_shop_ui.run_if(
+                resource_changed::<Shop>
+                    .or(resource_changed::<Credits>)
+                    .or(resource_changed::<Locale>),
+            ),
+        )
+            .chain(),
+    );
+}
+
+/// How many upgrades are offered after each wave
+pub const SHOP_CARDS: usize = 4;
+
+/// Something the shop can sell. `name` and `description` are [`Locale`] keys.
+pub struct UpgradeDefinition {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub cost: u32,
+    pub apply: fn(&mut PlayerShip, &mut GameStats),
+}
+
+/// Every upgrade the shop picks its offers from
+pub const UPGRADES: &[UpgradeDefinition] = &[
+    UpgradeDefinition {
+        name: "upgrade.thrusters",
+        description: "upgrade.thrusters.description",
+        cost: 100,
+        apply: |ship, _| ship.linear_accel += 25.0,
+    },
+    UpgradeDefinition {
+        name: "upgrade.gyros",
+        description: "upgrade.gyros.description",
+        cost: 100,
+        apply: |ship, _| ship.angular_accel += PI / 2.0,
+    },
+    UpgradeDefinition {
+        name: "upgrade.mines",
+        description: "upgrade.mines.description",
+        cost: 75,
+        apply: |ship, _| ship.secondary_ammo = (ship.secondary_ammo + 3).min(MAX_SECONDARY_AMMO),
+    },
+    UpgradeDefinition {
+        name: "upgrade.targeting",
+        description: "upgrade.targeting.description",
+        cost: 150,
+        apply: |ship, _| ship.crit_bonus += 0.05,
+    },
+    UpgradeDefinition {
+        name: "upgrade.bounty",
+        description: "upgrade.bounty.description",
+        cost: 200,
+        apply: |_, stats| stats.score_multiplier += 0.1,
+    },
+];
+
+#[derive(Resource, Default)]
+pub struct Shop {
+    pub open: bool,
+    /// Indices into [`UPGRADES`] on sale right now
+    pub offers: Vec<usize>,
+    /// The last wave the shop was opened after, so it only opens once per cleared wave
+    pub last_wave: u32,
+}
+
+impl Shop {
+    /// Buys offer `card` if it exists and there are enough credits, closing the shop
+    pub fn buy(
+        &mut self,
+        card: usize,
+        credits: &mut Credits,
+        ship: &mut PlayerShip,
+        stats: &mut GameStats,
+    ) -> bool {
+        let Some(upgrade) = self.offers.get(card).map(|index| &UPGRADES[*index]) else {
+            return false;
+        };
+        if credits.0 < upgrade.cost {
+            return false;
+        }
+
+        credits.0 -= upgrade.cost;
+        (upgrade.apply)(ship, stats);
+        self.open = false;
+        true
+    }
+}
+
+#[derive(Component)]
+pub struct ShopRoot;
+
+#[derive(Component)]
+pub struct ShopTitle;
+
+/// Button for offer `n`, or leaving the shop without buying
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShopButton {
+    Card(usize),
+    Continue,
+}
+
+pub fn spawn_shop_ui(locale: Res<Locale>, mut cmds: Commands) {
+    let button_node = Node {
+        padding: UiRect::axes(px(12), px(6)),
+        ..default()
+    };
+
+    cmds.spawn((
+        ShopRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: percent(20),
+            left: percent(25),
+            width: percent(50),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(6),
+            padding: UiRect::all(px(16)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.85)),
+        GlobalZIndex(9),
+    ))
+    .with_children(|root| {
+        root.spawn((ShopTitle, Text::default()));
+
+        for card in 0..SHOP_CARDS {
+            root.spawn((
+                ShopButton::Card(card),
+                Button,
+                Text::default(),
+                TextColor::default(),
+                button_node.clone(),
+                BackgroundColor(Color::WHITE.with_alpha(0.1)),
+            ));
+        }
+
+        root.spawn((
+            ShopButton::Continue,
+            Button,
+            LocalizedText("shop.continue"),
+            Text::new(locale.text("shop.continue")),
+            button_node.clone(),
+            BackgroundColor(Color::NONE),
+        ));
+    });
+}
+
+/// Opens the shop once a wave has fully arrived and been cleared, before the next wave starts
+pub fn open_shop(
+    asteroids: Query<(), With<Asteroid>>,
+    spawner: Res<WaveSpawner>,
+    game_stats: Res<GameStats>,
+    mut shop: ResMut<Shop>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if !asteroids.is_empty()
+        || !spawner.is_idle()
+        || game_stats.wave == 0
+        || shop.last_wave == game_stats.wave
+    {
+        return;
+    }
+
+    shop.open = true;
+    shop.last_wave = game_stats.wave;
+    shop.offers = sample(
+        &mut game_rng.rng,
+        UPGRADES.len(),
+        SHOP_CARDS.min(UPGRADES.len()),
+    )
+    .into_vec();
+}
+
+/// Number keys buy the matching card, Enter leaves without buying
+pub fn read_shop_keys(
+    btn_input: Res<ButtonInput<KeyCode>>,
+    mut shop: ResMut<Shop>,
+    mut credits: ResMut<Credits>,
+    mut ship: Single<&mut PlayerShip>,
+    mut game_stats: ResMut<GameStats>,
+) {
+    const CARD_KEYS: [KeyCode; SHOP_CARDS] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+    ];
+
+    if btn_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        shop.open = false;
+        return;
+    }
+
+    for (card, key) in CARD_KEYS.into_iter().enumerate() {
+        if btn_input.just_pressed(key) {
+            shop.buy(card, &mut credits, &mut ship, &mut game_stats);
+        }
+    }
+}
+
+/// Releases every key before gameplay systems see them, so shopping never fires the gun
+pub fn suppress_gameplay_input(mut btn_input: ResMut<ButtonInput<KeyCode>>) {
+    btn_input.reset_all();
+}
+
+pub fn click_shop_cards(
+    buttons: Query<(&Interaction, &ShopButton), Changed<Interaction>>,
+    mut shop: ResMut<Shop>,
+    mut credits: ResMut<Credits>,
+    mut ship: Single<&mut PlayerShip>,
+    mut game_stats: ResMut<GameStats>,
+) {
+    for (interaction, button) in buttons {
+        if *interaction != Interaction::Pressed || !shop.open {
+            continue;
+        }
+
+        match button {
+            ShopButton::Card(card) => {
+                shop.buy(*card, &mut credits, &mut ship, &mut game_stats);
+            }
+            ShopButton::Continue => shop.open = false,
+        }
+    }
+}
+
+pub fn update_shop_ui(
+    shop: Res<Shop>,
+    credits: Res<Credits>,
+    locale: Res<Locale>,
+    palette: Res<Palette>,
+    mut root: Single<&mut Node, With<ShopRoot>>,
+    mut title: Single<&mut Text, (With<ShopTitle>, Without<ShopButton>)>,
+    mut cards: Query<(&ShopButton, &mut Text, &mut TextColor, &mut Node), Without<ShopRoot>>,
+) {
+    root.display = if shop.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    title.0 = locale.text_with(
+        "shop.title",
+        &[&shop.last_wave, &locale.number(credits.0.into())],
+    );
+
+    for (button, mut text, mut color, mut node) in cards.iter_mut() {
+        let ShopButton::Card(card) = *button else {
+            continue;
+        };
+        let Some(upgrade) = shop.offers.get(card).map(|index| &UPGRADES[*index]) else {
+            node.display = Display::None;
+            continue;
+        };
+
+        node.display = Display::Flex;
+        text.0 = locale.text_with(
+            "shop.card",
+            &[
+                &(card + 1),
+                &locale.text(upgrade.name),
+                &locale.number(upgrade.cost.into()),
+                &locale.text(upgrade.description),
+            ],
+        );
+        // Cards the player can't afford are greyed out
+        color.0 = if credits.0 >= upgrade.cost {
+            palette.hud_text
+        } else {
+            palette.hud_text.with_alpha(0.4)
+        };
+    }
+}
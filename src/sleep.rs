@@ -0,0 +1,148 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::{
+    Asteroid, PlayerShip,
+    config::GameConfig,
+    physics::{CircleCollider, Disabled, Velocity, detect_collisions, screen_wrap},
+    playfield::PlayField,
+};
+
+/// Puts slow asteroids far from the ship to sleep in open space. Sleeping asteroids don't move
+/// and are left out of collision detection until something comes near them.
+pub fn sleep_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_sleeping
+            .after(screen_wrap)
+            .before(detect_collisions)
+            .run_if(resource_equals(PlayField::OpenSpace)),
+    );
+}
+
+/// Skipped by [`apply_velocity`](crate::physics::apply_velocity) and
+/// [`detect_collisions`] until [`update_sleeping`] wakes it
+#[derive(Component)]
+pub struct Sleeping;
+
+/// The [`PhysicsConfig::sleep_cell_size`](crate::config::PhysicsConfig::sleep_cell_size) grid
+/// cell `pos` falls in
+fn cell(pos: Vec2, size: f32) -> IVec2 {
+    (pos / size).floor().as_ivec2()
+}
+
+/// Puts far, slow asteroids to sleep and wakes them again.
+///
+/// Waking is done a grid cell at a time: every cell around something awake that collides is
+/// busy, and any sleeper in a busy cell wakes up before collisions are checked. A laser or
+/// another asteroid drifting toward a sleeper wakes it a whole cell early, so no hit is missed.
+pub fn update_sleeping(
+    ship: Option<Single<&Transform, With<PlayerShip>>>,
+    asteroids: Query<
+        (Entity, &Transform, &Velocity, Has<Sleeping>),
+        (With<Asteroid>, Without<Disabled>),
+    >,
+    others: Query<&Transform, (With<CircleCollider>, Without<Asteroid>, Without<Disabled>)>,
+    config: Res<GameConfig>,
+    mut cmds: Commands,
+) {
+    let Some(ship) = ship else {
+        return;
+    };
+    let ship_pos = ship.translation.xy();
+    let config = &config.physics;
+
+    let mut busy = HashSet::new();
+    let mut mark_busy = |pos: Vec2| {
+        let center = cell(pos, config.sleep_cell_size);
+        for x in -1..=1 {
+            for y in -1..=1 {
+                busy.insert(center + IVec2::new(x, y));
+            }
+        }
+    };
+
+    for tsf in others {
+        mark_busy(tsf.translation.xy());
+    }
+
+    let mut drowsy = vec![];
+    for (ent, tsf, vel, sleeping) in asteroids {
+        let pos = tsf.translation.xy();
+        let limit = if sleeping {
+            config.wake_distance
+        } else {
+            config.sleep_distance
+        };
+
+        if pos.distance(ship_pos) > limit && vel.linear.length() < config.sleep_speed {
+            drowsy.push((ent, pos, sleeping));
+        } else {
+            mark_busy(pos);
+            if sleeping {
+                cmds.entity(ent).remove::<Sleeping>();
+            }
+        }
+    }
+
+    for (ent, pos, sleeping) in drowsy {
+        let sleep = !busy.contains(&cell(pos, config.sleep_cell_size));
+        if sleep && !sleeping {
+            cmds.entity(ent).insert(Sleeping);
+        } else if !sleep && sleeping {
+            cmds.entity(ent).remove::<Sleeping>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::physics::apply_velocity;
+
+    #[test]
+    fn sleeping_asteroid_stays_put_until_the_ship_comes_back() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+        world.insert_resource(time);
+        world.init_resource::<GameConfig>();
+
+        let ship = world
+            .spawn((PlayerShip::default(), Transform::default()))
+            .id();
+        let asteroid = world
+            .spawn((
+                Asteroid,
+                CircleCollider { radius: 50.0 },
+                Transform::from_xyz(3000.0, 0.0, 0.0),
+                Velocity {
+                    linear: Vec2::new(10.0, 0.0),
+                    linear_drag: Vec2::ZERO,
+                    ..default()
+                },
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((apply_velocity, update_sleeping).chain());
+        let position = |world: &World| world.get::<Transform>(asteroid).unwrap().translation;
+
+        schedule.run(&mut world);
+        assert!(world.get::<Sleeping>(asteroid).is_some());
+
+        let asleep_at = position(&world);
+        for _ in 0..10 {
+            schedule.run(&mut world);
+        }
+        assert_eq!(position(&world), asleep_at);
+
+        world.get_mut::<Transform>(ship).unwrap().translation.x = 2000.0;
+        schedule.run(&mut world);
+        assert!(world.get::<Sleeping>(asteroid).is_none());
+
+        schedule.run(&mut world);
+        assert!(position(&world).x > asleep_at.x);
+    }
+}
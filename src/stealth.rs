@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::effects::{Invincible, tick_invincible};
+
+pub fn stealth_plugin(app: &mut App) {
+    app.add_systems(Update, reveal_stealth.before(tick_invincible));
+}
+
+/// Chance that a spawned asteroid comes in hidden
+pub const STEALTH_CHANCE: f64 = 1.0 / 50.0;
+/// How long a stealth asteroid stays hidden, and can't be shot
+pub const STEALTH_REVEAL_SECS: f32 = 1.5;
+
+/// Asteroid that spawned hidden. Keeps its [`Palette::stealth_asteroid`](crate::palette::Palette)
+/// tint after being revealed, so it still stands out from the rest.
+#[derive(Component)]
+pub struct StealthAsteroid;
+
+/// Counts down until a [`StealthAsteroid`] pops into view
+#[derive(Component)]
+pub struct StealthRevealTimer(pub Timer);
+
+/// Everything a stealth asteroid spawns with on top of the usual asteroid components
+pub fn stealth_bundle() -> impl Bundle {
+    (
+        StealthAsteroid,
+        StealthRevealTimer(Timer::from_seconds(STEALTH_REVEAL_SECS, TimerMode::Once)),
+        Invincible::new(STEALTH_REVEAL_SECS),
+        Visibility::Hidden,
+    )
+}
+
+/// Shows stealth asteroids once their timer runs out, and makes them shootable at the same moment
+pub fn reveal_stealth(
+    mut hidden: Query<(Entity, &mut StealthRevealTimer, &mut Visibility)>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    for (ent, mut reveal, mut visibility) in hidden.iter_mut() {
+        reveal.0.tick(time.delta());
+        if !reveal.0.is_finished() {
+            continue;
+        }
+
+        *visibility = Visibility::Visible;
+        cmds.entity(ent)
+            .remove::<(StealthRevealTimer, Invincible)>();
+    }
+}
@@ -0,0 +1,183 @@
+use std::f32::consts::PI;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use rand::Rng;
+
+use crate::{
+    Asteroid, AsteroidSize, GameAssets, GameCleanup, GameRng, Health, MeteorVariant, PlayerShip,
+    config::GameConfig,
+    palette::Palette,
+    physics::{CircleCollider, ForceAccum, Velocity, apply_velocity},
+};
+
+pub fn swarm_plugin(app: &mut App) {
+    app.init_resource::<NextSwarmId>();
+
+    app.add_systems(
+        Update,
+        (
+            dissolve_small_swarms,
+            flock_steer.before(apply_velocity),
+            draw_swarm_links.after(apply_velocity),
+        )
+            .chain(),
+    );
+}
+
+/// Chance that an asteroid spawn is replaced by a whole swarm
+pub const SWARM_CHANCE: f64 = 0.05;
+pub const SWARM_SIZE: usize = 8;
+/// Swarms enter from a ring this far from the center of the screen
+pub const SWARM_SPAWN_DISTANCE: f32 = 500.0;
+/// Once a swarm has fewer members than this, the survivors become ordinary asteroids
+pub const SWARM_MIN_MEMBERS: usize = 3;
+
+// Steering weights
+const COHESION: f32 = 1.5;
+const ALIGNMENT: f32 = 1.0;
+const SEPARATION: f32 = 4000.0;
+const SEPARATION_RADIUS: f32 = 45.0;
+const SEEK: f32 = 60.0;
+
+/// Members further apart than this aren't linked, so a swarm split by screen wrap doesn't draw
+/// lines across the whole screen
+const LINK_DISTANCE: f32 = 150.0;
+
+#[derive(Component)]
+pub struct FlockMember {
+    pub swarm_id: u8,
+}
+
+#[derive(Resource, Default)]
+pub struct NextSwarmId(pub u8);
+
+/// Spawns [`SWARM_SIZE`] small asteroids clustered around `location`
+pub fn spawn_swarm(
+    In(location): In<Vec2>,
+    assets: Res<GameAssets>,
+    config: Res<GameConfig>,
+    mut next_id: ResMut<NextSwarmId>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    let rng = &mut game_rng.rng;
+
+    let tier = &config.asteroids.small;
+    let swarm_id = next_id.0;
+    next_id.0 = next_id.0.wrapping_add(1);
+
+    for _ in 0..SWARM_SIZE {
+        let offset = Vec2::new(rng.random_range(-40.0..40.0), rng.random_range(-40.0..40.0));
+        let pos = location + offset;
+        let variant = tier.sprites[rng.random_range(0..tier.sprites.len())];
+
+        cmds.spawn((
+            Sprite::from_image(assets.meteors[variant].clone()),
+            MeteorVariant(variant),
+            Asteroid,
+            AsteroidSize::Small,
+            Health::new(tier.health),
+            FlockMember { swarm_id },
+            ForceAccum::default(),
+            Velocity {
+                angular: rng.random_range(-PI..PI),
+                angular_drag: 0.0,
+                ..default()
+            },
+            GameCleanup,
+            CircleCollider {
+                radius: tier.scaled_radius(),
+            },
+            Transform::from_xyz(pos.x, pos.y, 0.0).with_scale(Vec3::splat(tier.scale)),
+        ));
+    }
+}
+
+/// Applies cohesion, alignment and separation within each swarm, plus a pull toward the ship
+pub fn flock_steer(
+    mut members: Query<(Entity, &FlockMember, &Transform, &Velocity, &mut ForceAccum)>,
+    ship: Option<Single<&Transform, With<PlayerShip>>>,
+) {
+    // swarm_id -> (position sum, velocity sum, count)
+    let mut swarms: HashMap<u8, (Vec2, Vec2, f32)> = HashMap::new();
+    let mut positions: Vec<(Entity, u8, Vec2)> = vec![];
+
+    for (ent, member, tsf, vel, _) in members.iter() {
+        let pos = tsf.translation.xy();
+        let entry = swarms.entry(member.swarm_id).or_default();
+        entry.0 += pos;
+        entry.1 += vel.linear;
+        entry.2 += 1.0;
+        positions.push((ent, member.swarm_id, pos));
+    }
+
+    let target = ship.map(|ship| ship.translation.xy());
+
+    for (ent, member, tsf, vel, mut force) in members.iter_mut() {
+        let pos = tsf.translation.xy();
+        let (pos_sum, vel_sum, count) = swarms[&member.swarm_id];
+
+        let centroid = pos_sum / count;
+        let avg_vel = vel_sum / count;
+
+        let mut separation = Vec2::ZERO;
+        for (other, swarm_id, other_pos) in positions.iter() {
+            if *other == ent || *swarm_id != member.swarm_id {
+                continue;
+            }
+
+            let away = pos - *other_pos;
+            let dist_sq = away.length_squared();
+            if dist_sq > 0.0 && dist_sq < SEPARATION_RADIUS * SEPARATION_RADIUS {
+                separation += away / dist_sq;
+            }
+        }
+
+        let seek = target.map_or(Vec2::ZERO, |target| {
+            (target - centroid).normalize_or_zero() * SEEK
+        });
+
+        force.0 += (centroid - pos) * COHESION
+            + (avg_vel - vel.linear) * ALIGNMENT
+            + separation * SEPARATION
+            + seek;
+    }
+}
+
+/// Swarms that have been whittled down stop flocking and drift like any other asteroid
+pub fn dissolve_small_swarms(members: Query<(Entity, &FlockMember)>, mut cmds: Commands) {
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for (_, member) in members.iter() {
+        *counts.entry(member.swarm_id).or_default() += 1;
+    }
+
+    for (ent, member) in members.iter() {
+        if counts[&member.swarm_id] < SWARM_MIN_MEMBERS {
+            cmds.entity(ent).remove::<(FlockMember, ForceAccum)>();
+        }
+    }
+}
+
+/// Draws a line between consecutive members of each swarm, so it reads as one group rather
+/// than a handful of loose rocks
+pub fn draw_swarm_links(
+    members: Query<(Entity, &FlockMember, &Transform)>,
+    palette: Res<Palette>,
+    mut gizmos: Gizmos,
+) {
+    // Sorted so each member links to the same neighbors every frame
+    let mut members: Vec<(u8, Entity, Vec2)> = members
+        .iter()
+        .map(|(ent, member, tsf)| (member.swarm_id, ent, tsf.translation.xy()))
+        .collect();
+    members.sort_unstable_by_key(|(swarm_id, ent, _)| (*swarm_id, *ent));
+
+    for pair in members.windows(2) {
+        let [(swarm_a, _, a), (swarm_b, _, b)] = pair else {
+            continue;
+        };
+        if swarm_a == swarm_b && a.distance(*b) <= LINK_DISTANCE {
+            gizmos.line_2d(*a, *b, palette.swarm_link);
+        }
+    }
+}
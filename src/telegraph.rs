@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::{AsteroidConfig, palette::Palette, playfield::PlayBounds, spawn_asteroid};
+
+pub fn telegraph_plugin(app: &mut App) {
+    app.add_systems(Update, (tick_spawn_telegraphs, draw_spawn_telegraphs));
+}
+
+/// Markers are pulled this far inside the play area, asteroids enter from just outside it
+const TELEGRAPH_INSET: f32 = 30.0;
+const TELEGRAPH_RADIUS: f32 = 25.0;
+
+/// Warning shown where an asteroid is about to come in. The asteroid is spawned exactly as
+/// described by `asteroid` once the timer runs out.
+#[derive(Component)]
+pub struct SpawnTelegraph {
+    pub asteroid: AsteroidConfig,
+    pub timer: Timer,
+}
+
+impl SpawnTelegraph {
+    pub fn new(asteroid: AsteroidConfig, secs: f32) -> Self {
+        Self {
+            asteroid,
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+        }
+    }
+}
+
+/// Spawns the asteroid behind each finished telegraph. Runs on virtual time, so a paused game
+/// holds its telegraphs too.
+pub fn tick_spawn_telegraphs(
+    mut telegraphs: Query<(Entity, &mut SpawnTelegraph)>,
+    time: Res<Time>,
+    mut cmds: Commands,
+) {
+    for (ent, mut telegraph) in telegraphs.iter_mut() {
+        telegraph.timer.tick(time.delta());
+        if !telegraph.timer.is_finished() {
+            continue;
+        }
+
+        cmds.run_system_cached_with(spawn_asteroid, telegraph.asteroid);
+        cmds.entity(ent).despawn();
+    }
+}
+
+/// A crosshair ring that fades in over the telegraph, on the edge the asteroid comes through
+pub fn draw_spawn_telegraphs(
+    telegraphs: Query<&SpawnTelegraph>,
+    bounds: Res<PlayBounds>,
+    palette: Res<Palette>,
+    mut gizmos: Gizmos,
+) {
+    let inner = (bounds.half_extents - Vec2::splat(TELEGRAPH_INSET)).max(Vec2::ZERO);
+
+    for telegraph in telegraphs {
+        let pos = telegraph.asteroid.location.clamp(-inner, inner);
+        let color = palette.warning.with_alpha(telegraph.timer.fraction());
+
+        gizmos.circle_2d(pos, TELEGRAPH_RADIUS, color);
+        gizmos.line_2d(
+            pos - Vec2::X * TELEGRAPH_RADIUS,
+            pos + Vec2::X * TELEGRAPH_RADIUS,
+            color,
+        );
+        gizmos.line_2d(
+            pos - Vec2::Y * TELEGRAPH_RADIUS,
+            pos + Vec2::Y * TELEGRAPH_RADIUS,
+            color,
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    AsteroidSize,
+    events::{
+        AsteroidDestroyed, AsteroidSpawned, LaserHit, PlayerDied, PowerUpCollected, ShotFired,
+        WaveStarted,
+    },
+    mutators::{Mutator, RunModifiers},
+    powerups::PowerUpKind,
+};
+
+/// Records a run to a JSON Lines file when the game is started with `--record`
+pub fn telemetry_plugin(app: &mut App) {
+    if !std::env::args().any(|arg| arg == "--record") {
+        return;
+    }
+
+    app.init_resource::<Telemetry>();
+    app.add_systems(Update, record_telemetry);
+    app.add_systems(Last, flush_telemetry_on_exit);
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    AsteroidSpawned {
+        position: [f32; 2],
+        tier: AsteroidSize,
+        speed: f32,
+    },
+    AsteroidDestroyed {
+        position: [f32; 2],
+        tier: AsteroidSize,
+    },
+    ShotFired {
+        position: [f32; 2],
+        heading: f32,
+    },
+    /// Counting these against `shot_fired` gives the hit rate, and `critical` the crit rate
+    LaserHit {
+        position: [f32; 2],
+        critical: bool,
+    },
+    PlayerDied {
+        score: u64,
+        /// Modified runs score differently, so they're kept apart from normal ones
+        mutators: Vec<Mutator>,
+    },
+    PowerUpCollected {
+        kind: PowerUpKind,
+    },
+    WaveStarted {
+        wave: u32,
+        boss: bool,
+    },
+}
+
+#[derive(Serialize, Debug)]
+pub struct TelemetryRecord {
+    /// Seconds since the run started
+    pub time: f32,
+    #[serde(flatten)]
+    pub event: TelemetryEvent,
+}
+
+/// Events recorded since the current run started
+#[derive(Resource)]
+pub struct Telemetry {
+    /// Wall clock start of the run, used to name its file
+    pub run_started: SystemTime,
+    /// Game time the run started at
+    pub run_started_secs: f32,
+    pub records: Vec<TelemetryRecord>,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            run_started: SystemTime::now(),
+            run_started_secs: 0.0,
+            records: vec![],
+        }
+    }
+}
+
+impl Telemetry {
+    /// Writes the run to `telemetry-<unix secs>.jsonl` and starts a new one. Failing to write
+    /// only logs, a lost recording is never worth crashing the game over.
+    pub fn flush(&mut self, now_secs: f32) {
+        let records = std::mem::take(&mut self.records);
+        let started = std::mem::replace(&mut self.run_started, SystemTime::now());
+        self.run_started_secs = now_secs;
+
+        if records.is_empty() {
+            return;
+        }
+
+        let stamp = started
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or_default();
+        let path = format!("telemetry-{stamp}.jsonl");
+
+        let mut contents = String::new();
+        for record in &records {
+            match serde_json::to_string(record) {
+                Ok(line) => {
+                    contents.push_str(&line);
+                    contents.push('\n');
+                }
+                Err(err) => error!("Could not serialize telemetry record {record:?}: {err}"),
+            }
+        }
+
+        match std::fs::write(&path, contents) {
+            Ok(()) => info!("Wrote {} telemetry records to {path}", records.len()),
+            Err(err) => error!("Could not write telemetry to {path}: {err}"),
+        }
+    }
+}
+
+pub fn record_telemetry(
+    mut telemetry: ResMut<Telemetry>,
+    time: Res<Time>,
+    mut spawned: MessageReader<AsteroidSpawned>,
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    mut shots: MessageReader<ShotFired>,
+    mut hits: MessageReader<LaserHit>,
+    mut deaths: MessageReader<PlayerDied>,
+    mut powerups: MessageReader<PowerUpCollected>,
+    mut waves: MessageReader<WaveStarted>,
+    modifiers: Res<RunModifiers>,
+) {
+    let events = spawned
+        .read()
+        .map(|e| TelemetryEvent::AsteroidSpawned {
+            position: e.position.to_array(),
+            tier: e.size,
+            speed: e.speed,
+        })
+        .chain(destroyed.read().map(|e| TelemetryEvent::AsteroidDestroyed {
+            position: e.position.to_array(),
+            tier: e.size,
+        }))
+        .chain(shots.read().map(|e| TelemetryEvent::ShotFired {
+            position: e.position.to_array(),
+            heading: e.heading,
+        }))
+        .chain(hits.read().map(|e| TelemetryEvent::LaserHit {
+            position: e.position.to_array(),
+            critical: e.critical,
+        }))
+        .chain(
+            powerups
+                .read()
+                .map(|e| TelemetryEvent::PowerUpCollected { kind: e.kind }),
+        )
+        .chain(waves.read().map(|e| TelemetryEvent::WaveStarted {
+            wave: e.wave,
+            boss: e.boss,
+        }))
+        .collect::<Vec<_>>();
+
+    let now = time.elapsed_secs();
+    let run_time = now - telemetry.run_started_secs;
+    telemetry
+        .records
+        .extend(events.into_iter().map(|event| TelemetryRecord {
+            time: run_time,
+            event,
+        }));
+
+    // A death ends the run, everything after it goes into the next file
+    for death in deaths.read() {
+        telemetry.records.push(TelemetryRecord {
+            time: run_time,
+            event: TelemetryEvent::PlayerDied {
+                score: death.score,
+                mutators: modifiers.active().collect(),
+            },
+        });
+        telemetry.flush(now);
+    }
+}
+
+pub fn flush_telemetry_on_exit(
+    mut exits: MessageReader<AppExit>,
+    mut telemetry: ResMut<Telemetry>,
+    time: Res<Time>,
+) {
+    if exits.read().next().is_some() {
+        telemetry.flush(time.elapsed_secs());
+    }
+}
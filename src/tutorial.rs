@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+
+use crate::{
+    AsteroidConfig, AsteroidSize, GameMode, GameStats, MainCamera, PlayerShip, ShipInput,
+    config::GameConfig,
+    controls::{Action, key_name},
+    events::AsteroidDestroyed,
+    locale::Locale,
+    palette::Palette,
+    profile::PlayerProfile,
+    settings::Settings,
+    spawn_asteroid,
+};
+
+/// Prompts next to the ship walking through the controls the first time the game is played.
+/// Each one stays up until the player has actually done it, and asteroids only trickle in
+/// until the tutorial is over.
+pub fn tutorial_plugin(app: &mut App) {
+    app.init_resource::<Tutorial>();
+
+    app.add_systems(Startup, (start_tutorial, spawn_tutorial_overlay).chain());
+    app.add_systems(
+        Update,
+        (
+            skip_tutorial,
+            run_tutorial,
+            update_tutorial_overlay
+                .run_if(resource_changed::<Tutorial>.or(resource_changed::<Locale>)),
+            (follow_ship_with_prompt, animate_tutorial_prompt),
+        )
+            .chain()
+            .run_if(tutorial_active.or(resource_changed::<Tutorial>)),
+    );
+}
+
+/// Thrust has to be held this long before moving on to rotating
+pub const TUTORIAL_THRUST_SECS: f32 = 1.0;
+/// Turning either way for this long moves on to firing
+pub const TUTORIAL_ROTATE_SECS: f32 = 0.5;
+/// How long "Good luck" takes to fade out
+pub const TUTORIAL_GOOD_LUCK_SECS: f32 = 2.0;
+/// [`GameStats::spawn_rate`] is held at or below this while the tutorial runs
+pub const TUTORIAL_SPAWN_RATE: f32 = 0.04;
+/// Key that skips the rest of the tutorial
+pub const TUTORIAL_SKIP_KEY: KeyCode = KeyCode::Tab;
+const PRACTICE_SPEED: f32 = 30.0;
+/// Where the practice asteroids appear, clear of the ship in the middle
+const PRACTICE_POSITIONS: [Vec2; 2] = [Vec2::new(-300.0, 150.0), Vec2::new(300.0, 150.0)];
+/// The prompt sits this far below the ship on screen
+const PROMPT_OFFSET: f32 = 60.0;
+/// Wide enough for the longest prompt, which is centered in it
+const PROMPT_WIDTH: f32 = 400.0;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum TutorialPhase {
+    #[default]
+    Off,
+    /// Seconds thrust has been held so far
+    Thrust(f32),
+    /// Seconds spent rotating so far
+    Rotate(f32),
+    /// Practice asteroids are out, the first asteroid destroyed ends the tutorial
+    Fire,
+    /// Fading out, the tutorial is already done
+    GoodLuck(Timer),
+}
+
+#[derive(Resource, Default)]
+pub struct Tutorial {
+    pub phase: TutorialPhase,
+}
+
+pub fn tutorial_active(tutorial: Res<Tutorial>) -> bool {
+    tutorial.phase != TutorialPhase::Off
+}
+
+#[derive(Component)]
+pub struct TutorialRoot;
+
+/// One line of the overlay, the prompt on top and the skip hint under it
+#[derive(Component)]
+pub struct TutorialHint(pub usize);
+
+pub fn start_tutorial(
+    profile: Res<PlayerProfile>,
+    mode: Res<GameMode>,
+    mut tutorial: ResMut<Tutorial>,
+) {
+    if profile.first_run && *mode != GameMode::Sandbox {
+        tutorial.phase = TutorialPhase::Thrust(0.0);
+    }
+}
+
+pub fn spawn_tutorial_overlay(palette: Res<Palette>, mut cmds: Commands) {
+    cmds.spawn((
+        TutorialRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            width: px(PROMPT_WIDTH),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(4),
+            display: Display::None,
+            ..default()
+        },
+        GlobalZIndex(7),
+    ))
+    .with_children(|root| {
+        for line in 0..2 {
+            root.spawn((
+                TutorialHint(line),
+                Text::default(),
+                TextColor(palette.hud_text),
+                palette.hud_shadow(),
+            ));
+        }
+    });
+}
+
+/// Tab skips the rest of the tutorial. Like finishing it, that's remembered for good.
+pub fn skip_tutorial(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut tutorial: ResMut<Tutorial>,
+    mut profile: ResMut<PlayerProfile>,
+    mut game_stats: ResMut<GameStats>,
+    config: Res<GameConfig>,
+) {
+    if !keys.just_pressed(TUTORIAL_SKIP_KEY) || tutorial.phase == TutorialPhase::Off {
+        return;
+    }
+
+    tutorial.phase = TutorialPhase::Off;
+    profile.first_run = false;
+    game_stats.spawn_rate = config.spawning.spawn_rate;
+}
+
+/// Moves on to the next prompt once the player has done what the current one asks
+pub fn run_tutorial(
+    time: Res<Time>,
+    input: Res<ShipInput>,
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    mut tutorial: ResMut<Tutorial>,
+    mut profile: ResMut<PlayerProfile>,
+    mut game_stats: ResMut<GameStats>,
+    config: Res<GameConfig>,
+    mut cmds: Commands,
+) {
+    let dt = time.delta_secs();
+    let destroyed = destroyed.read().count() > 0;
+    // Only moving on to the next phase counts as a change, the overlay is redrawn for those
+    let next = match &mut tutorial.bypass_change_detection().phase {
+        TutorialPhase::Off => None,
+        TutorialPhase::Thrust(held) => {
+            if input.thrust {
+                *held += dt;
+            }
+            (*held >= TUTORIAL_THRUST_SECS).then_some(TutorialPhase::Rotate(0.0))
+        }
+        TutorialPhase::Rotate(held) => {
+            if input.rotate_left || input.rotate_right {
+                *held += dt;
+            }
+            (*held >= TUTORIAL_ROTATE_SECS).then(|| {
+                spawn_practice_asteroids(&mut cmds);
+                TutorialPhase::Fire
+            })
+        }
+        TutorialPhase::Fire => destroyed.then(|| {
+            TutorialPhase::GoodLuck(Timer::from_seconds(
+                TUTORIAL_GOOD_LUCK_SECS,
+                TimerMode::Once,
+            ))
+        }),
+        TutorialPhase::GoodLuck(timer) => timer
+            .tick(time.delta())
+            .is_finished()
+            .then_some(TutorialPhase::Off),
+    };
+
+    match (&tutorial.phase, &next) {
+        (TutorialPhase::Fire, Some(TutorialPhase::GoodLuck(_))) => {
+            profile.first_run = false;
+            game_stats.spawn_rate = config.spawning.spawn_rate;
+        }
+        // A death resets the spawn rate, so it's held down every frame rather than once
+        (TutorialPhase::Thrust(_) | TutorialPhase::Rotate(_) | TutorialPhase::Fire, _) => {
+            game_stats.spawn_rate = game_stats.spawn_rate.min(TUTORIAL_SPAWN_RATE);
+        }
+        _ => {}
+    }
+
+    if let Some(next) = next {
+        tutorial.phase = next;
+    }
+}
+
+fn spawn_practice_asteroids(cmds: &mut Commands) {
+    for location in PRACTICE_POSITIONS {
+        // Drift slowly inward
+        let heading = f32::atan2(location.x, -location.y);
+        cmds.run_system_cached_with(
+            spawn_asteroid,
+            AsteroidConfig {
+                location,
+                heading,
+                speed: PRACTICE_SPEED,
+                angvel: 0.5,
+                size: AsteroidSize::Small,
+                group: None,
+            },
+        );
+    }
+}
+
+/// Keeps the prompt just under the ship on screen. While the ship is gone it stays put.
+pub fn follow_ship_with_prompt(
+    ship: Option<Single<&GlobalTransform, With<PlayerShip>>>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut root: Single<&mut Node, With<TutorialRoot>>,
+) {
+    let Some(ship) = ship else {
+        return;
+    };
+    let (camera, camera_tsf) = *camera;
+    let Ok(pos) = camera.world_to_viewport(camera_tsf, ship.translation()) else {
+        return;
+    };
+
+    root.left = px(pos.x - PROMPT_WIDTH / 2.0);
+    root.top = px(pos.y + PROMPT_OFFSET);
+}
+
+/// Prompts gently pulse, "Good luck" fades away
+pub fn animate_tutorial_prompt(
+    tutorial: Res<Tutorial>,
+    time: Res<Time>,
+    mut hints: Query<&mut TextColor, With<TutorialHint>>,
+) {
+    let alpha = match &tutorial.phase {
+        TutorialPhase::Off => return,
+        TutorialPhase::GoodLuck(timer) => timer.fraction_remaining(),
+        _ => 0.85 + 0.15 * (time.elapsed_secs() * 3.0).sin(),
+    };
+
+    for mut color in hints.iter_mut() {
+        color.0.set_alpha(alpha);
+    }
+}
+
+pub fn update_tutorial_overlay(
+    tutorial: Res<Tutorial>,
+    locale: Res<Locale>,
+    settings: Res<Settings>,
+    mut root: Single<&mut Node, With<TutorialRoot>>,
+    mut hints: Query<(&TutorialHint, &mut Text)>,
+) {
+    root.display = if tutorial.phase == TutorialPhase::Off {
+        Display::None
+    } else {
+        Display::Flex
+    };
+
+    let key = |action| key_name(settings.bindings.key(action));
+    let prompt = match tutorial.phase {
+        TutorialPhase::Off => String::new(),
+        TutorialPhase::Thrust(_) => locale.text_with("tutorial.thrust", &[&key(Action::Thrust)]),
+        TutorialPhase::Rotate(_) => locale.text_with(
+            "tutorial.rotate",
+            &[&key(Action::RotateLeft), &key(Action::RotateRight)],
+        ),
+        TutorialPhase::Fire => locale.text_with("tutorial.fire", &[&key(Action::Fire)]),
+        TutorialPhase::GoodLuck(_) => locale.text("tutorial.good_luck").to_string(),
+    };
+    let skip = match tutorial.phase {
+        TutorialPhase::GoodLuck(_) => String::new(),
+        _ => locale.text_with("tutorial.skip", &[&key_name(TUTORIAL_SKIP_KEY)]),
+    };
+
+    for (hint, mut text) in hints.iter_mut() {
+        text.0 = if hint.0 == 0 {
+            prompt.clone()
+        } else {
+            skip.clone()
+        };
+    }
+}
@@ -0,0 +1,367 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    Asteroid, AsteroidConfig, AsteroidSize, GameMode, GameRng, GameStats,
+    asset_source::asset_path,
+    belt::Edge,
+    destroy_asteroids,
+    events::{BossDefeated, WaveStarted},
+    frenzy::frenzy_active,
+    physics::{ScreenWrap, random_edge_position},
+    playfield::PlayBounds,
+    shop::Shop,
+    spawn_asteroid,
+    tutorial::tutorial_active,
+};
+
+pub fn waves_plugin(app: &mut App) {
+    app.init_asset::<WaveDefinition>();
+    app.init_asset_loader::<WaveDefinitionLoader>();
+    app.init_resource::<WaveSpawner>();
+
+    app.add_systems(Startup, load_wave_definitions);
+    app.add_systems(
+        Update,
+        // Fragments of the last asteroid have to exist before checking whether the wave is clear
+        (
+            (
+                spawn_wave_groups,
+                spawn_mega_rock,
+                award_wave_bonus,
+                start_next_wave
+                    .run_if(|shop: Res<Shop>| !shop.open)
+                    .run_if(not(tutorial_active))
+                    .run_if(not(frenzy_active)),
+            )
+                .chain()
+                .after(destroy_asteroids),
+            wrap_wave_asteroids,
+        )
+            .run_if(resource_equals(GameMode::Waves)),
+    );
+}
+
+/// Asteroids in the first procedural wave, every wave after adds one more
+pub const WAVE_BASE_ASTEROIDS: u32 = 3;
+/// First wave a mega asteroid turns up in
+pub const MEGA_FIRST_WAVE: u32 = 3;
+/// Seconds into a wave before its mega asteroid arrives
+pub const MEGA_SPAWN_DELAY_SECS: f32 = 8.0;
+pub const MEGA_SPEED: f32 = 40.0;
+
+/// Authored waves, played in this order. Once they run out waves are generated instead.
+pub const WAVE_FILES: &[&str] = &[
+    "waves/01.ron",
+    "waves/02.ron",
+    "waves/03.ron",
+    "waves/04.ron",
+    "waves/05.ron",
+];
+
+/// One wave's worth of asteroids, read from a file in [`WAVE_FILES`]
+#[derive(Asset, TypePath, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WaveDefinition {
+    pub groups: Vec<SpawnGroup>,
+    /// Marks the wave as a boss fight, passed along with [`WaveStarted`]
+    #[serde(default)]
+    pub boss: bool,
+    /// Points awarded once every asteroid in the wave is destroyed
+    #[serde(default)]
+    pub score_bonus: u32,
+}
+
+/// A batch of asteroids that arrive together
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SpawnGroup {
+    pub tier: AsteroidSize,
+    pub count: u32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    /// Which side they come in from, a random point on any side if left out
+    #[serde(default)]
+    pub edge: Option<Edge>,
+    /// Seconds after the wave starts before the group arrives
+    #[serde(default)]
+    pub delay_secs: f32,
+}
+
+impl WaveDefinition {
+    /// Used once the authored waves run out, or if a wave file failed to load
+    pub fn procedural(wave: u32) -> Self {
+        Self {
+            groups: vec![SpawnGroup {
+                tier: AsteroidSize::Large,
+                count: WAVE_BASE_ASTEROIDS + wave,
+                min_speed: 60.0,
+                max_speed: 120.0,
+                edge: None,
+                delay_secs: 0.0,
+            }],
+            boss: false,
+            score_bonus: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WaveLoadError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for WaveLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WaveLoadError::Io(e) => write!(f, "couldn't read wave file: {e}"),
+            WaveLoadError::Parse(e) => write!(f, "invalid wave definition at {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WaveLoadError {}
+
+#[derive(Default)]
+pub struct WaveDefinitionLoader;
+
+impl AssetLoader for WaveDefinitionLoader {
+    type Asset = WaveDefinition;
+    type Settings = ();
+    type Error = WaveLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<WaveDefinition, WaveLoadError> {
+        let mut bytes = vec![];
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(WaveLoadError::Io)?;
+        ron::de::from_bytes(&bytes).map_err(WaveLoadError::Parse)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// Handles to every file in [`WAVE_FILES`], in order
+#[derive(Resource)]
+pub struct WaveDefinitions(pub Vec<Handle<WaveDefinition>>);
+
+/// Groups of the current wave that haven't arrived yet
+#[derive(Resource, Default)]
+pub struct WaveSpawner {
+    pub pending: Vec<(Timer, SpawnGroup)>,
+    /// Bonus for clearing the current wave, zero once it's been paid out
+    pub score_bonus: u32,
+    /// The current wave is a boss fight that hasn't been won yet
+    pub boss: bool,
+    /// Counts down to the current wave's mega asteroid, `None` once it's arrived or if the wave
+    /// doesn't get one
+    pub mega_spawn_timer: Option<Timer>,
+}
+
+impl WaveSpawner {
+    /// Whether every group of the current wave has arrived, its mega asteroid included
+    pub fn is_idle(&self) -> bool {
+        self.pending.is_empty() && self.mega_spawn_timer.is_none()
+    }
+}
+
+pub fn load_wave_definitions(asset_server: Res<AssetServer>, mut cmds: Commands) {
+    cmds.insert_resource(WaveDefinitions(
+        WAVE_FILES
+            .iter()
+            .map(|path| asset_server.load(asset_path(path)))
+            .collect(),
+    ));
+}
+
+/// Kicks off the next wave as soon as the field is clear.
+///
+/// Definitions are looked up when the wave starts, so a wave file edited while hot reloading
+/// is picked up from the next wave on.
+pub fn start_next_wave(
+    asteroids: Query<(), With<Asteroid>>,
+    definitions: Res<WaveDefinitions>,
+    wave_assets: Res<Assets<WaveDefinition>>,
+    mut spawner: ResMut<WaveSpawner>,
+    mut game_stats: ResMut<GameStats>,
+    mut started: MessageWriter<WaveStarted>,
+) {
+    if !asteroids.is_empty() || !spawner.is_idle() {
+        return;
+    }
+
+    game_stats.wave += 1;
+    let wave = game_stats.wave;
+
+    let definition = match definitions.0.get(wave as usize - 1) {
+        Some(handle) => wave_assets.get(handle).cloned().unwrap_or_else(|| {
+            warn!("wave {wave} isn't loaded, generating it instead");
+            WaveDefinition::procedural(wave)
+        }),
+        None => WaveDefinition::procedural(wave),
+    };
+
+    started.write(WaveStarted {
+        wave,
+        boss: definition.boss,
+    });
+
+    spawner.score_bonus = definition.score_bonus;
+    spawner.boss = definition.boss;
+    spawner.mega_spawn_timer = (wave >= MEGA_FIRST_WAVE)
+        .then(|| Timer::from_seconds(MEGA_SPAWN_DELAY_SECS, TimerMode::Once));
+    spawner.pending = definition
+        .groups
+        .into_iter()
+        .map(|group| {
+            (
+                Timer::from_seconds(group.delay_secs, TimerMode::Once),
+                group,
+            )
+        })
+        .collect();
+}
+
+/// Brings in each group of the current wave once its delay is up
+pub fn spawn_wave_groups(
+    time: Res<Time>,
+    bounds: Res<PlayBounds>,
+    mut spawner: ResMut<WaveSpawner>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    let rng = &mut game_rng.rng;
+    let bounds = bounds.wrap();
+
+    spawner.pending.retain_mut(|(delay, group)| {
+        delay.tick(time.delta());
+        if !delay.is_finished() {
+            return true;
+        }
+
+        for _ in 0..group.count {
+            let pos = match group.edge {
+                Some(edge) => edge.random_point(rng, bounds),
+                None => random_edge_position(rng, bounds),
+            };
+
+            // Head roughly toward the middle of the screen
+            let to_center = -pos.normalize_or_zero();
+            let rotation = f32::atan2(-to_center.x, to_center.y) + rng.random_range(-0.5..0.5);
+            let speed = rng.random_range(group.min_speed..=group.max_speed);
+            let angvel = rng.random_range(-PI..PI);
+            cmds.run_system_cached_with(
+                spawn_asteroid,
+                AsteroidConfig {
+                    location: pos,
+                    heading: rotation,
+                    speed,
+                    angvel,
+                    size: group.tier,
+                    group: None,
+                },
+            );
+        }
+        false
+    });
+}
+
+/// Drops the wave's mega asteroid in from above the top edge once its timer is up
+pub fn spawn_mega_rock(
+    time: Res<Time>,
+    bounds: Res<PlayBounds>,
+    mut spawner: ResMut<WaveSpawner>,
+    mut game_rng: ResMut<GameRng>,
+    mut cmds: Commands,
+) {
+    let Some(timer) = spawner.mega_spawn_timer.as_mut() else {
+        return;
+    };
+    if !timer.tick(time.delta()).is_finished() {
+        return;
+    }
+    spawner.mega_spawn_timer = None;
+
+    let rng = &mut game_rng.rng;
+    cmds.run_system_cached_with(
+        spawn_asteroid,
+        AsteroidConfig {
+            location: Edge::Top.random_point(rng, bounds.wrap()),
+            // Straight down, give or take
+            heading: PI + rng.random_range(-0.3..0.3),
+            speed: MEGA_SPEED,
+            angvel: rng.random_range(-0.5..0.5),
+            size: AsteroidSize::Mega,
+            group: None,
+        },
+    );
+}
+
+/// Pays out the wave's score bonus once the last of its asteroids is gone, and announces a
+/// boss win
+pub fn award_wave_bonus(
+    asteroids: Query<(), With<Asteroid>>,
+    mut spawner: ResMut<WaveSpawner>,
+    mut game_stats: ResMut<GameStats>,
+    mut boss_defeated: MessageWriter<BossDefeated>,
+) {
+    if !asteroids.is_empty() || !spawner.is_idle() || (spawner.score_bonus == 0 && !spawner.boss) {
+        return;
+    }
+
+    game_stats.award(spawner.score_bonus);
+    spawner.score_bonus = 0;
+
+    if spawner.boss {
+        spawner.boss = false;
+        boss_defeated.write(BossDefeated {
+            wave: game_stats.wave,
+        });
+    }
+}
+
+/// Waves only end once every asteroid is destroyed, so none of them may drift away for good
+pub fn wrap_wave_asteroids(asteroids: Query<Entity, Added<Asteroid>>, mut cmds: Commands) {
+    for asteroid in asteroids {
+        cmds.entity(asteroid).insert(ScreenWrap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset_source::read_text;
+
+    #[test]
+    fn wave_files_parse() {
+        for path in WAVE_FILES {
+            let text = read_text(path).unwrap();
+            if let Err(e) = ron::from_str::<WaveDefinition>(&text) {
+                panic!("{path}: {e}");
+            }
+        }
+    }
+
+    #[test]
+    fn missing_fields_are_named() {
+        let error = ron::from_str::<WaveDefinition>("(groups: [(tier: large, count: 3)])")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("min_speed"), "{error}");
+    }
+}
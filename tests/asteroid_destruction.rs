@@ -0,0 +1,157 @@
+mod common;
+
+use std::time::Duration;
+
+use bella_roids::{
+    Asteroid, AsteroidConfig, AsteroidSize, FRAGMENT_GRACE_SECS, GameAssets, GameRng, GameStats,
+    METEOR_SPRITES,
+    config::GameConfig,
+    destroy_asteroids,
+    events::{DestroyAsteroid, events_plugin},
+    mines::{MINE_ARM_SECS, Mine, detonate_mines},
+    palette::Palette,
+    physics::{CollisionEvent, RadialImpulse, detect_collisions, expire_collision_ignores},
+    settings::Settings,
+    spawn_asteroid,
+};
+use bevy::prelude::*;
+
+#[test]
+fn blast_destroys_mixed_tiers_once_each() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, common::count_warnings()));
+    app.add_plugins(events_plugin);
+    app.add_message::<RadialImpulse>();
+    app.init_resource::<GameConfig>();
+    app.init_resource::<Settings>();
+    app.init_resource::<Palette>();
+    app.init_resource::<GameStats>();
+    app.insert_resource(GameRng::seeded(0));
+    app.insert_resource(GameAssets {
+        meteors: vec![Handle::default(); METEOR_SPRITES.len()],
+        ..default()
+    });
+    app.add_systems(Update, (detonate_mines, destroy_asteroids).chain());
+
+    let sizes = [
+        AsteroidSize::Large,
+        AsteroidSize::Large,
+        AsteroidSize::Medium,
+        AsteroidSize::Small,
+    ];
+    for (i, size) in sizes.into_iter().enumerate() {
+        app.world_mut()
+            .run_system_cached_with(
+                spawn_asteroid,
+                AsteroidConfig {
+                    location: Vec2::from_angle(i as f32) * 50.0,
+                    size,
+                    ..default()
+                },
+            )
+            .unwrap();
+    }
+    let originals: Vec<Entity> = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(app.world())
+        .collect();
+    assert_eq!(originals.len(), 4);
+
+    let mut arm_timer = Timer::from_seconds(MINE_ARM_SECS, TimerMode::Once);
+    arm_timer.tick(Duration::from_secs_f32(MINE_ARM_SECS));
+    app.world_mut()
+        .spawn((Mine { arm_timer }, Transform::default()));
+
+    // A laser finishing off one of them in the same frame must not count it twice
+    app.world_mut().write_message(DestroyAsteroid {
+        asteroid: originals[0],
+        critical: false,
+    });
+
+    app.update();
+
+    let config = GameConfig::default();
+    let tiers = sizes.map(|size| config.asteroids.get(size));
+    let score: u64 = tiers.iter().map(|tier| u64::from(tier.score)).sum();
+    let fragments: u32 = sizes
+        .iter()
+        .zip(&tiers)
+        .filter(|(size, _)| size.child().is_some())
+        .map(|(_, tier)| tier.split_count)
+        .sum();
+
+    assert_eq!(app.world().resource::<GameStats>().score, score);
+
+    let remaining: Vec<Entity> = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(app.world())
+        .collect();
+    assert!(originals.iter().all(|ent| !remaining.contains(ent)));
+    assert_eq!(remaining.len(), fragments as usize);
+
+    assert_eq!(common::warnings(), 0);
+}
+
+/// Collisions this frame between two asteroids
+fn asteroid_collisions(app: &mut App) -> usize {
+    let asteroids: Vec<Entity> = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(app.world())
+        .collect();
+    app.world()
+        .resource::<Messages<CollisionEvent>>()
+        .iter_current_update_messages()
+        .filter(|CollisionEvent(a, b)| asteroids.contains(a) && asteroids.contains(b))
+        .count()
+}
+
+#[test]
+fn fresh_fragments_pass_through_each_other() {
+    let mut app = common::headless_game(0);
+    // Nothing moves, so the fragments stay piled up where they split
+    app.add_systems(
+        Update,
+        (
+            destroy_asteroids,
+            expire_collision_ignores,
+            detect_collisions,
+        )
+            .chain(),
+    );
+    app.update();
+
+    app.world_mut()
+        .run_system_cached_with(
+            spawn_asteroid,
+            AsteroidConfig {
+                location: Vec2::new(300.0, 300.0),
+                size: AsteroidSize::Large,
+                ..default()
+            },
+        )
+        .unwrap();
+    let asteroid = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .single(app.world())
+        .unwrap();
+    app.world_mut().write_message(DestroyAsteroid {
+        asteroid,
+        critical: false,
+    });
+
+    // Frames are a tenth of a second, the last of these is still inside the grace period
+    let frames = (FRAGMENT_GRACE_SECS * 10.0) as u32;
+    for _ in 0..frames {
+        app.update();
+        assert_eq!(asteroid_collisions(&mut app), 0);
+    }
+
+    // Siblings that are still overlapping afterwards collide as normal
+    app.update();
+    app.update();
+    assert!(asteroid_collisions(&mut app) > 0);
+}
@@ -0,0 +1,57 @@
+use bella_roids::{
+    AsteroidConfig, AsteroidSize, GameAssets, GameRng, METEOR_SPRITES, MeteorVariant,
+    config::GameConfig, events::events_plugin, palette::Palette, settings::Settings,
+    spawn_asteroid,
+};
+use bevy::{platform::collections::HashSet, prelude::*};
+
+/// Every big meteor sprite used to be loaded, but spawning only ever picked from the first three
+#[test]
+fn every_large_sprite_is_used() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    app.init_asset::<Image>();
+    app.add_plugins(events_plugin);
+    app.init_resource::<GameConfig>();
+    app.init_resource::<Settings>();
+    app.init_resource::<Palette>();
+    app.insert_resource(GameRng::seeded(0));
+
+    let meteors: Vec<Handle<Image>> = METEOR_SPRITES
+        .iter()
+        .map(|_| {
+            app.world_mut()
+                .resource_mut::<Assets<Image>>()
+                .add(Image::default())
+        })
+        .collect();
+    app.insert_resource(GameAssets {
+        meteors: meteors.clone(),
+        ..default()
+    });
+
+    for _ in 0..64 {
+        app.world_mut()
+            .run_system_cached_with(
+                spawn_asteroid,
+                AsteroidConfig {
+                    size: AsteroidSize::Large,
+                    ..default()
+                },
+            )
+            .unwrap();
+    }
+
+    let mut used = HashSet::new();
+    for (variant, sprite) in app
+        .world_mut()
+        .query::<(&MeteorVariant, &Sprite)>()
+        .iter(app.world())
+    {
+        assert_eq!(sprite.image, meteors[variant.0]);
+        used.insert(variant.0);
+    }
+    let large = &GameConfig::default().asteroids.large.sprites;
+    assert_eq!(used, large.iter().copied().collect());
+    assert_eq!(used.len(), 4);
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use bella_roids::{
+    Asteroid, AsteroidSize, GameStats,
+    belt::{AsteroidBelt, BeltPhase, Edge, tick_asteroid_belt},
+    config::GameConfig,
+    destroy_asteroids,
+    events::DestroyAsteroid,
+};
+use bevy::prelude::*;
+
+fn in_shower(app: &App) -> bool {
+    matches!(
+        app.world().resource::<AsteroidBelt>().phase,
+        BeltPhase::Warning { .. } | BeltPhase::Shower { .. }
+    )
+}
+
+/// Most of a shower is shot down after its last rock has streamed in
+#[test]
+fn shower_kills_score_double() {
+    let mut app = common::headless_game(0);
+    app.add_systems(Update, (tick_asteroid_belt, destroy_asteroids).chain());
+    app.update();
+
+    // Skip straight to the end of the warning
+    app.world_mut().resource_mut::<AsteroidBelt>().phase = BeltPhase::Warning {
+        timer: Timer::from_seconds(0.05, TimerMode::Once),
+        edge: Edge::Top,
+    };
+    while in_shower(&app) {
+        app.update();
+    }
+
+    let (asteroid, size) = app
+        .world_mut()
+        .query_filtered::<(Entity, &AsteroidSize), With<Asteroid>>()
+        .iter(app.world())
+        .find(|(_, size)| **size == AsteroidSize::Small)
+        .map(|(ent, size)| (ent, *size))
+        .unwrap();
+    app.world_mut().resource_mut::<GameStats>().score = 0;
+    app.world_mut().write_message(DestroyAsteroid {
+        asteroid,
+        critical: false,
+    });
+    app.update();
+
+    let config = app.world().resource::<GameConfig>().clone();
+    let score =
+        f64::from(config.asteroids.get(size).score) * f64::from(config.belt.score_multiplier);
+    assert_eq!(
+        app.world().resource::<GameStats>().score,
+        score.round() as u64
+    );
+
+    // Once the tail is over kills are back to normal
+    let frames = (config.belt.score_tail_secs * 10.0).ceil() as u32;
+    for _ in 0..frames {
+        app.update();
+    }
+    assert!(matches!(
+        app.world().resource::<AsteroidBelt>().phase,
+        BeltPhase::Idle
+    ));
+    assert_eq!(app.world().resource::<GameStats>().belt_multiplier, 1.0);
+}
@@ -0,0 +1,110 @@
+//! Setup shared by the integration tests. Each test binary only uses part of it.
+#![allow(dead_code)]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use bella_roids::{
+    GameMode, GameRng, GameStats, HudRoot, LaserShot, ShipInput,
+    belt::AsteroidBelt,
+    comet::{CometTimer, CometTrail},
+    config::GameConfig,
+    credits::Credits,
+    events::events_plugin,
+    frenzy::Frenzy,
+    load_assets,
+    locale::Locale,
+    mines::Shockwave,
+    mutators::RunModifiers,
+    palette::Palette,
+    physics::{CollisionBudget, CollisionEvent, RadialImpulse},
+    playfield::{PlayBounds, PlayField},
+    pool::Pool,
+    settings::{Language, Settings},
+    setup_scene,
+    shop::Shop,
+    swarm::NextSwarmId,
+    tutorial::Tutorial,
+    waves::WaveSpawner,
+};
+use bevy::{
+    log::{
+        Level, LogPlugin,
+        tracing::{Event, Subscriber},
+        tracing_subscriber::{Layer, layer::Context},
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+    window::PrimaryWindow,
+};
+
+/// An endless run set up headless, with time stepped a tenth of a second per frame. Only the
+/// scene is spawned at startup, every test adds the `Update` systems it needs.
+pub fn headless_game(seed: u64) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    app.init_asset::<Image>();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+
+    app.add_plugins(events_plugin);
+    app.add_message::<CollisionEvent>();
+    app.add_message::<RadialImpulse>();
+    app.init_resource::<GameConfig>();
+    app.init_resource::<Settings>();
+    app.init_resource::<Palette>();
+    app.init_resource::<Credits>();
+    app.init_resource::<Tutorial>();
+    app.init_resource::<GameStats>();
+    app.init_resource::<ShipInput>();
+    app.init_resource::<Pool<LaserShot>>();
+    app.init_resource::<Pool<Shockwave>>();
+    app.init_resource::<Pool<CometTrail>>();
+    app.insert_resource(Locale::load(Language::English));
+    app.init_resource::<CollisionBudget>();
+    app.init_resource::<NextSwarmId>();
+    app.insert_resource(GameMode::Endless);
+    app.insert_resource(GameRng::seeded(seed));
+
+    // Everything a death resets along with the score
+    app.init_resource::<Shop>();
+    app.init_resource::<WaveSpawner>();
+    app.init_resource::<Frenzy>();
+    app.init_resource::<AsteroidBelt>();
+    app.init_resource::<CometTimer>();
+    app.init_resource::<RunModifiers>();
+
+    app.insert_resource(PlayBounds::new(PlayField::Classic, &Window::default()));
+    app.world_mut().spawn((Window::default(), PrimaryWindow));
+    app.world_mut().spawn((HudRoot, Node::default()));
+    app.add_systems(Startup, (load_assets, setup_scene).chain());
+    app
+}
+
+/// Warnings and errors logged anywhere in the test binary
+static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountWarnings;
+
+impl<S: Subscriber> Layer<S> for CountWarnings {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        if *event.metadata().level() <= Level::WARN {
+            WARNINGS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Logging that counts every warning and error, read back with [`warnings`]
+pub fn count_warnings() -> LogPlugin {
+    LogPlugin {
+        custom_layer: |_| Some(Box::new(CountWarnings)),
+        ..default()
+    }
+}
+
+pub fn warnings() -> usize {
+    WARNINGS.load(Ordering::SeqCst)
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use bella_roids::{
+    GameStats, ShipInput, control_ship,
+    daily::DailyChallenge,
+    destroy_asteroids, game_tick, handle_collisions,
+    physics::{apply_radial_impulses, apply_velocity, detect_collisions, screen_wrap},
+    telegraph::tick_spawn_telegraphs,
+};
+use bevy::prelude::*;
+
+/// Plays a headless daily run for `frames` tenths of a second with the same scripted inputs
+/// every time: spinning, thrusting now and then and firing every few frames
+fn daily_score(challenge: DailyChallenge, frames: u32) -> u64 {
+    let mut app = common::headless_game(challenge.seed());
+    app.add_systems(
+        Update,
+        (
+            game_tick,
+            tick_spawn_telegraphs,
+            control_ship,
+            (
+                apply_radial_impulses,
+                apply_velocity,
+                screen_wrap,
+                detect_collisions,
+            )
+                .chain(),
+            handle_collisions,
+            destroy_asteroids,
+        )
+            .chain(),
+    );
+
+    for frame in 0..frames {
+        *app.world_mut().resource_mut::<ShipInput>() = ShipInput {
+            rotate_left: true,
+            thrust: frame % 20 < 5,
+            fire: frame % 3 == 0,
+            ..default()
+        };
+        app.update();
+    }
+
+    app.world().resource::<GameStats>().score
+}
+
+#[test]
+fn same_daily_same_inputs_same_score() {
+    let challenge = DailyChallenge::from_unix_days(20_742);
+
+    let first = daily_score(challenge, 600);
+    assert!(first > 0, "the scripted run never scored");
+    assert_eq!(first, daily_score(challenge, 600));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use bella_roids::{
+    Asteroid, AsteroidConfig, GameCleanup, PlayerShip, effects::Invincible, handle_collisions,
+    physics::CollisionEvent, spawn_asteroid,
+};
+use bevy::prelude::*;
+
+#[test]
+fn death_takes_ship_children_along() {
+    let mut app = common::headless_game(0);
+    app.add_plugins(common::count_warnings());
+    app.add_systems(Update, handle_collisions);
+    app.update();
+
+    let ship = app
+        .world_mut()
+        .query_filtered::<Entity, With<PlayerShip>>()
+        .single(app.world())
+        .unwrap();
+    app.world_mut().entity_mut(ship).remove::<Invincible>();
+    // One child swept up on its own as well, one only reachable through the ship
+    let children = [
+        app.world_mut().spawn((GameCleanup, ChildOf(ship))).id(),
+        app.world_mut()
+            .spawn((Transform::default(), ChildOf(ship)))
+            .id(),
+    ];
+    app.world_mut()
+        .run_system_cached_with(spawn_asteroid, AsteroidConfig::default())
+        .unwrap();
+    let asteroid = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(app.world())
+        .next()
+        .unwrap();
+
+    // Images can't load without a loader, only what the death logs counts
+    let warnings = common::warnings();
+    app.world_mut()
+        .write_message(CollisionEvent(ship, asteroid));
+    app.update();
+
+    assert!(app.world().get_entity(ship).is_err());
+    for child in children {
+        assert!(app.world().get_entity(child).is_err());
+    }
+    let orphans = app
+        .world_mut()
+        .query::<&ChildOf>()
+        .iter(app.world())
+        .filter(|parent| app.world().get_entity(parent.parent()).is_err())
+        .count();
+    assert_eq!(orphans, 0);
+    assert_eq!(common::warnings(), warnings);
+}
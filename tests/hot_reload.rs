@@ -0,0 +1,92 @@
+//! Sprites keep the handle they were spawned with, and a reload replaces the image behind that
+//! handle in place, so live asteroids pick up an edited texture without being touched.
+#![cfg(feature = "hot-reload")]
+
+use std::{
+    fs,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use bella_roids::{
+    Asteroid, AsteroidConfig, GameAssets, GameRng, METEOR_SPRITES, config::GameConfig,
+    events::events_plugin, palette::Palette, settings::Settings, spawn_asteroid,
+};
+use bevy::{
+    image::{CompressedImageFormats, ImageLoader},
+    prelude::*,
+};
+
+const RED: &str = "tests/fixtures/meteor_red.png";
+const BLUE: &str = "tests/fixtures/meteor_blue.png";
+/// The file watcher waits for writes to settle before reporting them
+const RELOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Color of the texture drawn for `asteroid`, once it has loaded
+fn sprite_color(app: &App, asteroid: Entity) -> Option<Srgba> {
+    let sprite = app.world().get::<Sprite>(asteroid)?;
+    let image = app.world().resource::<Assets<Image>>().get(&sprite.image)?;
+    image.get_color_at(0, 0).ok().map(|color| color.to_srgba())
+}
+
+/// Steps the app in real time until `asteroid` is drawn in `color`
+fn wait_for_color(app: &mut App, asteroid: Entity, color: Srgba) {
+    let start = Instant::now();
+    while sprite_color(app, asteroid) != Some(color) {
+        assert!(
+            start.elapsed() < RELOAD_TIMEOUT,
+            "sprite is {:?}, expected {color:?}",
+            sprite_color(app, asteroid)
+        );
+        thread::sleep(Duration::from_millis(20));
+        app.update();
+    }
+}
+
+#[test]
+fn swapped_meteor_texture_reaches_live_asteroids() {
+    let dir = std::env::temp_dir().join(format!("bella_roids_hot_reload_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let meteor = dir.join("meteor.png");
+    fs::copy(Path::new(env!("CARGO_MANIFEST_DIR")).join(RED), &meteor).unwrap();
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: dir.to_string_lossy().into_owned(),
+            watch_for_changes_override: Some(true),
+            ..default()
+        },
+        ImagePlugin::default(),
+    ));
+    // Normally the renderer registers the loader, and there isn't one here
+    app.register_asset_loader(ImageLoader::new(CompressedImageFormats::NONE));
+    app.add_plugins(events_plugin);
+    app.init_resource::<GameConfig>();
+    app.init_resource::<Settings>();
+    app.init_resource::<Palette>();
+    app.insert_resource(GameRng::seeded(0));
+
+    let texture: Handle<Image> = app.world().resource::<AssetServer>().load("meteor.png");
+    app.insert_resource(GameAssets {
+        meteors: vec![texture; METEOR_SPRITES.len()],
+        ..default()
+    });
+    app.world_mut()
+        .run_system_cached_with(spawn_asteroid, AsteroidConfig::default())
+        .unwrap();
+    let asteroid = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .single(app.world())
+        .unwrap();
+
+    wait_for_color(&mut app, asteroid, Srgba::rgb(1.0, 0.0, 0.0));
+
+    fs::copy(Path::new(env!("CARGO_MANIFEST_DIR")).join(BLUE), &meteor).unwrap();
+    wait_for_color(&mut app, asteroid, Srgba::rgb(0.0, 0.0, 1.0));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
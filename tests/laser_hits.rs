@@ -0,0 +1,95 @@
+mod common;
+
+use bella_roids::{
+    Asteroid, AsteroidConfig, AsteroidSize, GameStats, LaserShot,
+    config::GameConfig,
+    destroy_asteroids, handle_collisions,
+    physics::{CollisionEvent, Disabled},
+    spawn_asteroid,
+};
+use bevy::prelude::*;
+
+/// A run where collisions are written by hand and resolved the same frame
+fn collision_game() -> App {
+    let mut app = common::headless_game(0);
+    // Every hit is a plain one, so a kill is worth exactly its tier's score
+    app.world_mut()
+        .resource_mut::<GameConfig>()
+        .combat
+        .crit_chance = 0.0;
+    app.add_systems(Update, (handle_collisions, destroy_asteroids).chain());
+    app.update();
+    app.world_mut().resource_mut::<GameStats>().score = 0;
+    app
+}
+
+fn spawn_small_asteroid(app: &mut App, location: Vec2) -> Entity {
+    app.world_mut()
+        .run_system_cached_with(
+            spawn_asteroid,
+            AsteroidConfig {
+                location,
+                size: AsteroidSize::Small,
+                ..default()
+            },
+        )
+        .unwrap();
+    app.world_mut()
+        .query_filtered::<(Entity, &Transform), With<Asteroid>>()
+        .iter(app.world())
+        .find(|(_, tsf)| tsf.translation.xy() == location)
+        .map(|(ent, _)| ent)
+        .unwrap()
+}
+
+/// A laser doesn't need to be fired to be hit, only to be a [`LaserShot`] somewhere
+fn spawn_laser(app: &mut App, location: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((LaserShot, Transform::from_translation(location.extend(0.0))))
+        .id()
+}
+
+fn alive(app: &App, asteroid: Entity) -> bool {
+    app.world().get_entity(asteroid).is_ok()
+}
+
+fn small_score(app: &App) -> u64 {
+    let config = app.world().resource::<GameConfig>();
+    u64::from(config.asteroids.get(AsteroidSize::Small).score)
+}
+
+#[test]
+fn one_laser_over_two_asteroids_destroys_one() {
+    let mut app = collision_game();
+    let first = spawn_small_asteroid(&mut app, Vec2::new(200.0, 200.0));
+    let second = spawn_small_asteroid(&mut app, Vec2::new(210.0, 200.0));
+    let laser = spawn_laser(&mut app, Vec2::new(205.0, 200.0));
+
+    app.world_mut().write_message(CollisionEvent(laser, first));
+    app.world_mut().write_message(CollisionEvent(second, laser));
+    app.update();
+
+    assert!(!alive(&app, first));
+    assert!(alive(&app, second));
+    assert!(app.world().entity(laser).contains::<Disabled>());
+    assert_eq!(app.world().resource::<GameStats>().score, small_score(&app));
+}
+
+#[test]
+fn two_lasers_over_one_asteroid_spend_one() {
+    let mut app = collision_game();
+    let asteroid = spawn_small_asteroid(&mut app, Vec2::new(200.0, 200.0));
+    let first = spawn_laser(&mut app, Vec2::new(195.0, 200.0));
+    let second = spawn_laser(&mut app, Vec2::new(205.0, 200.0));
+
+    app.world_mut()
+        .write_message(CollisionEvent(first, asteroid));
+    app.world_mut()
+        .write_message(CollisionEvent(asteroid, second));
+    app.update();
+
+    assert!(!alive(&app, asteroid));
+    assert!(app.world().entity(first).contains::<Disabled>());
+    assert!(!app.world().entity(second).contains::<Disabled>());
+    assert_eq!(app.world().resource::<GameStats>().score, small_score(&app));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use bella_roids::{
+    Asteroid, AsteroidConfig, GameStats, LaserShot, PlayerShip, ShipInput, control_ship,
+    credits::Credits,
+    destroy_asteroids,
+    effects::Invincible,
+    game_tick, handle_collisions,
+    physics::{CollisionEvent, Disabled, apply_velocity, detect_collisions, screen_wrap},
+    spawn_asteroid,
+    telegraph::tick_spawn_telegraphs,
+};
+use bevy::prelude::*;
+
+fn headless_game() -> App {
+    let mut app = common::headless_game(0);
+    app.add_systems(
+        Update,
+        (
+            game_tick,
+            tick_spawn_telegraphs,
+            control_ship,
+            (apply_velocity, screen_wrap, detect_collisions).chain(),
+            handle_collisions,
+            destroy_asteroids,
+        )
+            .chain(),
+    );
+    app
+}
+
+fn active_lasers(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), (With<LaserShot>, Without<Disabled>)>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn game_keeps_running_without_a_ship() {
+    let mut app = headless_game();
+    app.update();
+
+    let ship = app
+        .world_mut()
+        .query_filtered::<Entity, With<PlayerShip>>()
+        .single(app.world())
+        .unwrap();
+    app.world_mut().despawn(ship);
+    app.world_mut()
+        .run_system_cached_with(spawn_asteroid, AsteroidConfig::default())
+        .unwrap();
+    let asteroid = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(app.world())
+        .next()
+        .unwrap();
+
+    *app.world_mut().resource_mut::<ShipInput>() = ShipInput {
+        thrust: true,
+        rotate_left: true,
+        fire: true,
+        ..default()
+    };
+    let lasers = active_lasers(&mut app);
+
+    for _ in 0..10 {
+        // A collision still naming the dead ship must not end the run again
+        app.world_mut()
+            .write_message(CollisionEvent(ship, asteroid));
+        app.update();
+    }
+
+    assert_eq!(active_lasers(&mut app), lasers);
+    assert!(app.world().get_entity(asteroid).is_ok());
+}
+
+#[test]
+fn death_starts_a_fresh_score() {
+    let mut app = headless_game();
+    app.update();
+
+    let ship = app
+        .world_mut()
+        .query_filtered::<Entity, With<PlayerShip>>()
+        .single(app.world())
+        .unwrap();
+    app.world_mut().entity_mut(ship).remove::<Invincible>();
+    app.world_mut()
+        .run_system_cached_with(spawn_asteroid, AsteroidConfig::default())
+        .unwrap();
+    let asteroid = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(app.world())
+        .next()
+        .unwrap();
+
+    {
+        let mut stats = app.world_mut().resource_mut::<GameStats>();
+        stats.score = 500;
+        stats.spawn_rate = 0.8;
+        stats.belt_multiplier = 2.0;
+    }
+    app.world_mut().resource_mut::<Credits>().0 = 30;
+    app.world_mut()
+        .write_message(CollisionEvent(ship, asteroid));
+    app.update();
+
+    let stats = app.world().resource::<GameStats>();
+    assert_eq!(stats.score, 0);
+    assert_eq!(stats.spawn_rate, GameStats::default().spawn_rate);
+    assert_eq!(stats.belt_multiplier, 1.0);
+    assert_eq!(app.world().resource::<Credits>().0, 0);
+    assert!(app.world().get_entity(ship).is_err());
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use std::f32::consts::TAU;
+
+use bella_roids::{
+    Asteroid, AsteroidConfig, LaserShot, LaserShotConfig, PlayerShip,
+    effects::{Invincible, spawn_impacts, tick_lifetimes},
+    events::LaserHit,
+    handle_collisions,
+    mines::{Shockwave, expand_shockwaves},
+    physics::{CollisionEvent, apply_velocity},
+    pool::{Pool, pool_plugin},
+    release_offscreen_lasers, spawn_asteroid, spawn_laser_shot,
+};
+use bevy::prelude::*;
+
+/// Shots fired and hits scored every frame, fanned out all the way around the ship
+const SHOTS_PER_FRAME: u32 = 12;
+
+/// A run where lasers fly off screen and hit flashes expire, nothing else moves on its own
+fn churning_game() -> App {
+    let mut app = common::headless_game(0);
+    app.add_plugins(pool_plugin);
+    app.add_systems(
+        Update,
+        (
+            handle_collisions,
+            apply_velocity,
+            release_offscreen_lasers,
+            spawn_impacts,
+            tick_lifetimes,
+            expand_shockwaves,
+        )
+            .chain(),
+    );
+    app.update();
+    app
+}
+
+/// Fires a full fan of lasers and leaves a hit flash for each, then steps a frame
+fn churn(app: &mut App) {
+    let shooter = app
+        .world_mut()
+        .query_filtered::<Entity, With<PlayerShip>>()
+        .single(app.world())
+        .unwrap_or(Entity::PLACEHOLDER);
+
+    for i in 0..SHOTS_PER_FRAME {
+        let heading = TAU * i as f32 / SHOTS_PER_FRAME as f32;
+        app.world_mut()
+            .run_system_cached_with(
+                spawn_laser_shot,
+                LaserShotConfig {
+                    origin: Vec2::ZERO,
+                    heading,
+                    inherited_linear: Vec2::ZERO,
+                    inherited_angular: 0.0,
+                    spread_angle: 0.0,
+                    shooter,
+                    charge: 0.0,
+                },
+            )
+            .unwrap();
+        app.world_mut().write_message(LaserHit {
+            position: Vec2::from_angle(heading) * 100.0,
+            critical: i % 4 == 0,
+        });
+    }
+    app.update();
+}
+
+fn entity_count(app: &App) -> u32 {
+    app.world().entities().len()
+}
+
+fn count<T: Component>(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), With<T>>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn entity_count_holds_over_thousands_of_shots() {
+    let mut app = churning_game();
+
+    // Long enough for the first lasers to leave the screen and come back out of the pool
+    for _ in 0..100 {
+        churn(&mut app);
+    }
+    let entities = entity_count(&app);
+
+    // Another 4800 shots and as many hit flashes
+    for _ in 0..400 {
+        churn(&mut app);
+        assert_eq!(entity_count(&app), entities);
+    }
+
+    // Some of both were parked and waiting at the end of every frame
+    assert!(!app.world().resource::<Pool<LaserShot>>().is_empty());
+    assert!(!app.world().resource::<Pool<Shockwave>>().is_empty());
+}
+
+#[test]
+fn pools_stay_stable_across_a_death() {
+    let mut app = churning_game();
+
+    for _ in 0..100 {
+        churn(&mut app);
+    }
+    let lasers = count::<LaserShot>(&mut app);
+    let shockwaves = count::<Shockwave>(&mut app);
+
+    let ship = app
+        .world_mut()
+        .query_filtered::<Entity, With<PlayerShip>>()
+        .single(app.world())
+        .unwrap();
+    app.world_mut().entity_mut(ship).remove::<Invincible>();
+    app.world_mut()
+        .run_system_cached_with(spawn_asteroid, AsteroidConfig::default())
+        .unwrap();
+    let asteroid = app
+        .world_mut()
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(app.world())
+        .next()
+        .unwrap();
+    // Lasers and flashes released this frame are swept along with the rest of the run
+    app.world_mut()
+        .write_message(CollisionEvent(ship, asteroid));
+    churn(&mut app);
+
+    for _ in 0..100 {
+        churn(&mut app);
+    }
+    let entities = entity_count(&app);
+    for _ in 0..100 {
+        churn(&mut app);
+    }
+    assert_eq!(entity_count(&app), entities);
+    assert_eq!(count::<LaserShot>(&mut app), lasers);
+    assert_eq!(count::<Shockwave>(&mut app), shockwaves);
+}
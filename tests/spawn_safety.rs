@@ -0,0 +1,178 @@
+mod common;
+
+use bella_roids::{
+    Asteroid, GameStats, PlayerShip,
+    config::GameConfig,
+    game_tick,
+    physics::CircleCollider,
+    playfield::{PlayBounds, PlayField},
+    respawn::{PendingRespawn, RESPAWN_SAFE_RADIUS, place_new_ships},
+    spawn_count,
+    swarm::FlockMember,
+    telegraph::tick_spawn_telegraphs,
+};
+use bevy::{platform::collections::HashSet, prelude::*};
+use rand::{SeedableRng, rngs::StdRng};
+
+/// Runs endless mode spawning headless, with time stepped a tenth of a second per frame
+fn spawning_app(seed: u64) -> App {
+    let mut app = common::headless_game(seed);
+    // Spawning as fast as the config allows, so there are plenty of asteroids to check
+    app.insert_resource(GameStats {
+        spawn_rate: 100.0,
+        ..default()
+    });
+    app.add_systems(Update, (game_tick, tick_spawn_telegraphs));
+    app
+}
+
+#[test]
+fn asteroids_never_spawn_on_the_ship() {
+    for seed in 0..5 {
+        let mut app = spawning_app(seed);
+        let mut seen = HashSet::new();
+
+        for _ in 0..100 {
+            app.update();
+
+            let world = app.world_mut();
+            let ship = world
+                .query_filtered::<(&Transform, &CircleCollider), With<PlayerShip>>()
+                .single(world)
+                .map(|(tsf, collider)| (tsf.translation.xy(), collider.radius))
+                .unwrap();
+
+            // Nothing moves in this app, so a new asteroid is still where it spawned
+            let mut asteroids =
+                world.query_filtered::<(Entity, &Transform, &CircleCollider), With<Asteroid>>();
+            for (ent, tsf, collider) in asteroids.iter(world) {
+                if !seen.insert(ent) {
+                    continue;
+                }
+
+                let distance = tsf.translation.xy().distance(ship.0);
+                assert!(
+                    distance > collider.radius + ship.1,
+                    "seed {seed}: asteroid spawned {distance} away from the ship"
+                );
+            }
+        }
+
+        assert!(!seen.is_empty(), "seed {seed}: no asteroids spawned");
+    }
+}
+
+#[test]
+fn asteroids_never_spawn_inside_each_other() {
+    for seed in 0..5 {
+        let mut app = spawning_app(seed);
+
+        for _ in 0..100 {
+            app.update();
+        }
+
+        // Nothing moves in this app, so every asteroid is still where it spawned. Swarms are
+        // packed together on purpose and left out.
+        let world = app.world_mut();
+        let asteroids: Vec<(Vec2, f32)> = world
+            .query_filtered::<(&Transform, &CircleCollider), (With<Asteroid>, Without<FlockMember>)>()
+            .iter(world)
+            .map(|(tsf, collider)| (tsf.translation.xy(), collider.radius))
+            .collect();
+        assert!(
+            asteroids.len() > 1,
+            "seed {seed}: too few asteroids spawned"
+        );
+
+        for (i, (pos, radius)) in asteroids.iter().enumerate() {
+            for (other, other_radius) in &asteroids[i + 1..] {
+                let distance = pos.distance(*other);
+                assert!(
+                    distance > radius + other_radius,
+                    "seed {seed}: asteroids spawned {distance} apart"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn ship_respawns_clear_of_an_asteroid_on_the_center() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(PlayBounds::new(PlayField::Classic, &Window::default()));
+    app.add_systems(Update, place_new_ships);
+
+    let blocker = CircleCollider { radius: 60.0 };
+    app.world_mut()
+        .spawn((Asteroid, blocker, Transform::default()));
+    let ship = app
+        .world_mut()
+        .spawn((
+            PlayerShip::default(),
+            Transform::default(),
+            Visibility::default(),
+        ))
+        .id();
+
+    app.update();
+
+    let world = app.world();
+    assert!(
+        world.get::<PendingRespawn>(ship).is_none(),
+        "nowhere to respawn"
+    );
+    let distance = world
+        .get::<Transform>(ship)
+        .unwrap()
+        .translation
+        .xy()
+        .length();
+    assert!(
+        distance >= RESPAWN_SAFE_RADIUS + blocker.radius,
+        "ship respawned {distance} from the asteroid"
+    );
+}
+
+#[test]
+fn spawn_rate_holds_at_any_frame_rate() {
+    const SECS: f32 = 10_000.0;
+    const RATE: f32 = 0.5;
+
+    for fps in [30.0, 60.0, 144.0] {
+        let mut rng = StdRng::seed_from_u64(7);
+        let dt = 1.0 / fps;
+        let frames = (SECS * fps) as u32;
+        let spawned: u32 = (0..frames).map(|_| spawn_count(RATE, dt, &mut rng)).sum();
+
+        let expected = RATE * SECS;
+        let error = (spawned as f32 - expected).abs() / expected;
+        assert!(
+            error < 0.03,
+            "{fps} fps: {spawned} spawns, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn frame_spike_spawns_everything_it_missed() {
+    let mut rng = StdRng::seed_from_u64(7);
+    // Four and a bit spawns were due during the one long frame
+    for _ in 0..100 {
+        let spawned = spawn_count(2.0, 2.1, &mut rng);
+        assert!((4..=5).contains(&spawned), "{spawned}");
+    }
+}
+
+#[test]
+fn spawn_rate_ramps_up_to_the_max() {
+    let spawning = GameConfig::default().spawning;
+    let base = spawning.spawn_rate;
+    assert_eq!(spawning.ramped_rate(base, 0.0), base);
+    assert_eq!(spawning.ramped_rate(base, 19.0), base);
+    assert_eq!(spawning.ramped_rate(base, 30.0), base * 3.0);
+    assert_eq!(
+        spawning.ramped_rate(base, 10_000.0),
+        spawning.max_spawn_rate
+    );
+}